@@ -0,0 +1,53 @@
+//! Compare the allocations made by `boxed` and `boxed_pooled` when a boxed future is rebuilt on
+//! every iteration of a loop.
+//!
+//! Run with `cargo +nightly bench --features allocator_api --bench boxed_pooled`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use completion::{completion_async_move, future, CompletionFutureExt};
+
+/// A global allocator that counts the allocations made through it.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITERATIONS: u64 = 100_000;
+
+/// Run `f` for every iteration, returning the number of allocations made and the time taken.
+fn measure(mut f: impl FnMut(u64) -> u64) -> (usize, Duration) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut total = 0_u64;
+    for i in 0..ITERATIONS {
+        total = total.wrapping_add(f(i));
+    }
+    let elapsed = start.elapsed();
+    assert_ne!(total, 1);
+    (ALLOCATIONS.load(Ordering::Relaxed) - allocations, elapsed)
+}
+
+fn main() {
+    let boxed = measure(|i| future::block_on(completion_async_move!([i; 4]).boxed())[0]);
+    let pooled = measure(|i| future::block_on(completion_async_move!([i; 4]).boxed_pooled())[0]);
+
+    println!("{} iterations", ITERATIONS);
+    println!("boxed:        {:>7} allocations, {:?}", boxed.0, boxed.1);
+    println!("boxed_pooled: {:>7} allocations, {:?}", pooled.0, pooled.1);
+    assert!(pooled.0 < boxed.0);
+}
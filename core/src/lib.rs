@@ -2,6 +2,7 @@
 //!
 //! See [completion](https://crates.io/crates/completion) for utilities based on this.
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -85,7 +86,7 @@ impl<F: CompletionFuture + Unpin + ?Sized> CompletionFuture for &'_ mut F {
     }
 }
 
-#[cfg(feature = "alloc")]
+#[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
 impl<F: CompletionFuture + Unpin + ?Sized> CompletionFuture for alloc::boxed::Box<F> {
     type Output = F::Output;
 
@@ -97,6 +98,22 @@ impl<F: CompletionFuture + Unpin + ?Sized> CompletionFuture for alloc::boxed::Bo
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<F, A> CompletionFuture for alloc::boxed::Box<F, A>
+where
+    F: CompletionFuture + Unpin + ?Sized,
+    A: core::alloc::Allocator,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut **self).poll(cx)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_cancel(cx)
+    }
+}
+
 impl<P> CompletionFuture for Pin<P>
 where
     P: Unpin + DerefMut,
@@ -124,6 +141,28 @@ impl<F: CompletionFuture> CompletionFuture for std::panic::AssertUnwindSafe<F> {
     }
 }
 
+/// An optional future.
+///
+/// [`None`] resolves immediately to [`None`], and [`Some`] resolves to [`Some`] of the inner
+/// future's output. Cancelling [`Some`] cancels the inner future, and cancelling [`None`] does
+/// nothing.
+impl<F: CompletionFuture> CompletionFuture for Option<F> {
+    type Output = Option<F::Output>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.as_pin_mut() {
+            Some(fut) => fut.poll(cx).map(Some),
+            None => Poll::Ready(None),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.as_pin_mut() {
+            Some(fut) => fut.poll_cancel(cx),
+            None => Poll::Ready(()),
+        }
+    }
+}
+
 macro_rules! derive_completion_future {
     ($([$($generics:tt)*] $t:ty,)*) => {
         $(
@@ -234,7 +273,7 @@ impl<S: CompletionStream + Unpin + ?Sized> CompletionStream for &'_ mut S {
     }
 }
 
-#[cfg(feature = "alloc")]
+#[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
 impl<S: CompletionStream + Unpin + ?Sized> CompletionStream for alloc::boxed::Box<S> {
     type Item = S::Item;
 
@@ -252,6 +291,28 @@ impl<S: CompletionStream + Unpin + ?Sized> CompletionStream for alloc::boxed::Bo
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<S, A> CompletionStream for alloc::boxed::Box<S, A>
+where
+    S: CompletionStream + Unpin + ?Sized,
+    A: core::alloc::Allocator,
+{
+    type Item = S::Item;
+
+    unsafe fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
 impl<P> CompletionStream for Pin<P>
 where
     P: Unpin + DerefMut,
@@ -284,3 +345,250 @@ impl<S: CompletionStream> CompletionStream for std::panic::AssertUnwindSafe<S> {
         self.0.size_hint()
     }
 }
+
+/// A sink of values, where each operation must be polled to completion.
+///
+/// This is the completion equivalent of the `Sink` trait from the futures crate. Values are sent
+/// to the sink by first waiting for it to be ready with [`poll_ready`], and then handing it the
+/// value with [`start_send`]. Since [`start_send`] is synchronous, ownership of a value is
+/// transferred to the sink all at once: cancelling an operation can never result in only part of
+/// a value being sent.
+///
+/// A completion sink has three states: running, cancelling and closed. Sinks initially start out
+/// in the running state, in which users may call [`poll_ready`], [`start_send`], [`poll_flush`]
+/// and [`poll_close`]. Once [`poll_close`] returns [`Poll::Ready`] the sink is closed.
+///
+/// At any time during the running state, users may call [`poll_cancel`] to initiate the cancelling
+/// state, which cancels the operation in progress. During this state, only [`poll_cancel`] should
+/// be called, and it can return [`Poll::Pending`] to continue the cancelling state or
+/// [`Poll::Ready`]`(())` to return to the running state.
+///
+/// [`poll_ready`]: Self::poll_ready
+/// [`start_send`]: Self::start_send
+/// [`poll_flush`]: Self::poll_flush
+/// [`poll_close`]: Self::poll_close
+/// [`poll_cancel`]: Self::poll_cancel
+#[must_use = "sinks do nothing unless you use them"]
+pub trait CompletionSink<Item> {
+    /// The type of value produced by the sink when an error occurs.
+    type Error;
+
+    /// Attempt to prepare the sink to receive a value, registering the current task for wakeup if
+    /// it is not ready yet.
+    ///
+    /// This must be called and return [`Poll::Ready`]`(`[`Ok`]`(()))` before each call to
+    /// [`start_send`](Self::start_send).
+    ///
+    /// # Safety
+    ///
+    /// Once this function has been called, the user **must not** drop or forget the sink until it
+    /// has returned [`Poll::Ready`], [`poll_cancel`](Self::poll_cancel) has returned
+    /// [`Poll::Ready`], or it has panicked.
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+
+    /// Begin sending a value to the sink.
+    ///
+    /// This must be preceded by a successful call to [`poll_ready`](Self::poll_ready). The value
+    /// is owned by the sink from this point on, but it may not actually be sent until the sink is
+    /// flushed.
+    ///
+    /// # Errors
+    ///
+    /// This fails if the sink can no longer accept values.
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error>;
+
+    /// Attempt to flush all the values sent to the sink, registering the current task for wakeup
+    /// if it has not finished flushing yet.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`poll_ready`](Self::poll_ready).
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+
+    /// Attempt to flush and close the sink, registering the current task for wakeup if it has not
+    /// finished closing yet.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`poll_ready`](Self::poll_ready).
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>>;
+
+    /// Attempt to cancel the operation in progress, registering the current task for wakeup if it
+    /// has not finished cancelling yet.
+    ///
+    /// Values that have already been passed to [`start_send`](Self::start_send) are not taken
+    /// back; they may or may not end up being sent.
+    ///
+    /// # Safety
+    ///
+    /// Once this function has been called, the user **must not** drop or forget the sink until it
+    /// has returned [`Poll::Ready`] or panicked.
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
+}
+
+impl<S: CompletionSink<Item> + Unpin + ?Sized, Item> CompletionSink<Item> for &'_ mut S {
+    type Error = S::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+    fn start_send(mut self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).start_send(item)
+    }
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_cancel(cx)
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
+impl<S: CompletionSink<Item> + Unpin + ?Sized, Item> CompletionSink<Item> for alloc::boxed::Box<S> {
+    type Error = S::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+    fn start_send(mut self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).start_send(item)
+    }
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_cancel(cx)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<S, A, Item> CompletionSink<Item> for alloc::boxed::Box<S, A>
+where
+    S: CompletionSink<Item> + Unpin + ?Sized,
+    A: core::alloc::Allocator,
+{
+    type Error = S::Error;
+
+    unsafe fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_ready(cx)
+    }
+    fn start_send(mut self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        Pin::new(&mut **self).start_send(item)
+    }
+    unsafe fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut **self).poll_close(cx)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_cancel(cx)
+    }
+}
+
+impl<P, Item> CompletionSink<Item> for Pin<P>
+where
+    P: Unpin + DerefMut,
+    P::Target: CompletionSink<Item>,
+{
+    type Error = <P::Target as CompletionSink<Item>>::Error;
+
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().as_mut().poll_ready(cx)
+    }
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.get_mut().as_mut().start_send(item)
+    }
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().as_mut().poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().as_mut().poll_close(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.get_mut().as_mut().poll_cancel(cx)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CompletionSink<T> for alloc::vec::Vec<T> {
+    type Error = core::convert::Infallible;
+
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        // SAFETY: The elements of a `Vec` are never pinned.
+        unsafe { self.get_unchecked_mut() }.push(item);
+        Ok(())
+    }
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
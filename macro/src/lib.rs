@@ -208,7 +208,17 @@ pub fn completion_stream_inner(input: TokenStream1) -> TokenStream1 {
         Ok(r) => r,
         Err(e) => return e.into_compile_error().into(),
     };
-    stream::transform(call_site_async(true, stmts), &crate_path).into()
+    stream::transform(call_site_async(true, stmts), &crate_path, false).into()
+}
+
+#[proc_macro]
+#[doc(hidden)]
+pub fn completion_generator_inner(input: TokenStream1) -> TokenStream1 {
+    let (crate_path, stmts) = match parse_bang_input.parse(input) {
+        Ok(r) => r,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    stream::transform(call_site_async(true, stmts), &crate_path, true).into()
 }
 
 fn parse_bang_input(input: ParseStream<'_>) -> parse::Result<(CratePath, Vec<Stmt>)> {
@@ -5,7 +5,14 @@ use syn::{Expr, ExprAsync, Stmt, Token};
 use super::{CratePath, OuterAttrs};
 
 /// Transform an async block to a completion stream.
-pub(crate) fn transform(mut expr: ExprAsync, crate_path: &CratePath) -> TokenStream {
+///
+/// If `generator` is set, the output of the block is kept as the stream's return value, and the
+/// `?` operator is left alone so that it returns the error instead of yielding it.
+pub(crate) fn transform(
+    mut expr: ExprAsync,
+    crate_path: &CratePath,
+    generator: bool,
+) -> TokenStream {
     let item = Ident::new("item", Span::mixed_site().located_at(expr.async_token.span));
 
     let mut yielded = false;
@@ -27,7 +34,7 @@ pub(crate) fn transform(mut expr: ExprAsync, crate_path: &CratePath) -> TokenStr
             })
             .unwrap();
         }
-        Expr::Try(expr_try) => {
+        Expr::Try(expr_try) if !generator => {
             let question_span = expr_try.question_token.spans[0];
             let crate_path = crate_path.with_span(question_span);
             let attrs = OuterAttrs(&expr_try.attrs);
@@ -69,10 +76,15 @@ pub(crate) fn transform(mut expr: ExprAsync, crate_path: &CratePath) -> TokenStr
     let transformed = crate::block::transform(expr, crate_path);
 
     let crate_path = crate_path.with_span(span);
+    let constructor = if generator {
+        quote_spanned!(span=> __completion_generator)
+    } else {
+        quote_spanned!(span=> __completion_stream)
+    };
 
     quote_spanned! {span=> {
         let #item = ::core::marker::PhantomData;
-        #crate_path::__completion_stream(#transformed, #item)
+        #crate_path::#constructor(#transformed, #item)
     }}
 }
 
@@ -105,7 +117,7 @@ mod tests {
         .expect("Failed to parse output");
 
         assert_eq!(
-            transform(input, &CratePath::new(quote!(crate))).to_string(),
+            transform(input, &CratePath::new(quote!(crate)), false).to_string(),
             output.into_token_stream().to_string(),
         );
     }
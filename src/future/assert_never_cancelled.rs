@@ -0,0 +1,67 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for
+    /// [`CompletionFutureExt::assert_never_cancelled`](super::CompletionFutureExt::assert_never_cancelled).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct AssertNeverCancelled<F> {
+        #[pin]
+        fut: F,
+    }
+}
+
+impl<F> AssertNeverCancelled<F> {
+    pub(super) fn new(fut: F) -> Self {
+        Self { fut }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for AssertNeverCancelled<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx)
+    }
+    #[track_caller]
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        debug_assert!(false, "future asserted to never be cancelled was cancelled");
+        self.project().fut.poll(cx).map(drop)
+    }
+}
+impl<F> Future for AssertNeverCancelled<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+
+    use crate::future;
+
+    #[test]
+    fn completes() {
+        let mut fut = AssertNeverCancelled::new(future::ready(5));
+        let poll = unsafe { CompletionFuture::poll(Pin::new(&mut fut), &mut crate::noop_cx()) };
+        assert_eq!(poll, Poll::Ready(5));
+    }
+
+    #[test]
+    #[should_panic = "future asserted to never be cancelled was cancelled"]
+    fn panics_on_cancel() {
+        let mut fut = AssertNeverCancelled::new(future::pending::<()>());
+        let _ = unsafe { Pin::new(&mut fut).poll_cancel(&mut crate::noop_cx()) };
+    }
+}
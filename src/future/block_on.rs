@@ -8,6 +8,8 @@ use std::thread::{self, Thread};
 
 use completion_core::CompletionFuture;
 
+use super::zip_all;
+
 /// Blocks the current thread on a completion future.
 ///
 /// # Examples
@@ -20,7 +22,20 @@ use completion_core::CompletionFuture;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 pub fn block_on<O, F: CompletionFuture<Output = O>>(mut future: F) -> O {
     let mut fut = unsafe { Pin::new_unchecked(&mut future) };
+    run(|cx| unsafe { fut.as_mut().poll(cx) })
+}
+
+/// Blocks the current thread until a completion future has finished cancelling.
+///
+/// # Safety
+///
+/// The [`CompletionFuture::poll_cancel`] contract must be upheld.
+pub(crate) unsafe fn cancel_blocking<F: CompletionFuture + ?Sized>(mut fut: Pin<&mut F>) {
+    run(|cx| fut.as_mut().poll_cancel(cx));
+}
 
+/// Repeatedly call the function, parking the thread between calls, until it returns `Ready`.
+fn run<O>(mut f: impl FnMut(&mut Context<'_>) -> Poll<O>) -> O {
     thread_local! {
         static CACHE: RefCell<(Parker, Waker)> = RefCell::new(wake_pair());
     }
@@ -40,7 +55,7 @@ pub fn block_on<O, F: CompletionFuture<Output = O>>(mut future: F) -> O {
         let mut cx = Context::from_waker(waker);
 
         loop {
-            if let Poll::Ready(output) = unsafe { fut.as_mut().poll(&mut cx) } {
+            if let Poll::Ready(output) = f(&mut cx) {
                 break output;
             }
             parker.park();
@@ -48,6 +63,35 @@ pub fn block_on<O, F: CompletionFuture<Output = O>>(mut future: F) -> O {
     })
 }
 
+/// Blocks the current thread on many completion futures at once, running them concurrently.
+///
+/// This returns the outputs of the futures in the same order as the iterator. It is equivalent to
+/// blocking on [`zip_all`](super::zip_all) and collecting its output; if any of the futures
+/// panics, all the other futures are cancelled before the panic is propagated.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{completion_async_move, future};
+/// use futures_lite::future::yield_now;
+///
+/// let outputs = future::block_on_all([3, 1, 2].iter().map(|&n| completion_async_move! {
+///     for _ in 0..n {
+///         yield_now().await;
+///     }
+///     n * 10
+/// }));
+/// assert_eq!(outputs, [30, 10, 20]);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn block_on_all<I>(iter: I) -> Vec<<I::Item as CompletionFuture>::Output>
+where
+    I: IntoIterator,
+    I::Item: CompletionFuture,
+{
+    block_on(zip_all(iter)).collect()
+}
+
 fn wake_pair() -> (Parker, Waker) {
     let inner = Arc::new(WakerInner {
         woken: AtomicBool::new(false),
@@ -132,3 +176,26 @@ fn test_block_on() {
         5
     );
 }
+
+#[cfg(feature = "macro")]
+#[test]
+fn test_block_on_all() {
+    use std::sync::Mutex;
+
+    use crate::test_utils::Yield;
+
+    let order = Mutex::new(Vec::new());
+    let outputs = block_on_all([4, 0, 2, 1, 3].iter().map(|&n| {
+        let order = &order;
+        Yield::new(
+            n,
+            crate::completion_async_move! {
+                order.lock().unwrap().push(n);
+                n
+            },
+        )
+    }));
+    assert_eq!(outputs, [4, 0, 2, 1, 3]);
+    assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3, 4]);
+    assert!(block_on_all(Vec::<Yield<super::Ready<()>>>::new()).is_empty());
+}
@@ -0,0 +1,101 @@
+use alloc::alloc::{AllocError, Allocator, Global, Layout};
+use core::cell::RefCell;
+use core::ptr::NonNull;
+use std::thread_local;
+use std::vec::Vec;
+
+/// The maximum number of freed allocations each thread keeps for reuse.
+const MAX_CACHED: usize = 32;
+
+thread_local! {
+    static FREE_LIST: RefCell<FreeList> = const { RefCell::new(FreeList(Vec::new())) };
+}
+
+/// Allocations that have been freed on this thread, along with their layouts.
+struct FreeList(Vec<(NonNull<u8>, Layout)>);
+
+impl Drop for FreeList {
+    fn drop(&mut self) {
+        for (ptr, layout) in self.0.drain(..) {
+            unsafe { Global.deallocate(ptr, layout) };
+        }
+    }
+}
+
+/// An allocator that reuses recently freed allocations, used by
+/// [`CompletionFutureExt::boxed_pooled`](super::CompletionFutureExt::boxed_pooled).
+///
+/// Each thread keeps a small cache of allocations freed on it. Allocating looks for a cached
+/// allocation with exactly the same layout, and only falls back to the global allocator if there
+/// isn't one. Since all the memory comes from the global allocator, allocations can be freely
+/// moved between threads.
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "allocator_api"))))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoxPool;
+
+unsafe impl Allocator for BoxPool {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Global.allocate(layout);
+        }
+
+        let cached = FREE_LIST
+            .try_with(|list| {
+                let list = &mut list.borrow_mut().0;
+                let i = list.iter().position(|&(_, cached)| cached == layout)?;
+                Some(list.swap_remove(i).0)
+            })
+            .ok()
+            .flatten();
+
+        match cached {
+            Some(ptr) => Ok(NonNull::slice_from_raw_parts(ptr, layout.size())),
+            None => Global.allocate(layout),
+        }
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // The free list is unavailable while the thread is being torn down.
+        let cached = layout.size() != 0
+            && FREE_LIST
+                .try_with(|list| {
+                    let list = &mut list.borrow_mut().0;
+                    if list.len() < MAX_CACHED {
+                        list.push((ptr, layout));
+                        true
+                    } else {
+                        false
+                    }
+                })
+                .unwrap_or(false);
+
+        if !cached {
+            Global.deallocate(ptr, layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+
+    #[test]
+    fn reuses_allocation() {
+        let fut = ready(1_u64).boxed_pooled();
+        let addr = &*fut as *const _ as *const u8;
+        assert_eq!(block_on(fut), 1);
+
+        let fut = ready(2_u64).boxed_pooled();
+        assert_eq!(&*fut as *const _ as *const u8, addr);
+        assert_eq!(block_on(fut), 2);
+    }
+
+    #[test]
+    fn different_layouts() {
+        let small = ready(1_u8).boxed_pooled();
+        drop(small);
+        let large = ready([0_u64; 8]).boxed_pooled();
+        assert_eq!(block_on(large), [0; 8]);
+    }
+}
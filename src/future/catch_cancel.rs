@@ -0,0 +1,57 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::catch_cancel`](super::CompletionFutureExt::catch_cancel).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct CatchCancel<F> {
+        #[pin]
+        fut: F,
+        cancelled: bool,
+    }
+}
+
+impl<F> CatchCancel<F> {
+    pub(super) fn new(fut: F) -> Self {
+        Self {
+            fut,
+            cancelled: false,
+        }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for CatchCancel<F> {
+    type Output = Option<F::Output>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if *this.cancelled {
+            return Poll::Ready(None);
+        }
+        this.fut.poll(cx).map(Some)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if !*this.cancelled {
+            ready!(this.fut.poll_cancel(cx));
+            *this.cancelled = true;
+        }
+        Poll::Ready(())
+    }
+}
+impl<F> Future for CatchCancel<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = Option<<F as CompletionFuture>::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
@@ -0,0 +1,156 @@
+use core::any::Any;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::boxed::Box;
+use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for
+    /// [`CompletionFutureExt::catch_unwind_full`](super::CompletionFutureExt::catch_unwind_full).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct CatchUnwindFull<F> {
+        #[pin]
+        inner: F,
+        state: State,
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    /// The inner future hasn't panicked.
+    Running,
+    /// The inner future panicked while being cancelled, and the panic hasn't been returned yet.
+    CancelPanicked(Box<dyn Any + Send>),
+    /// The inner future panicked, and it must not be polled again.
+    Panicked,
+}
+
+impl<F> CatchUnwindFull<F> {
+    pub(super) fn new(inner: F) -> Self {
+        Self {
+            inner,
+            state: State::Running,
+        }
+    }
+}
+
+impl<F: CompletionFuture + UnwindSafe> CompletionFuture for CatchUnwindFull<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        match core::mem::replace(this.state, State::Panicked) {
+            State::Running => {}
+            State::CancelPanicked(payload) => return Poll::Ready(Err(payload)),
+            State::Panicked => panic!("`CatchUnwindFull` polled after its future panicked"),
+        }
+
+        let inner = this.inner;
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(poll) => {
+                *this.state = State::Running;
+                poll.map(Ok)
+            }
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if let State::Running = this.state {
+            let inner = this.inner;
+            match catch_unwind(AssertUnwindSafe(|| inner.poll_cancel(cx))) {
+                Ok(poll) => return poll,
+                Err(payload) => *this.state = State::CancelPanicked(payload),
+            }
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<F> Future for CatchUnwindFull<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output> + UnwindSafe,
+{
+    type Output = Result<<F as CompletionFuture>::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A future that panics in either its poll or cancel method.
+    struct Panicking {
+        in_poll: bool,
+    }
+
+    impl CompletionFuture for Panicking {
+        type Output = ();
+
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.in_poll {
+                panic!("poll");
+            }
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            if !self.in_poll {
+                panic!("cancel");
+            }
+            Poll::Ready(())
+        }
+    }
+
+    fn message(payload: &(dyn Any + Send)) -> &str {
+        payload.downcast_ref::<&str>().unwrap()
+    }
+
+    #[test]
+    fn poll_panic() {
+        let mut fut = CatchUnwindFull::new(Panicking { in_poll: true });
+        let cx = &mut crate::noop_cx();
+        let payload = match unsafe { Pin::new(&mut fut).poll(cx) } {
+            Poll::Ready(Err(payload)) => payload,
+            _ => panic!("expected the panic to be caught"),
+        };
+        assert_eq!(message(&*payload), "poll");
+
+        // The inner future is never touched again.
+        assert_eq!(
+            unsafe { Pin::new(&mut fut).poll_cancel(cx) },
+            Poll::Ready(())
+        );
+    }
+
+    #[test]
+    fn cancel_panic() {
+        let mut fut = CatchUnwindFull::new(Panicking { in_poll: false });
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+
+        // The panic is recorded and the cancellation finishes.
+        assert_eq!(
+            unsafe { Pin::new(&mut fut).poll_cancel(cx) },
+            Poll::Ready(())
+        );
+        assert_eq!(
+            unsafe { Pin::new(&mut fut).poll_cancel(cx) },
+            Poll::Ready(())
+        );
+
+        // The next poll returns it.
+        let payload = match unsafe { Pin::new(&mut fut).poll(cx) } {
+            Poll::Ready(Err(payload)) => payload,
+            _ => panic!("expected the cancel panic to be returned"),
+        };
+        assert_eq!(message(&*payload), "cancel");
+    }
+}
@@ -0,0 +1,124 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::chain`](super::CompletionFutureExt::chain).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Chain<A, B> {
+        #[pin]
+        state: State<A, B>,
+    }
+}
+
+pin_project! {
+    #[project = StateProj]
+    #[project_replace = StateProjReplace]
+    #[derive(Debug, Clone)]
+    enum State<A, B> {
+        First {
+            #[pin]
+            first: A,
+            second: B,
+        },
+        Second {
+            #[pin]
+            second: B,
+        },
+        Temporary,
+    }
+}
+
+impl<A, B> Chain<A, B> {
+    pub(super) fn new(first: A, second: B) -> Self {
+        Self {
+            state: State::First { first, second },
+        }
+    }
+}
+
+impl<A: CompletionFuture, B: CompletionFuture> CompletionFuture for Chain<A, B> {
+    type Output = B::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if let StateProj::First { first, .. } = this.state.as_mut().project() {
+            ready!(first.poll(cx));
+            let state = this.state.as_mut().project_replace(State::Temporary);
+            if let StateProjReplace::First { second, .. } = state {
+                this.state.set(State::Second { second });
+            }
+        }
+        match this.state.project() {
+            StateProj::Second { second } => second.poll(cx),
+            StateProj::Temporary => panic!("polled after completion"),
+            StateProj::First { .. } => unreachable!(),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.project().state.project() {
+            StateProj::First { first, .. } => first.poll_cancel(cx),
+            StateProj::Second { second } => second.poll_cancel(cx),
+            StateProj::Temporary => Poll::Ready(()),
+        }
+    }
+}
+
+impl<A, B> Future for Chain<A, B>
+where
+    A: CompletionFuture + Future<Output = <A as CompletionFuture>::Output>,
+    B: CompletionFuture + Future<Output = <B as CompletionFuture>::Output>,
+{
+    type Output = <B as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    fn poll<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<F::Output> {
+        unsafe { Pin::new(fut).poll(&mut crate::noop_cx()) }
+    }
+
+    #[test]
+    fn second_waits_for_first() {
+        let (second, counts) = Yield::once(ready(2)).count_polls();
+        let mut fut = Yield::once(ready(1)).chain(second);
+
+        assert!(poll(&mut fut).is_pending());
+        assert_eq!(counts.polls(), 0);
+
+        assert!(poll(&mut fut).is_pending());
+        assert_eq!(counts.polls(), 1);
+
+        assert_eq!(block_on(fut), 2);
+        assert_eq!(counts.polls(), 2);
+        assert_eq!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn cancel_first() {
+        let (first, first_counts) = Yield::once(ready(1)).count_polls();
+        let (second, second_counts) = Yield::once(ready(2)).count_polls();
+        let mut fut = first.chain(second);
+
+        assert!(poll(&mut fut).is_pending());
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(&mut crate::noop_cx()) }.is_ready());
+        assert_eq!(first_counts.cancels(), 1);
+        assert_eq!(second_counts.polls(), 0);
+        assert_eq!(second_counts.cancels(), 0);
+    }
+}
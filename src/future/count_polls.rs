@@ -0,0 +1,79 @@
+use alloc::sync::Arc;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::count_polls`](super::CompletionFutureExt::count_polls).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct CountPolls<F> {
+        #[pin]
+        fut: F,
+        counts: Arc<PollCounts>,
+    }
+}
+
+impl<F> CountPolls<F> {
+    pub(super) fn new(fut: F) -> (Self, Arc<PollCounts>) {
+        let counts = Arc::new(PollCounts {
+            polls: AtomicUsize::new(0),
+            cancels: AtomicUsize::new(0),
+        });
+        let this = Self {
+            fut,
+            counts: Arc::clone(&counts),
+        };
+        (this, counts)
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for CountPolls<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.counts.polls.fetch_add(1, Ordering::Relaxed);
+        this.fut.poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        this.counts.cancels.fetch_add(1, Ordering::Relaxed);
+        this.fut.poll_cancel(cx)
+    }
+}
+impl<F> Future for CountPolls<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+/// The number of times a [`CountPolls`] future has been polled.
+#[derive(Debug)]
+pub struct PollCounts {
+    polls: AtomicUsize,
+    cancels: AtomicUsize,
+}
+
+impl PollCounts {
+    /// Get the number of times [`CompletionFuture::poll`] has been called.
+    #[must_use]
+    pub fn polls(&self) -> usize {
+        self.polls.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times [`CompletionFuture::poll_cancel`] has been called.
+    #[must_use]
+    pub fn cancels(&self) -> usize {
+        self.cancels.load(Ordering::Relaxed)
+    }
+}
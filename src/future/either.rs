@@ -0,0 +1,66 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// One of two futures with the same output type.
+///
+/// This allows different futures to be returned from the arms of an `if` or `match` without
+/// boxing them. It is most easily created with
+/// [`CompletionFutureExt::left_future`](super::CompletionFutureExt::left_future) and
+/// [`CompletionFutureExt::right_future`](super::CompletionFutureExt::right_future).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use = "futures do nothing unless you use them"]
+pub enum Either<A, B> {
+    /// The first future.
+    Left(A),
+    /// The second future.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    fn project(self: Pin<&mut Self>) -> Either<Pin<&mut A>, Pin<&mut B>> {
+        // SAFETY: The variants are structurally pinned, and we never move out of them.
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::Left(a) => Either::Left(Pin::new_unchecked(a)),
+                Self::Right(b) => Either::Right(Pin::new_unchecked(b)),
+            }
+        }
+    }
+}
+
+impl<A, B> CompletionFuture for Either<A, B>
+where
+    A: CompletionFuture,
+    B: CompletionFuture<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            Either::Left(a) => a.poll(cx),
+            Either::Right(b) => b.poll(cx),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.project() {
+            Either::Left(a) => a.poll_cancel(cx),
+            Either::Right(b) => b.poll_cancel(cx),
+        }
+    }
+}
+
+impl<A, B> Future for Either<A, B>
+where
+    A: CompletionFuture + Future<Output = <A as CompletionFuture>::Output>,
+    B: CompletionFuture<Output = <A as CompletionFuture>::Output>
+        + Future<Output = <A as CompletionFuture>::Output>,
+{
+    type Output = <A as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
@@ -0,0 +1,176 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::ensure`](super::CompletionFutureExt::ensure).
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Ensure<F, C>
+    where
+        F: CompletionFuture,
+    {
+        #[pin]
+        fut: F,
+        #[pin]
+        cleanup: C,
+        // Whether the main future has completed or finished cancelling.
+        fut_done: bool,
+        output: Option<F::Output>,
+    }
+}
+
+impl<F: CompletionFuture, C> Ensure<F, C> {
+    pub(super) fn new(fut: F, cleanup: C) -> Self {
+        Self {
+            fut,
+            cleanup,
+            fut_done: false,
+            output: None,
+        }
+    }
+}
+
+impl<F: CompletionFuture + Debug, C: Debug> Debug for Ensure<F, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ensure")
+            .field("fut", &self.fut)
+            .field("cleanup", &self.cleanup)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, C> CompletionFuture for Ensure<F, C>
+where
+    F: CompletionFuture,
+    C: CompletionFuture<Output = ()>,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if !*this.fut_done {
+            let output = ready!(this.fut.poll(cx));
+            *this.output = Some(output);
+            *this.fut_done = true;
+        }
+
+        ready!(this.cleanup.poll(cx));
+        Poll::Ready(this.output.take().expect("polled after completion"))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+
+        if !*this.fut_done {
+            ready!(this.fut.poll_cancel(cx));
+            *this.fut_done = true;
+        }
+
+        // The cleanup future is never cancelled, only run to completion.
+        ready!(this.cleanup.poll(cx));
+        *this.output = None;
+        Poll::Ready(())
+    }
+}
+
+impl<F, C> Future for Ensure<F, C>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    C: CompletionFuture<Output = ()> + Future<Output = ()>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::vec::Vec;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    /// A future that logs its name when it completes.
+    struct Log<'a> {
+        log: &'a RefCell<Vec<&'static str>>,
+        name: &'static str,
+    }
+    impl CompletionFuture for Log<'_> {
+        type Output = ();
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.log.borrow_mut().push(self.name);
+            Poll::Ready(())
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn cleanup_after_completion() {
+        let log = RefCell::new(Vec::new());
+        let fut = Yield::new(2, ready(5)).ensure(Yield::new(
+            2,
+            Log {
+                log: &log,
+                name: "cleanup",
+            },
+        ));
+        assert_eq!(block_on(fut.check()), 5);
+        assert_eq!(*log.borrow(), ["cleanup"]);
+    }
+
+    #[test]
+    fn cleanup_after_cancel() {
+        let log = RefCell::new(Vec::new());
+        let (main, counts) = Yield::new(5, ready(5)).count_polls();
+        let mut fut = main.ensure(Yield::new(
+            2,
+            Log {
+                log: &log,
+                name: "cleanup",
+            },
+        ));
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            let mut fut = Pin::new_unchecked(&mut fut);
+            assert!(fut.as_mut().poll(cx).is_pending());
+            while fut.as_mut().poll_cancel(cx).is_pending() {}
+        }
+        assert_ne!(counts.cancels(), 0);
+        assert_eq!(*log.borrow(), ["cleanup"]);
+    }
+
+    #[test]
+    fn cancel_during_cleanup() {
+        let log = RefCell::new(Vec::new());
+        let mut fut = ready(5).ensure(Yield::new(
+            3,
+            Log {
+                log: &log,
+                name: "cleanup",
+            },
+        ));
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            let mut fut = Pin::new_unchecked(&mut fut);
+            assert!(fut.as_mut().poll(cx).is_pending());
+            assert!(log.borrow().is_empty());
+            // Cancelling runs the cleanup to completion instead of cancelling it.
+            while fut.as_mut().poll_cancel(cx).is_pending() {}
+        }
+        assert_eq!(*log.borrow(), ["cleanup"]);
+    }
+}
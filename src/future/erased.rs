@@ -0,0 +1,236 @@
+use alloc::boxed::Box;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// Future for [`CompletionFutureExt::erase`](super::CompletionFutureExt::erase).
+///
+/// This is a type-erased, heap-allocated completion future like
+/// [`BoxCompletionFuture`](crate::BoxCompletionFuture), but instead of a `dyn` trait object it is
+/// made of a data pointer and a pointer to a manually-constructed [`ErasedVTable`], both of which
+/// are `#[repr(C)]` and use the `"C"` calling convention. This makes it suitable for passing across
+/// FFI or plugin boundaries where the layout of trait objects isn't guaranteed to match.
+///
+/// The output type `T` itself still crosses the boundary as a Rust type, so it is up to you to
+/// make sure that both sides agree on its layout.
+///
+/// # Panics
+///
+/// Because the vtable functions use the `"C"` ABI, a panic inside the erased future cannot unwind
+/// out of it and will abort the process instead.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[repr(C)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct ErasedCompletionFuture<'a, T> {
+    ptr: *mut (),
+    vtable: &'static ErasedVTable,
+    _lifetime: PhantomData<&'a ()>,
+    _output: PhantomData<fn() -> T>,
+}
+
+/// The vtable of an [`ErasedCompletionFuture`].
+///
+/// All the functions take the erased data pointer as their first argument.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[repr(C)]
+pub struct ErasedVTable {
+    /// Poll the future.
+    ///
+    /// The second argument is a `*mut Context<'_>` and the third is a pointer to uninitialized
+    /// memory for the future's output. If the future is ready, its output is written there and
+    /// `true` is returned; otherwise `false` is returned and the memory is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// The data pointer must be valid, and the future must not have completed or been cancelled.
+    /// The rest of the [`CompletionFuture::poll`] contract must also be upheld.
+    pub poll: unsafe extern "C" fn(*mut (), *mut (), *mut ()) -> bool,
+    /// Cancel the future.
+    ///
+    /// The second argument is a `*mut Context<'_>`. Returns `true` once cancellation has finished.
+    ///
+    /// # Safety
+    ///
+    /// The data pointer must be valid, and the [`CompletionFuture::poll_cancel`] contract must be
+    /// upheld.
+    pub poll_cancel: unsafe extern "C" fn(*mut (), *mut ()) -> bool,
+    /// Drop the future and free its allocation.
+    ///
+    /// # Safety
+    ///
+    /// The data pointer must be valid, and must not be used again afterwards. If the future has
+    /// been polled it must have completed or been cancelled first.
+    pub drop: unsafe extern "C" fn(*mut ()),
+}
+
+impl Debug for ErasedVTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErasedVTable").finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> ErasedCompletionFuture<'a, T> {
+    pub(super) fn new<F>(fut: F) -> Self
+    where
+        F: CompletionFuture<Output = T> + Send + 'a,
+    {
+        Self {
+            ptr: Box::into_raw(Box::new(fut)).cast(),
+            vtable: &ErasedVTable {
+                poll: poll::<F>,
+                poll_cancel: poll_cancel::<F>,
+                drop: drop::<F>,
+            },
+            _lifetime: PhantomData,
+            _output: PhantomData,
+        }
+    }
+
+    /// Decompose the erased future into its data pointer and vtable.
+    ///
+    /// The returned pointer owns the future; to avoid leaking it, either call the vtable's `drop`
+    /// function on it or rebuild the erased future with
+    /// [`from_raw_parts`](Self::from_raw_parts).
+    #[must_use]
+    pub fn into_raw_parts(self) -> (*mut (), &'static ErasedVTable) {
+        let this = ManuallyDrop::new(self);
+        (this.ptr, this.vtable)
+    }
+
+    /// Rebuild an erased future from its data pointer and vtable.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have come from [`into_raw_parts`](Self::into_raw_parts) on an erased future
+    /// with the same output type `T` and a lifetime at least as long as `'a`, and they must not be
+    /// used to rebuild more than one erased future.
+    ///
+    /// The state of the future carries over: if it was polled before being decomposed, it must
+    /// still be run to completion or cancelled before the rebuilt erased future is dropped.
+    pub unsafe fn from_raw_parts(ptr: *mut (), vtable: &'static ErasedVTable) -> Self {
+        Self {
+            ptr,
+            vtable,
+            _lifetime: PhantomData,
+            _output: PhantomData,
+        }
+    }
+}
+
+// SAFETY: The erased future can only be constructed from `Send` futures, and it doesn't allow any
+// access through a shared reference.
+unsafe impl<T> Send for ErasedCompletionFuture<'_, T> {}
+unsafe impl<T> Sync for ErasedCompletionFuture<'_, T> {}
+
+impl<T> Unpin for ErasedCompletionFuture<'_, T> {}
+
+impl<T> CompletionFuture for ErasedCompletionFuture<'_, T> {
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut out = MaybeUninit::<T>::uninit();
+        let cx: *mut Context<'_> = cx;
+        if (self.vtable.poll)(self.ptr, cx.cast(), out.as_mut_ptr().cast()) {
+            Poll::Ready(out.assume_init())
+        } else {
+            Poll::Pending
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let cx: *mut Context<'_> = cx;
+        if (self.vtable.poll_cancel)(self.ptr, cx.cast()) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for ErasedCompletionFuture<'_, T> {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.drop)(self.ptr) };
+    }
+}
+
+impl<T> Debug for ErasedCompletionFuture<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErasedCompletionFuture")
+            .field("ptr", &self.ptr)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe extern "C" fn poll<F: CompletionFuture>(ptr: *mut (), cx: *mut (), out: *mut ()) -> bool {
+    // SAFETY: The future is boxed so it never moves, and the caller guarantees the pointers are
+    // valid.
+    let fut = Pin::new_unchecked(&mut *ptr.cast::<F>());
+    match fut.poll(&mut *cx.cast::<Context<'_>>()) {
+        Poll::Ready(val) => {
+            out.cast::<F::Output>().write(val);
+            true
+        }
+        Poll::Pending => false,
+    }
+}
+
+unsafe extern "C" fn poll_cancel<F: CompletionFuture>(ptr: *mut (), cx: *mut ()) -> bool {
+    let fut = Pin::new_unchecked(&mut *ptr.cast::<F>());
+    fut.poll_cancel(&mut *cx.cast::<Context<'_>>()).is_ready()
+}
+
+unsafe extern "C" fn drop<F>(ptr: *mut ()) {
+    core::mem::drop(Box::from_raw(ptr.cast::<F>()));
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn round_trip() {
+        let fut = Yield::new(2, ready(5)).check().erase();
+        let (ptr, vtable) = fut.into_raw_parts();
+        let fut: ErasedCompletionFuture<'_, i32> =
+            unsafe { ErasedCompletionFuture::from_raw_parts(ptr, vtable) };
+        assert_eq!(block_on(fut), 5);
+    }
+
+    #[test]
+    fn cancel() {
+        let (fut, counts) = Yield::once(ready(())).count_polls();
+        let mut fut = fut.erase();
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(cx) }.is_ready());
+        assert_eq!(counts.polls(), 1);
+        assert_eq!(counts.cancels(), 1);
+    }
+
+    #[test]
+    fn drops_inner() {
+        struct SetOnDrop(Arc<AtomicBool>);
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(Arc::clone(&dropped));
+        let fut = ready(guard).erase();
+        assert!(!dropped.load(Ordering::SeqCst));
+        core::mem::drop(fut);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
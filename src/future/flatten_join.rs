@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use super::{zip_all, ZipAll};
+
+type InnerFuture<F> = <<F as CompletionFuture>::Output as IntoIterator>::Item;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::flatten_join`](super::CompletionFutureExt::flatten_join).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct FlattenJoin<F>
+    where
+        F: CompletionFuture,
+        F::Output: IntoIterator,
+        InnerFuture<F>: CompletionFuture,
+    {
+        #[pin]
+        outer: F,
+        // The joined inner futures, present once the outer future has completed.
+        inner: Option<ZipAll<InnerFuture<F>>>,
+    }
+}
+
+impl<F> FlattenJoin<F>
+where
+    F: CompletionFuture,
+    F::Output: IntoIterator,
+    InnerFuture<F>: CompletionFuture,
+{
+    pub(super) fn new(outer: F) -> Self {
+        Self { outer, inner: None }
+    }
+}
+
+impl<F> Debug for FlattenJoin<F>
+where
+    F: CompletionFuture + Debug,
+    F::Output: IntoIterator,
+    InnerFuture<F>: CompletionFuture,
+    ZipAll<InnerFuture<F>>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlattenJoin")
+            .field("outer", &self.outer)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<F> CompletionFuture for FlattenJoin<F>
+where
+    F: CompletionFuture,
+    F::Output: IntoIterator,
+    InnerFuture<F>: CompletionFuture,
+{
+    type Output = Vec<<InnerFuture<F> as CompletionFuture>::Output>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.inner.is_none() {
+            let futures = ready!(this.outer.poll(cx));
+            *this.inner = Some(zip_all(futures));
+        }
+
+        let inner = this.inner.as_mut().unwrap();
+        Pin::new(inner).poll(cx).map(Iterator::collect)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        match this.inner {
+            Some(inner) => Pin::new(inner).poll_cancel(cx),
+            None => this.outer.poll_cancel(cx),
+        }
+    }
+}
+
+impl<F> Future for FlattenJoin<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    <F as CompletionFuture>::Output: IntoIterator,
+    InnerFuture<F>:
+        CompletionFuture + Future<Output = <InnerFuture<F> as CompletionFuture>::Output>,
+{
+    type Output = <Self as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    fn joins_inner_futures() {
+        let outer = Yield::new(2, ready(vec![ready(1), ready(2), ready(3)]));
+        assert_eq!(block_on(outer.flatten_join()), [1, 2, 3]);
+    }
+
+    #[test]
+    fn cancel_outer() {
+        let (outer, counts) = Yield::new(5, ready(vec![ready(1)])).count_polls();
+        let mut fut = outer.flatten_join();
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+        while unsafe { CompletionFuture::poll_cancel(Pin::new(&mut fut), cx) }.is_pending() {}
+        assert_ne!(counts.cancels(), 0);
+        assert!(fut.inner.is_none());
+    }
+
+    #[test]
+    fn cancel_inner() {
+        let (a, a_counts) = Yield::new(5, ready(1)).count_polls();
+        let (b, b_counts) = Yield::new(5, ready(2)).count_polls();
+        let mut fut = ready(vec![a, b]).flatten_join();
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+        assert!(fut.inner.is_some());
+        while unsafe { CompletionFuture::poll_cancel(Pin::new(&mut fut), cx) }.is_pending() {}
+        assert_ne!(a_counts.cancels(), 0);
+        assert_ne!(b_counts.cancels(), 0);
+    }
+}
@@ -0,0 +1,51 @@
+use completion_core::CompletionFuture;
+
+/// Conversion into a [`CompletionFuture`].
+///
+/// This is the completion equivalent of the standard library's
+/// [`IntoFuture`](core::future::IntoFuture). Combinators such as
+/// [`CompletionFutureExt::zip`](super::CompletionFutureExt::zip) and
+/// [`CompletionFutureExt::race`](super::CompletionFutureExt::race) accept any type implementing
+/// this trait, so types that aren't futures themselves but can be turned into one can be passed
+/// to them directly.
+///
+/// All completion futures implement this trait, converting into themselves.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{CompletionFutureExt, completion_async};
+/// use completion::future::{IntoCompletionFuture, Ready};
+///
+/// struct Five;
+/// impl IntoCompletionFuture for Five {
+///     type Output = i32;
+///     type IntoFut = Ready<i32>;
+///     fn into_completion_future(self) -> Self::IntoFut {
+///         completion::future::ready(5)
+///     }
+/// }
+///
+/// # completion::future::block_on(completion_async! {
+/// assert_eq!(completion_async!(4).zip(Five).await, (4, 5));
+/// # });
+/// ```
+pub trait IntoCompletionFuture {
+    /// The output of the future.
+    type Output;
+
+    /// The future this value is converted into.
+    type IntoFut: CompletionFuture<Output = Self::Output>;
+
+    /// Convert this value into a completion future.
+    fn into_completion_future(self) -> Self::IntoFut;
+}
+
+impl<F: CompletionFuture> IntoCompletionFuture for F {
+    type Output = F::Output;
+    type IntoFut = F;
+
+    fn into_completion_future(self) -> Self::IntoFut {
+        self
+    }
+}
@@ -1,24 +1,24 @@
+use alloc::boxed::Box;
+use alloc::vec::{self, Vec};
 use core::fmt::{self, Debug, Formatter};
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::mem;
 use core::pin::Pin;
 use core::ptr;
-use core::sync::atomic::{self, AtomicBool};
+use core::sync::atomic::{self, AtomicBool, AtomicUsize};
 use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
-use std::panic::resume_unwind;
-use std::vec;
 
 use atomic_waker::AtomicWaker;
 use completion_core::CompletionFuture;
-use concurrent_queue::ConcurrentQueue;
 
 use crate::stream::{FromCompletionStream, FromCompletionStreamInner};
 
+use super::super::queue::Queue;
 use super::super::{ControlFlow, ControlFlowFuture, FutureState, Panic};
 
 /// This is to ensure we create the correct `Arc` when dealing with type-erased pointers.
-type ArcShared = std::sync::Arc<Shared>;
+type ArcShared = alloc::sync::Arc<Shared>;
 
 /// The generic implementation behind all the `_all` futures.
 #[derive(Debug)]
@@ -56,13 +56,7 @@ enum State<F: ControlFlowFuture> {
 #[derive(Debug)]
 struct Shared {
     /// The queue of futures that need to be polled.
-    ///
-    /// TODO: Switch to a local implementation, so that we can take advantage of:
-    /// - It being MPSC not MPMC.
-    /// - Knowing its size at construction time, so it can be stored locally.
-    /// - `usize` being `Copy` and potentially atomic.
-    /// - It not being able to overflow.
-    to_poll: ConcurrentQueue<usize>,
+    to_poll: Queue<Box<[AtomicUsize]>>,
     /// The waker passed to `JoinAll`. Should be woken after pushing to the poll queue.
     waker: AtomicWaker,
     /// The state of each of the futures' wakers.
@@ -113,7 +107,7 @@ impl WakerState {
                     .offset_from((&*shared.waker_states as *const [Self]).cast::<Self>())
             } as usize;
 
-            shared.to_poll.push(index).unwrap();
+            shared.to_poll.push(index);
             shared.waker.wake();
         }
     }
@@ -125,7 +119,7 @@ impl<F: ControlFlowFuture> JoinAll<F> {
     }
     fn new_inner(futures: Vec<FutureState<F>>) -> Self {
         let mut shared = ArcShared::new(Shared {
-            to_poll: ConcurrentQueue::bounded(futures.len()),
+            to_poll: Queue::new((0..futures.len()).map(|_| AtomicUsize::new(0)).collect()),
             waker: AtomicWaker::new(),
             waker_states: (0..futures.len())
                 .map(|_| WakerState {
@@ -179,7 +173,7 @@ impl<F: ControlFlowFuture> JoinAll<F> {
 
         loop {
             let i = if self.polled_once {
-                self.shared.to_poll.pop().ok()
+                self.shared.to_poll.pop()
             } else {
                 full_range.next()
             };
@@ -215,12 +209,21 @@ impl<F: ControlFlowFuture> JoinAll<F> {
         })
     }
 
+    /// Take the outputs of the futures that completed, leaving the rest as `None`.
+    ///
+    /// This must only be called once this future has finished.
+    pub(super) fn take_outputs(&mut self) -> Vec<Option<F::Continue>> {
+        (0..self.futures.len())
+            .map(|i| slice_index_pin_mut(self.futures.as_mut(), i).take_output())
+            .collect()
+    }
+
     fn poll_panicked(&mut self) {
         match self.poll_with(FutureState::poll_panicked) {
-            ControlFlow::Continue(Poll::Ready(state)) => resume_unwind(match state {
-                State::Panicked(payload) => payload.into_inner(),
+            ControlFlow::Continue(Poll::Ready(state)) => match state {
+                State::Panicked(panic) => panic.resume(),
                 _ => panic!("Polled `JoinAll` after completion"),
-            }),
+            },
             ControlFlow::Continue(Poll::Pending) => {}
             ControlFlow::Break(infallible) => match infallible {},
         }
@@ -371,7 +374,7 @@ fn slice_index_pin_mut<T>(slice: Pin<&mut [T]>, i: usize) -> Pin<&mut T> {
     unsafe { Pin::new_unchecked(&mut Pin::into_inner_unchecked(slice)[i]) }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -596,6 +599,37 @@ mod tests {
         assert!(x);
     }
 
+    #[test]
+    // Miri doesn't support boxed futures
+    #[cfg(not(miri))]
+    fn cancel_skips_completed() {
+        // The completed futures panic if they are cancelled.
+        let mut fut = JoinAll::new(vec![
+            ready(<ControlFlow<Infallible, _>>::Continue(0))
+                .boxed()
+                .check()
+                .max_cancels(0),
+            async {
+                Yield::once(ready(())).await;
+                ControlFlow::Continue(1)
+            }
+            .into_completion()
+            .boxed()
+            .check()
+            .max_cancels(1),
+            ready(ControlFlow::Continue(2))
+                .boxed()
+                .check()
+                .max_cancels(0),
+        ]);
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            assert!(Pin::new(&mut fut).poll(cx).is_pending());
+            assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+        }
+    }
+
     #[test]
     // Miri is too slow
     #[cfg(not(miri))]
@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FromIterator;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+use super::super::TryFuture;
+use super::try_zip::TryZipAll;
+
+/// Create a group of fallible futures that cancels all its futures as soon as one of them fails.
+///
+/// This behaves like [`try_zip_all`](super::try_zip_all), but the group is an explicit value that
+/// more futures can be [`push`](CancelGroup::push)ed to before it is first polled, and on success
+/// it directly outputs a `Vec` of the results in the order the futures were added.
+///
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
+///
+/// # Examples
+///
+/// ```
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// let mut group = future::cancel_group(vec![future::ready(Ok::<_, &str>(1))]);
+/// group.push(future::ready(Ok(2)));
+/// assert_eq!(group.await, Ok(vec![1, 2]));
+///
+/// let mut group = future::cancel_group(vec![future::ready(Ok(1))]);
+/// group.push(future::ready(Err("oh no")));
+/// assert_eq!(group.await, Err("oh no"));
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn cancel_group<I>(futures: I) -> CancelGroup<I::Item>
+where
+    I: IntoIterator,
+    I::Item: TryFuture,
+{
+    futures.into_iter().collect()
+}
+
+/// Future for [`cancel_group`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[must_use = "futures do nothing unless you use them"]
+pub struct CancelGroup<F: TryFuture> {
+    state: State<F>,
+}
+
+enum State<F: TryFuture> {
+    /// The group hasn't been polled yet, and futures can still be added.
+    Building(Vec<F>),
+    /// The group has been polled.
+    Running(TryZipAll<F>),
+}
+
+impl<F: TryFuture> Unpin for CancelGroup<F> {}
+
+impl<F: TryFuture> CancelGroup<F> {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self {
+            state: State::Building(Vec::new()),
+        }
+    }
+
+    /// Add a future to the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group has already been polled.
+    pub fn push(&mut self, fut: F) {
+        match &mut self.state {
+            State::Building(futures) => futures.push(fut),
+            State::Running(_) => panic!("cannot add futures to a `CancelGroup` once it is polled"),
+        }
+    }
+}
+
+impl<F: TryFuture> Default for CancelGroup<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: TryFuture> FromIterator<F> for CancelGroup<F> {
+    fn from_iter<T: IntoIterator<Item = F>>(iter: T) -> Self {
+        Self {
+            state: State::Building(iter.into_iter().collect()),
+        }
+    }
+}
+
+impl<F: TryFuture> Extend<F> for CancelGroup<F> {
+    fn extend<T: IntoIterator<Item = F>>(&mut self, iter: T) {
+        for fut in iter {
+            self.push(fut);
+        }
+    }
+}
+
+impl<F: TryFuture> CompletionFuture for CancelGroup<F> {
+    type Output = Result<Vec<F::Ok>, F::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let State::Building(futures) = &mut this.state {
+            this.state = State::Running(mem::take(futures).into_iter().collect());
+        }
+        match &mut this.state {
+            State::Running(inner) => Pin::new(inner).poll(cx).map_ok(Iterator::collect),
+            State::Building(_) => unreachable!(),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &mut self.get_mut().state {
+            State::Building(_) => Poll::Ready(()),
+            State::Running(inner) => Pin::new(inner).poll_cancel(cx),
+        }
+    }
+}
+
+impl<F: TryFuture + Debug> Debug for CancelGroup<F>
+where
+    TryZipAll<F>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.state {
+            State::Building(futures) => f.debug_tuple("CancelGroup").field(futures).finish(),
+            State::Running(inner) => f.debug_tuple("CancelGroup").field(inner).finish(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    fn error_cancels_rest() {
+        let (a, a_counts) = Yield::new(5, ready(Ok(1))).count_polls();
+        let (b, b_counts) = Yield::new(5, ready(Ok(2))).count_polls();
+        let (c, _) = Yield::once(ready(Err("oh no"))).count_polls();
+
+        let mut group = CancelGroup::new();
+        group.push(a);
+        group.push(b);
+        group.push(c);
+        assert_eq!(block_on(group), Err("oh no"));
+
+        assert_ne!(a_counts.cancels(), 0);
+        assert_ne!(b_counts.cancels(), 0);
+    }
+
+    #[test]
+    fn all_ok_in_order() {
+        let group = cancel_group(vec![
+            Yield::new(3, ready(Ok::<_, ()>(1))),
+            Yield::new(0, ready(Ok(2))),
+            Yield::new(1, ready(Ok(3))),
+        ]);
+        assert_eq!(block_on(group), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic = "cannot add futures to a `CancelGroup` once it is polled"]
+    fn push_after_poll() {
+        let mut group = cancel_group(vec![Yield::once(ready(Ok::<_, ()>(1)))]);
+        assert!(unsafe { Pin::new(&mut group).poll(&mut crate::noop_cx()) }.is_pending());
+        group.push(Yield::once(ready(Ok(2))));
+    }
+}
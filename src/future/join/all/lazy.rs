@@ -0,0 +1,373 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::Fuse;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use pin_project_lite::pin_project;
+
+use crate::stream::Unordered;
+
+use super::super::TryFuture;
+
+/// Wait for all the futures in an iterator to complete, creating them only as they are needed.
+///
+/// Unlike [`zip_all`](super::zip_all), which collects every future up front, this pulls futures
+/// from the iterator one at a time so that at most `limit` of them exist at once. Whenever one
+/// completes, the next one is taken from the iterator. This bounds the memory used by the
+/// futures themselves, at the cost of only running `limit` of them concurrently; the outputs are
+/// still all kept until the end, and are returned in iterator order.
+///
+/// Like the other joining futures in this crate, this is named after `zip` rather than `join`, so
+/// it is what other crates would call `join_all_lazy`.
+///
+/// Cancelling the returned future cancels the futures that are running, and doesn't take any more
+/// from the iterator.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{future, completion_async_move};
+/// use futures_lite::future::yield_now;
+///
+/// # future::block_on(completion::completion_async! {
+/// let futures = (0..100).map(|i| completion_async_move! {
+///     yield_now().await;
+///     i * 2
+/// });
+/// let outputs = future::zip_all_lazy(futures, 4).await;
+/// assert_eq!(outputs, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn zip_all_lazy<I>(iter: I, limit: usize) -> ZipAllLazy<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: CompletionFuture,
+{
+    assert_ne!(limit, 0, "`zip_all_lazy` limit must be non-zero");
+    ZipAllLazy {
+        iter: iter.into_iter().fuse(),
+        limit,
+        in_flight: Unordered::new(),
+        outputs: Vec::new(),
+    }
+}
+
+/// Future for [`zip_all_lazy`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[must_use = "futures do nothing unless you use them"]
+pub struct ZipAllLazy<I>
+where
+    I: Iterator,
+    I::Item: CompletionFuture,
+{
+    iter: Fuse<I>,
+    limit: usize,
+    in_flight: Unordered<Indexed<I::Item>>,
+    // The outputs of the futures taken so far, in iterator order.
+    outputs: Vec<Option<<I::Item as CompletionFuture>::Output>>,
+}
+
+impl<I> Unpin for ZipAllLazy<I>
+where
+    I: Iterator,
+    I::Item: CompletionFuture,
+{
+}
+
+impl<I> CompletionFuture for ZipAllLazy<I>
+where
+    I: Iterator,
+    I::Item: CompletionFuture,
+{
+    type Output = Vec<<I::Item as CompletionFuture>::Output>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            while this.in_flight.len() < this.limit {
+                match this.iter.next() {
+                    Some(fut) => {
+                        this.in_flight.push(Indexed {
+                            fut,
+                            index: this.outputs.len(),
+                        });
+                        this.outputs.push(None);
+                    }
+                    None => break,
+                }
+            }
+
+            if this.in_flight.is_empty() {
+                return Poll::Ready(
+                    this.outputs
+                        .drain(..)
+                        .map(|output| output.expect("polled after completion"))
+                        .collect(),
+                );
+            }
+
+            match this.in_flight.poll_next_eager(cx) {
+                Poll::Ready(Some((index, output))) => this.outputs[index] = Some(output),
+                Poll::Ready(None) => unreachable!("the set is never closed"),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.in_flight).poll_cancel(cx);
+        if poll.is_ready() {
+            this.outputs.clear();
+        }
+        poll
+    }
+}
+
+impl<I> Debug for ZipAllLazy<I>
+where
+    I: Iterator + Debug,
+    I::Item: CompletionFuture + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipAllLazy")
+            .field("iter", &self.iter)
+            .field("limit", &self.limit)
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Wait for the first future in an iterator to successfully complete, creating them only as they
+/// are needed.
+///
+/// Unlike [`race_ok_all`](super::race_ok_all), which collects every future up front, this pulls
+/// futures from the iterator one at a time so that at most `limit` of them exist at once. Whenever
+/// one fails, the next one is taken from the iterator, and once one succeeds the others are
+/// cancelled and the rest of the iterator is never consumed. This is useful when there are many
+/// candidates but most of them are expected to never be needed, at the cost of only racing
+/// `limit` of them at a time.
+///
+/// If every future fails, this outputs all their errors in the order they occurred.
+///
+/// This takes the place of a lazy `race_all`, which wouldn't be useful: the first future to
+/// complete ends the race, so it would never take more than the first `limit` futures from the
+/// iterator.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{future, completion_async_move};
+///
+/// # future::block_on(completion::completion_async! {
+/// // Only the first five futures are ever created.
+/// let futures = (0..).map(|i| completion_async_move! {
+///     if i < 4 { Err(i) } else { Ok(i) }
+/// });
+/// assert_eq!(future::race_ok_all_lazy(futures, 1).await, Ok(4));
+///
+/// let futures = (0..3).map(|i| completion_async_move!(Err::<(), _>(i)));
+/// assert_eq!(future::race_ok_all_lazy(futures, 2).await, Err(vec![0, 1, 2]));
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn race_ok_all_lazy<I>(iter: I, limit: usize) -> RaceOkAllLazy<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: TryFuture,
+{
+    assert_ne!(limit, 0, "`race_ok_all_lazy` limit must be non-zero");
+    RaceOkAllLazy {
+        iter: iter.into_iter().fuse(),
+        limit,
+        in_flight: Unordered::new(),
+        output: None,
+        errors: Vec::new(),
+    }
+}
+
+/// Future for [`race_ok_all_lazy`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[must_use = "futures do nothing unless you use them"]
+pub struct RaceOkAllLazy<I>
+where
+    I: Iterator,
+    I::Item: TryFuture,
+{
+    iter: Fuse<I>,
+    limit: usize,
+    in_flight: Unordered<I::Item>,
+    // The successful output, stored while the other futures are cancelled.
+    output: Option<<I::Item as TryFuture>::Ok>,
+    errors: Vec<<I::Item as TryFuture>::Error>,
+}
+
+impl<I> Unpin for RaceOkAllLazy<I>
+where
+    I: Iterator,
+    I::Item: TryFuture,
+{
+}
+
+impl<I> CompletionFuture for RaceOkAllLazy<I>
+where
+    I: Iterator,
+    I::Item: TryFuture,
+{
+    type Output = Result<<I::Item as TryFuture>::Ok, Vec<<I::Item as TryFuture>::Error>>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            if !this.in_flight.is_closed() {
+                while this.in_flight.len() < this.limit {
+                    match this.iter.next() {
+                        Some(fut) => this.in_flight.push(fut),
+                        None => break,
+                    }
+                }
+                if this.in_flight.is_empty() {
+                    return Poll::Ready(Err(mem::take(&mut this.errors)));
+                }
+            }
+
+            match this.in_flight.poll_next_eager(cx) {
+                Poll::Ready(Some(Ok(output))) => {
+                    this.output = Some(output);
+                    this.in_flight.cancel_all();
+                }
+                Poll::Ready(Some(Err(e))) => this.errors.push(e),
+                // The set has finished cancelling the losers.
+                Poll::Ready(None) => {
+                    return Poll::Ready(Ok(this.output.take().expect("polled after completion")))
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.in_flight).poll_cancel(cx);
+        if poll.is_ready() {
+            this.output = None;
+            this.errors.clear();
+        }
+        poll
+    }
+}
+
+impl<I> Debug for RaceOkAllLazy<I>
+where
+    I: Iterator + Debug,
+    I::Item: TryFuture + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceOkAllLazy")
+            .field("iter", &self.iter)
+            .field("limit", &self.limit)
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+pin_project! {
+    /// A future that outputs its index alongside its output.
+    #[derive(Debug)]
+    struct Indexed<F> {
+        #[pin]
+        fut: F,
+        index: usize,
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for Indexed<F> {
+    type Output = (usize, F::Output);
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let index = *this.index;
+        this.fut.poll(cx).map(|output| (index, output))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "macro")]
+    use std::cell::Cell;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    #[cfg(feature = "macro")]
+    fn zip_bounded() {
+        let (live, max_live) = (&Cell::new(0), &Cell::new(0));
+        let futures = (0..20).map(|i| {
+            live.set(live.get() + 1);
+            max_live.set(max_live.get().max(live.get()));
+            crate::completion_async_move! {
+                let output = Yield::new(i % 4, ready(i)).await;
+                live.set(live.get() - 1);
+                output
+            }
+        });
+        let outputs = block_on(zip_all_lazy(futures, 3));
+        assert_eq!(outputs, (0..20).collect::<Vec<_>>());
+        assert_eq!(max_live.get(), 3);
+    }
+
+    #[test]
+    fn race_ok_lazy() {
+        let futures = (0..).map(|i| {
+            assert!(i < 6, "iterator consumed too far");
+            Yield::new(i, ready(if i == 3 { Ok(i) } else { Err(i) }))
+        });
+        assert_eq!(block_on(race_ok_all_lazy(futures, 2)), Ok(3));
+    }
+
+    #[test]
+    fn race_ok_cancels_others() {
+        let (slow, counts) = Yield::new(10, ready(Ok::<_, ()>(1))).count_polls();
+        let (fast, _) = Yield::once(ready(Ok(2))).count_polls();
+        let futures = vec![slow, fast]
+            .into_iter()
+            .chain(core::iter::from_fn(|| -> Option<_> {
+                panic!("iterator consumed too far");
+            }));
+        assert_eq!(block_on(race_ok_all_lazy(futures, 2)), Ok(2));
+        assert_ne!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn cancel() {
+        let (fut, counts) = Yield::new(5, ready(0)).count_polls();
+        let mut fut = zip_all_lazy(vec![fut], 1);
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+        while unsafe { Pin::new(&mut fut).poll_cancel(cx) }.is_pending() {}
+        assert_ne!(counts.cancels(), 0);
+    }
+}
@@ -1,4 +1,5 @@
-//! Futures that join iterators over futures: `zip_all`, `try_zip_all`, `race_all`, `race_ok_all`.
+//! Futures that join iterators over futures: `zip_all`, `try_zip_all`, `try_zip_all_partial`,
+//! `cancel_group`, `race_all`, `race_ok_all`, `zip_all_lazy`, `race_ok_all_lazy`.
 
 mod base;
 
@@ -6,10 +7,16 @@ mod zip;
 pub use zip::{zip_all, ZipAll, ZipAllOutput};
 
 mod try_zip;
-pub use try_zip::{try_zip_all, TryZipAll, TryZipAllOutput};
+pub use try_zip::{try_zip_all, try_zip_all_partial, TryZipAll, TryZipAllOutput, TryZipAllPartial};
+
+mod cancel_group;
+pub use cancel_group::{cancel_group, CancelGroup};
 
 mod race;
 pub use race::{race_all, RaceAll};
 
 mod race_ok;
 pub use race_ok::{race_ok_all, RaceOkAll, RaceOkAllErrors};
+
+mod lazy;
+pub use lazy::{race_ok_all_lazy, zip_all_lazy, RaceOkAllLazy, ZipAllLazy};
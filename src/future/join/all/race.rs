@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::iter::FromIterator;
 use core::marker::PhantomData;
 use core::pin::Pin;
@@ -13,8 +14,15 @@ use super::base::JoinAll;
 
 /// Wait for the first future in an iterator to complete.
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// The other futures are cancelled once one completes, and the output is only returned after they
+/// have all finished cancelling. The losers are cancelled concurrently rather than one after
+/// another: each of them is asked to cancel straight away, and after that only the ones that issue
+/// wakeups are polled again, so a loser that is slow to cancel doesn't delay the cancellation of
+/// the others. To return the output immediately and cancel the losers
+/// separately, see [`CompletionFutureExt::race_eager`](crate::CompletionFutureExt::race_eager).
+///
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -76,7 +84,7 @@ use super::base::JoinAll;
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn race_all<I>(iter: I) -> RaceAll<I::Item>
 where
     I: IntoIterator,
@@ -86,7 +94,7 @@ where
 }
 
 /// Future for [`race_all`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[derive(Debug)]
 pub struct RaceAll<F: CompletionFuture> {
     inner: JoinAll<RaceFuture<F>>,
@@ -138,3 +146,103 @@ impl<F: CompletionFuture> CompletionFuture for RaceAll<F> {
         self.inner.poll_cancel(cx)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::vec;
+
+    use crate::future::{ready, LocalBoxCompletionFuture};
+    use crate::test_utils::Yield;
+
+    /// A future that never completes, and takes several polls to cancel.
+    struct SlowCancel<'a> {
+        name: &'static str,
+        cancel_polls: usize,
+        log: &'a RefCell<Vec<&'static str>>,
+    }
+
+    impl CompletionFuture for SlowCancel<'_> {
+        type Output = i32;
+
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_mut();
+            if this.cancel_polls == 0 {
+                this.log.borrow_mut().push(this.name);
+                return Poll::Ready(());
+            }
+            this.log.borrow_mut().push("cancelling");
+            this.cancel_polls -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn losers_cancelled_concurrently() {
+        let log = RefCell::new(Vec::new());
+        let futures: Vec<LocalBoxCompletionFuture<'_, i32>> = vec![
+            Box::pin(SlowCancel {
+                name: "slow",
+                cancel_polls: 3,
+                log: &log,
+            }),
+            Box::pin(SlowCancel {
+                name: "fast",
+                cancel_polls: 1,
+                log: &log,
+            }),
+            Box::pin(Yield::once(ready(5))),
+        ];
+        let mut race = race_all(futures);
+
+        let cx = &mut crate::noop_cx();
+        let output = loop {
+            if let Poll::Ready(output) = unsafe { Pin::new(&mut race).poll(cx) } {
+                break output;
+            }
+        };
+        assert_eq!(output, 5);
+
+        // Both losers start cancelling in the same poll, the fast one finishes while the slow
+        // one is still going, and the race only completes after the slow one has finished.
+        assert_eq!(
+            *log.borrow(),
+            [
+                "cancelling",
+                "cancelling",
+                "cancelling",
+                "fast",
+                "cancelling",
+                "slow"
+            ],
+        );
+    }
+
+    #[test]
+    fn loser_that_never_wakes_cancelled_promptly() {
+        // The loser never wakes the task, so its cancellation has to be started on the same poll
+        // that the winner completes on.
+        let log = RefCell::new(Vec::new());
+        let futures: Vec<LocalBoxCompletionFuture<'_, i32>> = vec![
+            Box::pin(SlowCancel {
+                name: "loser",
+                cancel_polls: 0,
+                log: &log,
+            }),
+            Box::pin(Yield::once(ready(5))),
+        ];
+        let mut race = race_all(futures);
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut race).poll(cx) }.is_pending());
+        assert!(log.borrow().is_empty());
+        assert_eq!(unsafe { Pin::new(&mut race).poll(cx) }, Poll::Ready(5));
+        assert_eq!(*log.borrow(), ["loser"]);
+    }
+}
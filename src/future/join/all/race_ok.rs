@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::fmt::{self, Debug, Formatter};
 use core::iter::{FromIterator, FusedIterator};
 use core::marker::PhantomData;
@@ -16,8 +17,8 @@ use super::base::{JoinAll, JoinAllOutput};
 ///
 /// If all the futures fail, this will return all the errors in a [`RaceOkAllErrors`].
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -111,7 +112,7 @@ use super::base::{JoinAll, JoinAllOutput};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn race_ok_all<I>(iter: I) -> RaceOkAll<I::Item>
 where
     I: IntoIterator,
@@ -121,7 +122,7 @@ where
 }
 
 /// Future for [`race_ok_all`]. If all the futures fail, this returns a [`RaceOkAllErrors`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[derive(Debug)]
 pub struct RaceOkAll<F: TryFuture> {
     inner: JoinAll<RaceOkFuture<F>>,
@@ -174,7 +175,7 @@ impl<F: TryFuture> CompletionFuture for RaceOkAll<F> {
 }
 
 /// An iterator over the errors of the futures in a [`RaceOkAll`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub struct RaceOkAllErrors<F: TryFuture>(JoinAllOutput<RaceOkFuture<F>>);
 
 impl<F: TryFuture> Iterator for RaceOkAllErrors<F> {
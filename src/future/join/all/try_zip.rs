@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::fmt::{self, Debug, Formatter};
 use core::iter::{FromIterator, FusedIterator};
 use core::marker::PhantomData;
@@ -5,6 +6,7 @@ use core::pin::Pin;
 use core::task::{Context, Poll};
 
 use completion_core::CompletionFuture;
+use futures_core::ready;
 
 use crate::future::CompletionFutureExt;
 use crate::stream::{FromCompletionStream, FromCompletionStreamInner};
@@ -16,8 +18,8 @@ use super::base::{JoinAll, JoinAllOutput};
 ///
 /// On success, this outputs a [`TryZipAllOutput`].
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -73,7 +75,7 @@ use super::base::{JoinAll, JoinAllOutput};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn try_zip_all<I>(iter: I) -> TryZipAll<I::Item>
 where
     I: IntoIterator,
@@ -83,7 +85,7 @@ where
 }
 
 /// Future for [`try_zip_all`]. On success, this outputs a [`TryZipAllOutput`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[derive(Debug)]
 pub struct TryZipAll<F: TryFuture> {
     inner: JoinAll<TryZipFuture<F>>,
@@ -135,8 +137,89 @@ impl<F: TryFuture> CompletionFuture for TryZipAll<F> {
     }
 }
 
+/// Wait for all the futures in an iterator to successfully complete or one to return an error,
+/// keeping the outputs of the futures that succeeded.
+///
+/// This is like [`try_zip_all`], but when a future fails its error is returned alongside the
+/// outputs of the futures that had already completed successfully, in the same order as the
+/// iterator. Futures that had not completed yet are cancelled as usual and are `None` in the list,
+/// as is the future that failed. This is useful to find out which operations in a batch succeeded
+/// when it partially fails.
+///
+/// On success, this outputs a [`TryZipAllOutput`].
+///
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{future, completion_async_move};
+///
+/// # future::block_on(completion::completion_async! {
+/// let (error, outputs) = future::try_zip_all_partial(
+///     [1, 2, 3]
+///         .iter()
+///         .map(|&i| completion_async_move! {
+///             if i == 2 {
+///                 Err("oh no")
+///             } else {
+///                 Ok(i * 2)
+///             }
+///         })
+/// )
+/// .await
+/// .unwrap_err();
+/// assert_eq!(error, "oh no");
+/// assert_eq!(outputs[0], Some(2));
+/// assert_eq!(outputs[1], None);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn try_zip_all_partial<I>(iter: I) -> TryZipAllPartial<I::Item>
+where
+    I: IntoIterator,
+    I::Item: TryFuture,
+{
+    iter.into_iter().collect()
+}
+
+/// Future for [`try_zip_all_partial`]. On success, this outputs a [`TryZipAllOutput`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct TryZipAllPartial<F: TryFuture> {
+    inner: JoinAll<TryZipFuture<F>>,
+    _correct_debug_bounds: PhantomData<(F::Ok, F::Error)>,
+}
+
+impl<F: TryFuture> Unpin for TryZipAllPartial<F> {}
+
+impl<F: TryFuture> FromIterator<F> for TryZipAllPartial<F> {
+    fn from_iter<T: IntoIterator<Item = F>>(iter: T) -> Self {
+        Self {
+            inner: JoinAll::new(iter.into_iter().map(TryZipFuture::new)),
+            _correct_debug_bounds: PhantomData,
+        }
+    }
+}
+
+impl<F: TryFuture> CompletionFuture for TryZipAllPartial<F> {
+    type Output = Result<TryZipAllOutput<F>, (F::Error, Vec<Option<F::Ok>>)>;
+
+    unsafe fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let flow = ready!(self.inner.poll(cx));
+        Poll::Ready(match flow {
+            ControlFlow::Continue(val) => Ok(TryZipAllOutput(val)),
+            ControlFlow::Break(e) => Err((e, self.inner.take_outputs())),
+        })
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.poll_cancel(cx)
+    }
+}
+
 /// An iterator over the successful outputs of futures in a [`TryZipAll`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub struct TryZipAllOutput<F: TryFuture>(JoinAllOutput<TryZipFuture<F>>);
 
 impl<F: TryFuture> Iterator for TryZipAllOutput<F> {
@@ -172,3 +255,34 @@ where
         f.debug_tuple("TryZipAllOutput").field(&self.0).finish()
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::ready;
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn partial_outputs() {
+        let (error, outputs) = crate::future::block_on(try_zip_all_partial(vec![
+            Yield::new(0, ready(Ok(1))).check().max_cancels(0),
+            Yield::new(1, ready(Ok(2))).check().max_cancels(0),
+            Yield::new(2, ready(Err("oh no"))).check().max_cancels(0),
+            Yield::new(5, ready(Ok(4))).check(),
+        ]))
+        .unwrap_err();
+        assert_eq!(error, "oh no");
+        assert_eq!(outputs, [Some(1), Some(2), None, None]);
+    }
+
+    #[test]
+    fn success() {
+        let outputs = crate::future::block_on(try_zip_all_partial(vec![
+            Yield::new(1, ready(Ok::<_, ()>(1))),
+            Yield::new(0, ready(Ok(2))),
+        ]))
+        .unwrap();
+        assert_eq!(outputs.collect::<Vec<_>>(), [1, 2]);
+    }
+}
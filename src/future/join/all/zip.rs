@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::fmt::{self, Debug, Formatter};
 use core::iter::{FromIterator, FusedIterator};
 use core::marker::PhantomData;
@@ -16,8 +17,8 @@ use super::base::{JoinAll, JoinAllOutput};
 ///
 /// This outputs a [`ZipAllOutput`].
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -55,7 +56,7 @@ use super::base::{JoinAll, JoinAllOutput};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn zip_all<I>(iter: I) -> ZipAll<I::Item>
 where
     I: IntoIterator,
@@ -65,7 +66,7 @@ where
 }
 
 /// Future for [`zip_all`]. This outputs a [`ZipAllOutput`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[derive(Debug)]
 pub struct ZipAll<F: CompletionFuture> {
     inner: JoinAll<ZipFuture<F>>,
@@ -118,7 +119,7 @@ impl<F: CompletionFuture> CompletionFuture for ZipAll<F> {
 }
 
 /// An iterator over the outputs of futures in a [`ZipAll`].
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub struct ZipAllOutput<F: CompletionFuture>(JoinAllOutput<ZipFuture<F>>);
 
 impl<F: CompletionFuture> Iterator for ZipAllOutput<F> {
@@ -0,0 +1,365 @@
+//! Futures that join arrays of futures: `zip_array`, `race_array`.
+//!
+//! Unlike the other joining futures these don't track which futures issued wakeups, as that
+//! requires state shared with the wakers, which would have to be allocated. Instead every running
+//! future is polled each time.
+
+use core::fmt::{self, Debug, Formatter};
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+use super::{ControlFlow, ControlFlowFuture, FutureState, Panic, RaceFuture, ZipFuture};
+
+/// The generic implementation behind `ZipArray` and `RaceArray`.
+struct JoinArray<F: ControlFlowFuture, const N: usize> {
+    // The futures themselves, which are pinned.
+    futures: [FutureState<F>; N],
+    // This `JoinArray`'s state.
+    state: State<F::Break>,
+}
+
+/// The state of a `JoinArray`.
+#[derive(Debug)]
+enum State<B> {
+    /// We are currently running all the futures.
+    Running,
+    /// A future has decided to break, we are cancelling the futures.
+    Broken(B),
+    /// The user is calling `poll_cancel`.
+    Cancelling,
+    /// A future has panicked. We are cancelling the futures, and then will propagate the panic.
+    Panicked(Panic),
+    /// This future is complete.
+    Done,
+}
+
+impl<F: ControlFlowFuture, const N: usize> JoinArray<F, N> {
+    fn new(futures: [F; N]) -> Self {
+        Self {
+            futures: futures.map(FutureState::Running),
+            state: State::Running,
+        }
+    }
+
+    fn project(self: Pin<&mut Self>) -> (Pin<&mut [FutureState<F>; N]>, &mut State<F::Break>) {
+        let this = unsafe { Pin::into_inner_unchecked(self) };
+        (
+            unsafe { Pin::new_unchecked(&mut this.futures) },
+            &mut this.state,
+        )
+    }
+
+    /// Poll all the futures with the given function, returning whether they are all done.
+    fn poll_with<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        mut f: impl FnMut(Pin<&mut FutureState<F>>, &mut Context<'_>) -> Poll<ControlFlow<B>>,
+    ) -> ControlFlow<B, bool> {
+        let futures = unsafe { Pin::into_inner_unchecked(self.project().0) };
+        let mut done = true;
+        for future in futures {
+            match f(unsafe { Pin::new_unchecked(future) }, cx) {
+                Poll::Ready(ControlFlow::Continue(())) => {}
+                Poll::Ready(ControlFlow::Break(b)) => return ControlFlow::Break(b),
+                Poll::Pending => done = false,
+            }
+        }
+        ControlFlow::Continue(done)
+    }
+
+    fn poll_panicked(mut self: Pin<&mut Self>, cx: &mut Context<'_>) {
+        let done = match self
+            .as_mut()
+            .poll_with(cx, |future, cx| unsafe { future.poll_panicked(cx) })
+        {
+            ControlFlow::Continue(done) => done,
+            ControlFlow::Break(infallible) => match infallible {},
+        };
+        if done {
+            match mem::replace(self.project().1, State::Done) {
+                State::Panicked(panic) => panic.resume(),
+                _ => panic!("Polled `JoinArray` after completion"),
+            }
+        }
+    }
+}
+
+impl<F: ControlFlowFuture, const N: usize> CompletionFuture for JoinArray<F, N> {
+    type Output = ControlFlow<F::Break, [F::Continue; N]>;
+
+    unsafe fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let State::Running = self.state {
+            match self.as_mut().poll_with(cx, |future, cx| future.poll(cx)) {
+                ControlFlow::Continue(false) => return Poll::Pending,
+                ControlFlow::Continue(true) => {
+                    let (futures, state) = self.project();
+                    *state = State::Done;
+                    let futures = Pin::into_inner_unchecked(futures);
+                    let mut futures = futures
+                        .iter_mut()
+                        .map(|future| Pin::new_unchecked(future).take_output().unwrap());
+                    let output = core::array::from_fn(|_| futures.next().unwrap());
+                    return Poll::Ready(ControlFlow::Continue(output));
+                }
+                ControlFlow::Break(Ok(val)) => *self.as_mut().project().1 = State::Broken(val),
+                ControlFlow::Break(Err(panic)) => {
+                    *self.as_mut().project().1 = State::Panicked(panic);
+                }
+            }
+        }
+
+        if let State::Broken(_) = self.state {
+            match self
+                .as_mut()
+                .poll_with(cx, |future, cx| future.poll_cancel(cx))
+            {
+                ControlFlow::Continue(false) => return Poll::Pending,
+                ControlFlow::Continue(true) => {
+                    return match mem::replace(self.project().1, State::Done) {
+                        State::Broken(val) => Poll::Ready(ControlFlow::Break(val)),
+                        _ => unreachable!(),
+                    };
+                }
+                ControlFlow::Break(panic) => *self.as_mut().project().1 = State::Panicked(panic),
+            }
+        }
+
+        self.poll_panicked(cx);
+        Poll::Pending
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if let State::Running | State::Broken(_) = self.state {
+            *self.as_mut().project().1 = State::Cancelling;
+        }
+
+        if let State::Cancelling = self.state {
+            match self
+                .as_mut()
+                .poll_with(cx, |future, cx| future.poll_cancel(cx))
+            {
+                ControlFlow::Continue(false) => return Poll::Pending,
+                ControlFlow::Continue(true) => {
+                    *self.project().1 = State::Done;
+                    return Poll::Ready(());
+                }
+                ControlFlow::Break(panic) => *self.as_mut().project().1 = State::Panicked(panic),
+            }
+        }
+
+        self.poll_panicked(cx);
+        Poll::Pending
+    }
+}
+
+impl<F, const N: usize> Debug for JoinArray<F, N>
+where
+    F: ControlFlowFuture + Debug,
+    F::Continue: Debug,
+    F::Break: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinArray")
+            .field("futures", &self.futures)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Wait for all the futures in an array to complete.
+///
+/// This outputs an array of the results. Unlike [`zip`](super::zip) and
+/// [`zip_all`](super::zip_all), this doesn't allocate, so it is available without the `alloc`
+/// feature. In exchange it doesn't keep track of which futures issued wakeups, and polls all the
+/// futures that are still running whenever it is woken.
+///
+/// Without the `std` feature, panics in any of the futures will abort the process, as
+/// [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
+///
+/// # Examples
+///
+/// ```
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// let futures = [future::ready(1), future::ready(2), future::ready(3)];
+/// assert_eq!(future::zip_array(futures).await, [1, 2, 3]);
+/// # });
+/// ```
+pub fn zip_array<F: CompletionFuture, const N: usize>(futures: [F; N]) -> ZipArray<F, N> {
+    ZipArray {
+        inner: JoinArray::new(futures.map(ZipFuture::new)),
+    }
+}
+
+/// Future for [`zip_array`].
+#[must_use = "futures do nothing unless you use them"]
+pub struct ZipArray<F: CompletionFuture, const N: usize> {
+    inner: JoinArray<ZipFuture<F>, N>,
+}
+
+impl<F: CompletionFuture, const N: usize> ZipArray<F, N> {
+    fn inner(self: Pin<&mut Self>) -> Pin<&mut JoinArray<ZipFuture<F>, N>> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.inner) }
+    }
+}
+
+impl<F: CompletionFuture, const N: usize> CompletionFuture for ZipArray<F, N> {
+    type Output = [F::Output; N];
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner().poll(cx).map(|flow| match flow {
+            ControlFlow::Continue(val) => val,
+            ControlFlow::Break(no) => match no {},
+        })
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner().poll_cancel(cx)
+    }
+}
+
+impl<F, const N: usize> Debug for ZipArray<F, N>
+where
+    F: CompletionFuture + Debug,
+    F::Output: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipArray")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Wait for the first future in an array to complete.
+///
+/// The other futures are cancelled once one completes, and the output is only returned after they
+/// have all finished cancelling. If multiple futures are ready at once, the earliest one in the
+/// array is chosen.
+///
+/// Unlike [`race`](super::race) and [`race_all`](super::race_all), this doesn't allocate, so it
+/// is available without the `alloc` feature. In exchange it doesn't keep track of which futures
+/// issued wakeups, and polls all the futures whenever it is woken.
+///
+/// Without the `std` feature, panics in any of the futures will abort the process, as
+/// [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
+///
+/// # Panics
+///
+/// Panics if the array is empty.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{future, completion_async, completion_async_move};
+/// use futures_lite::future::yield_now;
+///
+/// # future::block_on(completion_async! {
+/// let futures = [3, 1, 2].map(|n| completion_async_move! {
+///     for _ in 0..n {
+///         yield_now().await;
+///     }
+///     n
+/// });
+/// assert_eq!(future::race_array(futures).await, 1);
+/// # });
+/// ```
+pub fn race_array<F: CompletionFuture, const N: usize>(futures: [F; N]) -> RaceArray<F, N> {
+    RaceArray {
+        inner: JoinArray::new(futures.map(RaceFuture::new)),
+    }
+}
+
+/// Future for [`race_array`].
+#[must_use = "futures do nothing unless you use them"]
+pub struct RaceArray<F: CompletionFuture, const N: usize> {
+    inner: JoinArray<RaceFuture<F>, N>,
+}
+
+impl<F: CompletionFuture, const N: usize> RaceArray<F, N> {
+    fn inner(self: Pin<&mut Self>) -> Pin<&mut JoinArray<RaceFuture<F>, N>> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.inner) }
+    }
+}
+
+impl<F: CompletionFuture, const N: usize> CompletionFuture for RaceArray<F, N> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner().poll(cx).map(|flow| match flow {
+            ControlFlow::Continue(_) if N == 0 => panic!("raced an empty array of futures"),
+            // The array contains an `Infallible`.
+            ControlFlow::Continue(_) => unreachable!(),
+            ControlFlow::Break(val) => val,
+        })
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner().poll_cancel(cx)
+    }
+}
+
+impl<F, const N: usize> Debug for RaceArray<F, N>
+where
+    F: CompletionFuture + Debug,
+    F::Output: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceArray")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::panic::{catch_unwind, panic_any, AssertUnwindSafe};
+
+    use crate::future::{block_on, ready, CompletionFutureExt, FutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn zip() {
+        assert_eq!(
+            block_on(zip_array([ready(1), ready(2), ready(3)])),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            block_on(zip_array([
+                Yield::once(ready(0)).check().max_cancels(0),
+                Yield::new(3, ready(1)).check().max_cancels(0),
+                Yield::new(0, ready(2)).check().max_polls(1).max_cancels(0),
+            ])),
+            [0, 1, 2],
+        );
+        assert_eq!(block_on(zip_array::<crate::future::Ready<()>, 0>([])), []);
+    }
+
+    #[test]
+    fn race() {
+        assert_eq!(block_on(race_array([ready(1), ready(2), ready(3)])), 1);
+
+        // The losers are cancelled rather than dropped, and one that is ready on the same poll as
+        // the winner is not polled again.
+        let (a, a_counts) = Yield::new(2, ready(1)).check().count_polls();
+        let (b, b_counts) = Yield::once(ready(2)).check().count_polls();
+        let (c, c_counts) = Yield::once(ready(3)).check().count_polls();
+        assert_eq!(block_on(race_array([a, b, c])), 2);
+        assert_eq!((a_counts.polls(), a_counts.cancels()), (2, 1));
+        assert_eq!((b_counts.polls(), b_counts.cancels()), (2, 0));
+        assert_eq!((c_counts.polls(), c_counts.cancels()), (1, 1));
+    }
+
+    #[test]
+    fn panic() {
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            block_on(zip_array([
+                Yield::once(ready(())).boxed().check(),
+                async { panic_any(0) }.into_completion().boxed().check(),
+            ]))
+        }));
+        assert_eq!(*res.unwrap_err().downcast::<i32>().unwrap(), 0);
+    }
+}
@@ -1,24 +1,43 @@
 //! Futures that join together several futures.
 
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
 use core::any::Any;
 use core::convert::Infallible;
 use core::fmt::{self, Debug, Formatter};
 use core::mem;
 use core::pin::Pin;
 use core::task::{Context, Poll};
-use std::panic::{catch_unwind, AssertUnwindSafe};
+#[cfg(feature = "std")]
+use std::panic::{resume_unwind, AssertUnwindSafe};
 
 use completion_core::CompletionFuture;
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "alloc")]
+mod queue;
+
+#[cfg(feature = "alloc")]
 mod tuple;
+#[cfg(feature = "alloc")]
 pub use tuple::*;
 
+#[cfg(feature = "alloc")]
 mod all;
+#[cfg(feature = "alloc")]
 pub use all::*;
 
+mod array;
+pub use array::*;
+
 /// The payload of a panic.
+#[cfg(feature = "std")]
 pub struct Panic(Box<dyn Any + Send>);
+/// The payload of a panic. Without the `std` feature panics can't be caught, so this is
+/// uninhabited.
+#[cfg(not(feature = "std"))]
+pub struct Panic(Infallible);
 unsafe impl Sync for Panic {}
 impl Debug for Panic {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -26,18 +45,53 @@ impl Debug for Panic {
     }
 }
 impl Panic {
-    fn into_inner(self) -> Box<dyn Any + Send> {
-        self.0
+    /// Continue unwinding the panic.
+    fn resume(self) -> ! {
+        #[cfg(feature = "std")]
+        {
+            resume_unwind(self.0)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            match self.0 {}
+        }
+    }
+}
+
+/// Call a function, catching any panics that occur inside it.
+///
+/// If a future panics, we must make sure to cancel all the other futures before we unwind past
+/// them. Without the `std` feature panics cannot be caught, so instead we abort by panicking again
+/// while unwinding.
+#[cfg(feature = "std")]
+fn catch_unwind<R>(f: impl FnOnce() -> R) -> Result<R, Panic> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).map_err(Panic)
+}
+#[cfg(not(feature = "std"))]
+#[allow(clippy::unnecessary_wraps)]
+fn catch_unwind<R>(f: impl FnOnce() -> R) -> Result<R, Panic> {
+    struct AbortOnUnwind;
+    impl Drop for AbortOnUnwind {
+        fn drop(&mut self) {
+            panic!("a joined future panicked, aborting as the `std` feature is disabled");
+        }
     }
+
+    let guard = AbortOnUnwind;
+    let res = f();
+    mem::forget(guard);
+    Ok(res)
 }
 
 /// A future that outputs a `Result`.
+#[cfg(feature = "alloc")]
 pub trait TryFuture:
     CompletionFuture<Output = Result<<Self as TryFuture>::Ok, <Self as TryFuture>::Error>>
 {
     type Ok;
     type Error;
 }
+#[cfg(feature = "alloc")]
 impl<T, E, F> TryFuture for F
 where
     F: CompletionFuture<Output = Result<T, E>> + ?Sized,
@@ -58,7 +112,7 @@ pub enum ControlFlow<B, C = ()> {
     Continue(C),
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 impl<B, C> ControlFlow<B, C> {
     #[track_caller]
     fn unwrap_break(self) -> B
@@ -138,7 +192,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
         match this {
             Self::Running(fut) => {
                 let fut = Pin::new_unchecked(fut);
-                match catch_unwind(AssertUnwindSafe(|| fut.poll(cx))) {
+                match catch_unwind(|| fut.poll(cx)) {
                     Ok(Poll::Ready(ControlFlow::Continue(c))) => {
                         *this = Self::Completed(c);
                         Poll::Ready(ControlFlow::Continue(()))
@@ -150,7 +204,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
                     Ok(Poll::Pending) => Poll::Pending,
                     Err(panic) => {
                         *this = Self::Cancelled;
-                        Poll::Ready(ControlFlow::Break(Err(Panic(panic))))
+                        Poll::Ready(ControlFlow::Break(Err(panic)))
                     }
                 }
             }
@@ -168,7 +222,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
         match this {
             Self::Running(fut) => {
                 let fut = Pin::new_unchecked(fut);
-                match catch_unwind(AssertUnwindSafe(|| fut.poll_cancel(cx))) {
+                match catch_unwind(|| fut.poll_cancel(cx)) {
                     Ok(Poll::Ready(())) => {
                         *this = Self::Cancelled;
                         Poll::Ready(ControlFlow::Continue(()))
@@ -176,7 +230,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
                     Ok(Poll::Pending) => Poll::Pending,
                     Err(e) => {
                         *this = Self::Cancelled;
-                        Poll::Ready(ControlFlow::Break(Panic(e)))
+                        Poll::Ready(ControlFlow::Break(e))
                     }
                 }
             }
@@ -194,7 +248,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
         match this {
             Self::Running(fut) => {
                 let fut = Pin::new_unchecked(fut);
-                match catch_unwind(AssertUnwindSafe(|| fut.poll_cancel(cx))) {
+                match catch_unwind(|| fut.poll_cancel(cx)) {
                     Ok(Poll::Ready(())) | Err(_) => {
                         *this = Self::Cancelled;
                         Poll::Ready(ControlFlow::Continue(()))
@@ -211,7 +265,7 @@ impl<F: ControlFlowFuture> FutureState<F> {
 }
 
 pin_project! {
-    /// A wrapper for a future inside `zip` or `zip_all`.
+    /// A wrapper for a future inside `zip`, `zip_all` or `zip_array`.
     #[derive(Debug)]
     pub struct ZipFuture<F> {
         #[pin]
@@ -233,6 +287,7 @@ impl<F: CompletionFuture> CompletionFuture for ZipFuture<F> {
     }
 }
 
+#[cfg(feature = "alloc")]
 pin_project! {
     /// A wrapper for a future inside `try_zip` or `try_zip_all`.
     #[derive(Debug)]
@@ -241,11 +296,13 @@ pin_project! {
         inner: F,
     }
 }
+#[cfg(feature = "alloc")]
 impl<F> TryZipFuture<F> {
     fn new(inner: F) -> Self {
         Self { inner }
     }
 }
+#[cfg(feature = "alloc")]
 impl<F, T, E> CompletionFuture for TryZipFuture<F>
 where
     F: CompletionFuture<Output = Result<T, E>>,
@@ -263,7 +320,7 @@ where
 }
 
 pin_project! {
-    /// A wrapper for a future inside `race` or `race_all`.
+    /// A wrapper for a future inside `race`, `race_all` or `race_array`.
     #[derive(Debug)]
     pub struct RaceFuture<F> {
         #[pin]
@@ -285,6 +342,7 @@ impl<F: CompletionFuture> CompletionFuture for RaceFuture<F> {
     }
 }
 
+#[cfg(feature = "alloc")]
 pin_project! {
     /// A wrapper for a future inside `race_ok` or `race_ok_all`.
     #[derive(Debug)]
@@ -293,11 +351,13 @@ pin_project! {
         inner: F,
     }
 }
+#[cfg(feature = "alloc")]
 impl<F> RaceOkFuture<F> {
     fn new(inner: F) -> Self {
         Self { inner }
     }
 }
+#[cfg(feature = "alloc")]
 impl<F, T, E> CompletionFuture for RaceOkFuture<F>
 where
     F: CompletionFuture<Output = Result<T, E>>,
@@ -0,0 +1,117 @@
+use core::sync::atomic::{self, AtomicUsize};
+
+/// The queue of futures that need to be polled, shared between a joining future and its wakers.
+///
+/// This is a bounded multi-producer single-consumer queue of indices. Each index is only ever in
+/// the queue once at a time, as the wakers keep track of whether they have already notified the
+/// joining future. Because of this, the queue needs exactly as many slots as there are futures
+/// and can never overflow.
+#[derive(Debug)]
+pub(super) struct Queue<S> {
+    /// The position of the next slot to pop from. Only the consumer accesses this.
+    head: AtomicUsize,
+    /// The position of the next slot to push to.
+    tail: AtomicUsize,
+    /// The slots of the queue. Each slot holds either zero for an empty slot, or the index plus
+    /// one.
+    slots: S,
+}
+
+impl<S: AsRef<[AtomicUsize]>> Queue<S> {
+    /// Create a new queue. All the slots must be zero.
+    pub(super) fn new(slots: S) -> Self {
+        Self {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            slots,
+        }
+    }
+
+    /// Push an index onto the queue. The index must not already be in the queue.
+    pub(super) fn push(&self, index: usize) {
+        let slots = self.slots.as_ref();
+        let position = self
+            .tail
+            .fetch_update(atomic::Ordering::SeqCst, atomic::Ordering::SeqCst, |tail| {
+                Some((tail + 1) % slots.len())
+            })
+            .unwrap();
+        slots[position].store(index + 1, atomic::Ordering::SeqCst);
+    }
+
+    /// Pop an index from the queue.
+    ///
+    /// This can spuriously return `None` if a waker is in the middle of pushing, but then that
+    /// waker will wake the joining future once it's done.
+    pub(super) fn pop(&self) -> Option<usize> {
+        let slots = self.slots.as_ref();
+        if slots.is_empty() {
+            return None;
+        }
+
+        let head = self.head.load(atomic::Ordering::Relaxed);
+        match slots[head].swap(0, atomic::Ordering::SeqCst) {
+            0 => None,
+            value => {
+                self.head
+                    .store((head + 1) % slots.len(), atomic::Ordering::Relaxed);
+                Some(value - 1)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn single_threaded() {
+        let queue = Queue::new([
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+        ]);
+        assert_eq!(queue.pop(), None);
+
+        queue.push(2);
+        queue.push(0);
+        assert_eq!(queue.pop(), Some(2));
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), None);
+
+        let empty = Queue::new([]);
+        assert_eq!(empty.pop(), None);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        const THREADS: usize = 8;
+
+        let queue = Arc::new(Queue::new(
+            (0..THREADS)
+                .map(|_| AtomicUsize::new(0))
+                .collect::<Box<[_]>>(),
+        ));
+        let threads: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.push(i))
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let mut popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        popped.sort_unstable();
+        assert_eq!(popped, (0..THREADS).collect::<Vec<_>>());
+    }
+}
@@ -4,19 +4,18 @@ use core::marker::PhantomData;
 use core::mem;
 use core::pin::Pin;
 use core::ptr;
-use core::sync::atomic::{self, AtomicBool};
+use core::sync::atomic::{self, AtomicBool, AtomicUsize};
 use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
-use std::panic::resume_unwind;
 
 use atomic_waker::AtomicWaker;
 use completion_core::CompletionFuture;
-use concurrent_queue::ConcurrentQueue;
 use pin_project_lite::pin_project;
 
+use super::super::queue::Queue;
 use super::super::{ControlFlow, ControlFlowFuture, FutureState, Panic};
 
 /// This is to ensure we create the correct `Arc` when dealing with type-erased pointers.
-type ArcShared<T> = std::sync::Arc<Shared<T>>;
+type ArcShared<T> = alloc::sync::Arc<Shared<T>>;
 
 pin_project! {
     /// The generic implementation behind all the tuple joining futures.
@@ -58,14 +57,9 @@ enum State<T: JoinTuple> {
 struct Shared<T: JoinTuple> {
     /// The queue of futures that need to be polled.
     ///
-    /// TODO: Switch to a local implementation, so that we can take advantage of:
-    /// - It being MPSC not MPMC.
-    /// - Knowing its size at compile time, so it can be stored locally instead of on the heap.
-    /// - Its size being small.
-    /// - Each `u8` not using many of the upper bits.
-    /// - `u8`s being `Copy` and potentially atomic.
-    /// - It not being able to overflow.
-    to_poll: ConcurrentQueue<u8>,
+    /// TODO: Store this locally instead of on the heap, and take advantage of the indices being
+    /// `u8`s.
+    to_poll: Queue<T::QueueSlots>,
     /// The waker passed to `Join`. Should be woken after pushing to the poll queue.
     waker: AtomicWaker,
     /// The state of each of the futures' wakers. This is an array of `WakerState`s.
@@ -112,7 +106,7 @@ impl<T: JoinTuple> WakerState<T> {
                 (self as *const Self)
                     .offset_from((shared.waker_states.as_ref() as *const [Self]).cast::<Self>())
             } as u8;
-            shared.to_poll.push(index).unwrap();
+            shared.to_poll.push(usize::from(index));
             shared.waker.wake();
         }
     }
@@ -129,7 +123,7 @@ impl<T: JoinTuple> Debug for WakerState<T> {
 impl<T: JoinTuple> Join<T> {
     pub(super) fn new(tuple: T) -> Self {
         let mut shared = ArcShared::new(Shared {
-            to_poll: ConcurrentQueue::bounded(usize::from(T::LEN)),
+            to_poll: Queue::new(T::new_queue_slots()),
             waker: AtomicWaker::new(),
             waker_states: T::new_waker_states(),
         });
@@ -164,8 +158,9 @@ impl<T: JoinTuple> Join<T> {
         let mut full_range = 0..T::LEN;
 
         loop {
+            #[allow(clippy::cast_possible_truncation)]
             let i = if *this.polled_once {
-                this.shared.to_poll.pop().ok()
+                this.shared.to_poll.pop().map(|i| i as u8)
             } else {
                 full_range.next()
             };
@@ -203,10 +198,10 @@ impl<T: JoinTuple> Join<T> {
 
     fn poll_panicked(self: Pin<&mut Self>) {
         match self.poll_with(T::poll_panicked) {
-            ControlFlow::Continue(Poll::Ready(state)) => resume_unwind(match state {
-                State::Panicked(payload) => payload.into_inner(),
+            ControlFlow::Continue(Poll::Ready(state)) => match state {
+                State::Panicked(panic) => panic.resume(),
                 _ => panic!("Polled `Join` after completion"),
-            }),
+            },
             ControlFlow::Continue(Poll::Pending) => {}
             ControlFlow::Break(infallible) => match infallible {},
         }
@@ -296,6 +291,11 @@ pub trait JoinTuple: Sized {
     /// Create the waker states.
     fn new_waker_states() -> Self::WakerStates;
 
+    /// Array of zeroed `AtomicUsize`s, one for each future, used as the slots of the poll queue.
+    type QueueSlots: AsRef<[AtomicUsize]> + Send + Sync + Debug;
+    /// Create the queue slots.
+    fn new_queue_slots() -> Self::QueueSlots;
+
     /// The tuple of results outputted when all the futures complete.
     type Output;
     /// The single result outputted when one future decides to break control flow.
@@ -370,6 +370,11 @@ macro_rules! impl_tuple {
                 }),)*]
             }
 
+            type QueueSlots = [AtomicUsize; count_tts!($($param)*)];
+            fn new_queue_slots() -> Self::QueueSlots {
+                [$(with!($param, AtomicUsize::new(0)),)*]
+            }
+
             type Output = ($(<$param as ControlFlowFuture>::Continue,)*);
             type Break = Break;
 
@@ -433,7 +438,7 @@ macro_rules! impl_tuple {
 }
 apply_on_tuples!(impl_tuple!);
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
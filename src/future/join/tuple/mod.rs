@@ -1,4 +1,4 @@
-//! Futures that join tuples of futures: `zip`, `try_zip`, `race`, `race_ok`.
+//! Futures that join tuples of futures: `zip`, `try_zip`, `race`, `race_ok`, `select_ok`.
 //!
 //! This code is difficult to read due to its use of macros to support tuples of arbitrary length.
 //! The `all` variants implement the same algorithms, but are simpler to understand.
@@ -39,7 +39,7 @@ macro_rules! apply_on_tuples {
 mod base;
 
 mod zip;
-pub use zip::{zip, Zip};
+pub use zip::{zip, Zip, ZipWith};
 
 mod try_zip;
 pub use try_zip::{try_zip, TryZip};
@@ -47,5 +47,14 @@ pub use try_zip::{try_zip, TryZip};
 mod race;
 pub use race::{race, Race};
 
+mod race_eager;
+pub use race_eager::{CancelRemaining, RaceEager};
+
+mod race_preferring;
+pub use race_preferring::{Preference, RacePreferring};
+
 mod race_ok;
-pub use race_ok::{race_ok, RaceOk};
+pub use race_ok::{race_ok, RaceOk, RaceOkErrors, RaceOkPair};
+
+mod select_ok;
+pub use select_ok::SelectOk;
@@ -10,8 +10,16 @@ use super::base::{Join, JoinTuple};
 
 /// Wait for the first future in a tuple to complete.
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// The other futures are cancelled once one completes, and the output is only returned after they
+/// have all finished cancelling. Exactly one output is ever returned: once a future is chosen the
+/// others are not polled again, so even if they would also be ready they are cancelled through
+/// [`poll_cancel`](CompletionFuture::poll_cancel) rather than dropped. The losers are asked to
+/// cancel on the same poll that the winner completes on, so a loser that never wakes the task is
+/// still cancelled promptly. To return the output immediately and cancel the losers separately,
+/// see [`CompletionFutureExt::race_eager`](crate::CompletionFutureExt::race_eager).
+///
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -58,7 +66,7 @@ use super::base::{Join, JoinTuple};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn race<T: RaceTuple>(futures: T) -> Race<T> {
     Race {
         inner: Join::new(futures.into_tuple()),
@@ -68,7 +76,7 @@ pub fn race<T: RaceTuple>(futures: T) -> Race<T> {
 
 pin_project! {
     /// Future for [`race`].
-    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     #[derive(Debug)]
     pub struct Race<T: RaceTuple> {
         #[pin]
@@ -120,3 +128,72 @@ macro_rules! impl_race_tuple {
     }
 }
 apply_on_tuples!(impl_race_tuple!);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    /// A future that never completes, and records when it is cancelled.
+    struct RecordCancel(Arc<AtomicBool>);
+
+    impl CompletionFuture for RecordCancel {
+        type Output = i32;
+
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.0.store(true, Ordering::SeqCst);
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn boxed_loser_cancelled() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let boxed = RecordCancel(Arc::clone(&cancelled)).boxed();
+        assert_eq!(block_on(race((boxed, ready(5)))), 5);
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn simultaneously_ready_loser_cancelled() {
+        // Both futures become ready on their second poll, and `Check` asserts that the loser is
+        // cancelled rather than just dropped.
+        let (a, a_counts) = Yield::once(ready(1)).check().count_polls();
+        let (b, b_counts) = Yield::once(ready(2)).check().count_polls();
+        assert_eq!(block_on(race((a, b))), 1);
+
+        assert_eq!(a_counts.polls(), 2);
+        assert_eq!(a_counts.cancels(), 0);
+        assert_eq!(b_counts.polls(), 1);
+        assert_eq!(b_counts.cancels(), 1);
+    }
+
+    #[test]
+    fn loser_that_never_wakes_cancelled_promptly() {
+        // The loser never wakes the task, so its cancellation has to be started on the same poll
+        // that the winner completes on.
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut fut = race((RecordCancel(Arc::clone(&cancelled)), Yield::once(ready(5))));
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+        assert!(!cancelled.load(Ordering::SeqCst));
+        assert_eq!(unsafe { Pin::new(&mut fut).poll(cx) }, Poll::Ready(5));
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn immediately_ready_loser_not_polled() {
+        let a = ready(1).check().max_cancels(0);
+        let b = ready(2).check().max_polls(0);
+        assert_eq!(block_on(race((a, b))), 1);
+    }
+}
@@ -0,0 +1,180 @@
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// Future for [`CompletionFutureExt::race_eager`](crate::CompletionFutureExt::race_eager).
+///
+/// If this is dropped after being polled but before it completes (for example while unwinding
+/// from a panic), the futures are leaked instead of dropped.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct RaceEager<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+    started: bool,
+}
+
+impl<A: 'static, B: 'static> RaceEager<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a: Some(a),
+            b: Some(b),
+            started: false,
+        }
+    }
+}
+
+impl<A, B> CompletionFuture for RaceEager<A, B>
+where
+    A: CompletionFuture + Unpin + 'static,
+    B: CompletionFuture<Output = A::Output> + Unpin + 'static,
+{
+    type Output = (A::Output, CancelRemaining<A, B>);
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.started = true;
+
+        let a = this.a.as_mut().expect("polled after completion");
+        if let Poll::Ready(output) = Pin::new(a).poll(cx) {
+            this.a = None;
+            return Poll::Ready((output, CancelRemaining::new(None, this.b.take())));
+        }
+        let b = this.b.as_mut().expect("polled after completion");
+        if let Poll::Ready(output) = Pin::new(b).poll(cx) {
+            this.b = None;
+            return Poll::Ready((output, CancelRemaining::new(this.a.take(), None)));
+        }
+        Poll::Pending
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        ready_both(&mut this.a, &mut this.b, cx)
+    }
+}
+
+impl<A, B> Drop for RaceEager<A, B> {
+    fn drop(&mut self) {
+        if self.started {
+            mem::forget(self.a.take());
+            mem::forget(self.b.take());
+        }
+    }
+}
+
+/// The cancellation of the losing future of a [`RaceEager`].
+///
+/// Both [`poll`](CompletionFuture::poll) and [`poll_cancel`](CompletionFuture::poll_cancel)
+/// drive the cancellation, and complete once the loser has finished cancelling.
+///
+/// The loser has already been polled, so if this is dropped before it completes the loser is
+/// leaked instead of dropped. Any resources it holds are not released until it is cancelled.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+#[must_use = "the losing future is leaked unless this is driven to completion"]
+pub struct CancelRemaining<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+}
+
+impl<A: 'static, B: 'static> CancelRemaining<A, B> {
+    fn new(a: Option<A>, b: Option<B>) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A, B> CompletionFuture for CancelRemaining<A, B>
+where
+    A: CompletionFuture + Unpin + 'static,
+    B: CompletionFuture + Unpin + 'static,
+{
+    type Output = ();
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready_both(&mut this.a, &mut this.b, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        ready_both(&mut this.a, &mut this.b, cx)
+    }
+}
+
+impl<A, B> Drop for CancelRemaining<A, B> {
+    fn drop(&mut self) {
+        mem::forget(self.a.take());
+        mem::forget(self.b.take());
+    }
+}
+
+/// Cancel both futures if they are present, removing each one once it has finished cancelling.
+unsafe fn ready_both<A, B>(a: &mut Option<A>, b: &mut Option<B>, cx: &mut Context<'_>) -> Poll<()>
+where
+    A: CompletionFuture + Unpin,
+    B: CompletionFuture + Unpin,
+{
+    if let Some(fut) = a {
+        if Pin::new(fut).poll_cancel(cx).is_ready() {
+            *a = None;
+        }
+    }
+    if let Some(fut) = b {
+        if Pin::new(fut).poll_cancel(cx).is_ready() {
+            *b = None;
+        }
+    }
+    if a.is_none() && b.is_none() {
+        Poll::Ready(())
+    } else {
+        Poll::Pending
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, pending, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    fn poll<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<F::Output> {
+        unsafe { Pin::new(fut).poll(&mut crate::noop_cx()) }
+    }
+
+    #[test]
+    fn race_waits_for_cancellation() {
+        let (loser, counts) = Yield::new(3, pending::<i32>()).count_polls();
+        // The loser has finished cancelling by the time the race resolves.
+        assert_eq!(block_on(ready(1).race(loser)), 1);
+        assert_eq!(counts.cancels(), 4);
+    }
+
+    #[test]
+    fn eager_returns_before_cancellation() {
+        let (loser, counts) = Yield::new(3, pending::<i32>()).count_polls();
+        let mut fut = RaceEager::new(ready(1).check(), loser);
+
+        let (output, mut cancel) = match poll(&mut fut) {
+            Poll::Ready(res) => res,
+            Poll::Pending => panic!("winner was not returned immediately"),
+        };
+        assert_eq!(output, 1);
+        assert_eq!(counts.cancels(), 0);
+
+        assert_eq!(poll(&mut cancel), Poll::Pending);
+        assert_eq!(counts.cancels(), 1);
+        block_on(cancel);
+        assert_eq!(counts.cancels(), 4);
+    }
+
+    #[test]
+    fn second_wins() {
+        let fut = RaceEager::new(Yield::once(ready(1)), ready(2).check().max_cancels(0));
+        let (output, cancel) = block_on(fut);
+        assert_eq!(output, 2);
+        block_on(cancel);
+    }
+}
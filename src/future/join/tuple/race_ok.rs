@@ -1,3 +1,4 @@
+use core::fmt::{self, Debug, Display, Formatter};
 use core::marker::PhantomData;
 use core::pin::Pin;
 use core::task::{Context, Poll};
@@ -10,8 +11,8 @@ use super::base::{Join, JoinTuple};
 
 /// Wait for the first future in a tuple to successfully complete.
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -74,7 +75,7 @@ use super::base::{Join, JoinTuple};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn race_ok<T: RaceOkTuple>(futures: T) -> RaceOk<T> {
     RaceOk {
         inner: Join::new(futures.into_tuple()),
@@ -84,7 +85,7 @@ pub fn race_ok<T: RaceOkTuple>(futures: T) -> RaceOk<T> {
 
 pin_project! {
     /// Future for [`race_ok`].
-    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     #[derive(Debug)]
     pub struct RaceOk<T: RaceOkTuple> {
         #[pin]
@@ -107,6 +108,72 @@ impl<T: RaceOkTuple> CompletionFuture for RaceOk<T> {
     }
 }
 
+pin_project! {
+    /// Future for [`CompletionFutureExt::race_ok`](crate::CompletionFutureExt::race_ok).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub struct RaceOkPair<A: TryFuture, B: TryFuture<Ok = A::Ok>> {
+        #[pin]
+        inner: RaceOk<(A, B)>,
+    }
+}
+
+impl<A: TryFuture, B: TryFuture<Ok = A::Ok>> RaceOkPair<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            inner: race_ok((a, b)),
+        }
+    }
+}
+
+impl<A: TryFuture, B: TryFuture<Ok = A::Ok>> Debug for RaceOkPair<A, B>
+where
+    RaceOk<(A, B)>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceOkPair")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<A: TryFuture, B: TryFuture<Ok = A::Ok>> CompletionFuture for RaceOkPair<A, B> {
+    type Output = Result<A::Ok, RaceOkErrors<A::Error, B::Error>>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project()
+            .inner
+            .poll(cx)
+            .map(|res| res.map_err(|(first, second)| RaceOkErrors { first, second }))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+}
+
+/// The errors of both futures in a [`RaceOkPair`], returned if they both fail.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RaceOkErrors<A, B> {
+    /// The error of the first future.
+    pub first: A,
+    /// The error of the second future.
+    pub second: B,
+}
+
+impl<A: Display, B: Display> Display for RaceOkErrors<A, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "both futures failed: {}; {}", self.first, self.second)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A, B> std::error::Error for RaceOkErrors<A, B>
+where
+    A: std::error::Error,
+    B: std::error::Error,
+{
+}
+
 /// A tuple of futures that can be used in `RaceOk`.
 pub trait RaceOkTuple {
     /// The tuple that can be used with `Join`.
@@ -0,0 +1,231 @@
+use core::pin::Pin;
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use super::super::{catch_unwind, Panic};
+
+/// Which future a [`RacePreferring`] chooses when both are ready at the same time.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preference {
+    /// The first future is always polled first, so if it is ready it always wins. The second
+    /// future is only polled when the first one is pending.
+    First,
+    /// The second future is always polled first, so if it is ready it always wins. The first
+    /// future is only polled when the second one is pending.
+    Second,
+    /// The order in which the futures are polled is chosen pseudo-randomly each time, so neither
+    /// future can starve the other. The randomness comes from a simple xorshift generator and is
+    /// not suitable for anything security-sensitive.
+    Random,
+}
+
+pin_project! {
+    /// Future for
+    /// [`CompletionFutureExt::race_preferring`](crate::CompletionFutureExt::race_preferring).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct RacePreferring<A: CompletionFuture, B> {
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+        preference: Preference,
+        rng: u32,
+        a_running: bool,
+        b_running: bool,
+        // The outcome of the race, present while the loser is being cancelled.
+        result: Option<Result<A::Output, Panic>>,
+    }
+}
+
+impl<A: CompletionFuture, B> RacePreferring<A, B> {
+    pub(crate) fn new(a: A, b: B, preference: Preference) -> Self {
+        // Give each race a different seed. The seed must be non-zero for xorshift to work.
+        static SEED: AtomicU32 = AtomicU32::new(0);
+        let rng = SEED
+            .fetch_add(0x9E37_79B9, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9)
+            | 1;
+
+        Self {
+            a,
+            b,
+            preference,
+            rng,
+            a_running: true,
+            b_running: true,
+            result: None,
+        }
+    }
+}
+
+impl<A, B> RacePreferring<A, B>
+where
+    A: CompletionFuture,
+    B: CompletionFuture<Output = A::Output>,
+{
+    /// Cancel all the futures that are still running, storing the first panic that occurs.
+    unsafe fn cancel_remaining(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let (a, b) = (this.a, this.b);
+
+        if *this.a_running {
+            match catch_unwind(|| a.poll_cancel(cx)) {
+                Ok(Poll::Ready(())) => *this.a_running = false,
+                Ok(Poll::Pending) => {}
+                Err(panic) => {
+                    *this.a_running = false;
+                    store_panic(this.result, panic);
+                }
+            }
+        }
+        if *this.b_running {
+            match catch_unwind(|| b.poll_cancel(cx)) {
+                Ok(Poll::Ready(())) => *this.b_running = false,
+                Ok(Poll::Pending) => {}
+                Err(panic) => {
+                    *this.b_running = false;
+                    store_panic(this.result, panic);
+                }
+            }
+        }
+
+        if *this.a_running || *this.b_running {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+/// Store a panic as the result of the race, unless a panic has already been stored.
+fn store_panic<T>(result: &mut Option<Result<T, Panic>>, panic: Panic) {
+    if !matches!(result, Some(Err(_))) {
+        *result = Some(Err(panic));
+    }
+}
+
+impl<A, B> CompletionFuture for RacePreferring<A, B>
+where
+    A: CompletionFuture,
+    B: CompletionFuture<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    unsafe fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.as_mut().project();
+
+        if this.result.is_none() {
+            let a_first = match this.preference {
+                Preference::First => true,
+                Preference::Second => false,
+                Preference::Random => {
+                    *this.rng ^= *this.rng << 13;
+                    *this.rng ^= *this.rng >> 17;
+                    *this.rng ^= *this.rng << 5;
+                    *this.rng & 1 == 0
+                }
+            };
+
+            for &poll_a in &[a_first, !a_first] {
+                let res = if poll_a {
+                    catch_unwind(|| this.a.as_mut().poll(cx))
+                } else {
+                    catch_unwind(|| this.b.as_mut().poll(cx))
+                };
+                let res = match res {
+                    Ok(Poll::Ready(val)) => Ok(val),
+                    Ok(Poll::Pending) => continue,
+                    Err(panic) => Err(panic),
+                };
+                if poll_a {
+                    *this.a_running = false;
+                } else {
+                    *this.b_running = false;
+                }
+                *this.result = Some(res);
+                break;
+            }
+
+            if this.result.is_none() {
+                return Poll::Pending;
+            }
+        }
+
+        ready!(self.as_mut().cancel_remaining(cx));
+
+        match self.project().result.take().unwrap() {
+            Ok(val) => Poll::Ready(val),
+            Err(panic) => panic.resume(),
+        }
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        ready!(self.as_mut().cancel_remaining(cx));
+
+        if let Some(Err(panic)) = self.project().result.take() {
+            panic.resume();
+        }
+        Poll::Ready(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::future::ready;
+
+    use crate::future::{block_on, FutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn prefer_first() {
+        let fut = RacePreferring::new(
+            ready(1).into_completion().check().max_cancels(0),
+            ready(2).into_completion().check().max_polls(0),
+            Preference::First,
+        );
+        assert_eq!(block_on(fut), 1);
+    }
+
+    #[test]
+    fn prefer_second() {
+        let fut = RacePreferring::new(
+            ready(1).into_completion().check().max_polls(0),
+            ready(2).into_completion().check().max_cancels(0),
+            Preference::Second,
+        );
+        assert_eq!(block_on(fut), 2);
+    }
+
+    #[test]
+    fn preferred_pending() {
+        let fut = RacePreferring::new(
+            Yield::once(ready(1).into_completion()).check(),
+            ready(2).into_completion().check().max_cancels(0),
+            Preference::First,
+        );
+        assert_eq!(block_on(fut), 2);
+    }
+
+    #[test]
+    fn random() {
+        let mut wins = [0; 2];
+        for _ in 0..100 {
+            let fut = RacePreferring::new(
+                ready(0).into_completion().check(),
+                ready(1).into_completion().check(),
+                Preference::Random,
+            );
+            wins[block_on(fut)] += 1;
+        }
+        assert!(wins[0] > 0);
+        assert!(wins[1] > 0);
+    }
+}
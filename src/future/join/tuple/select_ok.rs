@@ -0,0 +1,146 @@
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+use super::super::TryFuture;
+use super::race_ok::{RaceOkErrors, RaceOkPair};
+use crate::future::Either;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::select_ok`](crate::CompletionFutureExt::select_ok).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct SelectOk<A: TryFuture, B: TryFuture> {
+        #[pin]
+        inner: RaceOkPair<LeftOk<A, B::Ok>, RightOk<A::Ok, B>>,
+    }
+}
+
+impl<A: TryFuture, B: TryFuture> SelectOk<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            inner: RaceOkPair::new(
+                LeftOk {
+                    inner: a,
+                    _right: PhantomData,
+                },
+                RightOk {
+                    inner: b,
+                    _left: PhantomData,
+                },
+            ),
+        }
+    }
+}
+
+impl<A: TryFuture, B: TryFuture> Debug for SelectOk<A, B>
+where
+    RaceOkPair<LeftOk<A, B::Ok>, RightOk<A::Ok, B>>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectOk")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<A: TryFuture, B: TryFuture> CompletionFuture for SelectOk<A, B> {
+    type Output = Result<Either<A::Ok, B::Ok>, RaceOkErrors<A::Error, B::Error>>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+}
+
+pin_project! {
+    /// Wraps the success value of the first future in `Either::Left`.
+    #[derive(Debug)]
+    pub struct LeftOk<F, R> {
+        #[pin]
+        inner: F,
+        _right: PhantomData<fn() -> R>,
+    }
+}
+
+impl<F: TryFuture, R> CompletionFuture for LeftOk<F, R> {
+    type Output = Result<Either<F::Ok, R>, F::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx).map_ok(Either::Left)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+}
+
+pin_project! {
+    /// Wraps the success value of the second future in `Either::Right`.
+    #[derive(Debug)]
+    pub struct RightOk<L, F> {
+        #[pin]
+        inner: F,
+        _left: PhantomData<fn() -> L>,
+    }
+}
+
+impl<L, F: TryFuture> CompletionFuture for RightOk<L, F> {
+    type Output = Result<Either<L, F::Ok>, F::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx).map_ok(Either::Right)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::future::ready;
+
+    use crate::future::{block_on, CompletionFutureExt, FutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn first_fails_second_succeeds() {
+        let fut = SelectOk::new(
+            ready(Err::<i32, _>("first")).into_completion().check(),
+            Yield::once(ready(Ok::<_, ()>("second")).into_completion()).check(),
+        );
+        assert_eq!(block_on(fut), Ok(Either::Right("second")));
+    }
+
+    #[test]
+    fn loser_cancelled() {
+        let (slow, counts) = Yield::new(5, ready(Ok::<_, ()>(0)).into_completion()).count_polls();
+        let fast = Yield::once(ready(Ok::<_, ()>("fast")).into_completion());
+        let fut = SelectOk::new(slow, fast);
+        assert_eq!(block_on(fut), Ok(Either::Right("fast")));
+        assert_ne!(counts.polls(), 0);
+        assert_ne!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn both_fail() {
+        let fut = SelectOk::new(
+            ready(Err::<(), _>(1)).into_completion(),
+            Yield::once(ready(Err::<i32, _>("two")).into_completion()),
+        );
+        assert_eq!(
+            block_on(fut),
+            Err(RaceOkErrors {
+                first: 1,
+                second: "two",
+            })
+        );
+    }
+}
@@ -13,8 +13,8 @@ use super::base::{Join, JoinTuple};
 /// This takes any tuple of two or more futures. On success it outputs a tuple of the results, and
 /// on failure it returns the error.
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -42,7 +42,7 @@ use super::base::{Join, JoinTuple};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn try_zip<T: TryZipTuple>(futures: T) -> TryZip<T> {
     TryZip {
         inner: Join::new(futures.into_tuple()),
@@ -52,7 +52,7 @@ pub fn try_zip<T: TryZipTuple>(futures: T) -> TryZip<T> {
 
 pin_project! {
     /// Future for [`try_zip`].
-    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     #[derive(Debug)]
     pub struct TryZip<T: TryZipTuple> {
         #[pin]
@@ -1,9 +1,11 @@
 use core::convert::Infallible;
+use core::fmt::{self, Debug, Formatter};
 use core::marker::PhantomData;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
 use completion_core::CompletionFuture;
+use futures_core::ready;
 use pin_project_lite::pin_project;
 
 use super::super::{ControlFlow, ZipFuture};
@@ -13,8 +15,8 @@ use super::base::{Join, JoinTuple};
 ///
 /// This takes any tuple of two or more futures, and outputs a tuple of the results.
 ///
-/// Requires the `std` feature, as [`std::panic::catch_unwind`] is needed when polling the futures
-/// to ensure soundness.
+/// Requires the `alloc` feature. Without the `std` feature, panics in any of the futures will abort
+/// the process, as [`std::panic::catch_unwind`] is needed to cancel the other futures soundly.
 ///
 /// # Examples
 ///
@@ -32,7 +34,20 @@ use super::base::{Join, JoinTuple};
 /// );
 /// # });
 /// ```
-#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+///
+/// Optional futures can be zipped directly, since [`Option`] is a future that outputs [`None`]
+/// immediately if it is [`None`]:
+///
+/// ```
+/// use completion::{future, completion_async};
+///
+/// # future::block_on(completion_async! {
+/// let (a, b) = future::zip((Some(completion_async!(5)), None::<future::Ready<()>>)).await;
+/// assert_eq!(a, Some(5));
+/// assert_eq!(b, None);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub fn zip<T: ZipTuple>(futures: T) -> Zip<T> {
     Zip {
         inner: Join::new(futures.into_tuple()),
@@ -42,7 +57,7 @@ pub fn zip<T: ZipTuple>(futures: T) -> Zip<T> {
 
 pin_project! {
     /// Future for [`zip`].
-    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     #[derive(Debug)]
     pub struct Zip<T: ZipTuple> {
         #[pin]
@@ -65,6 +80,58 @@ impl<T: ZipTuple> CompletionFuture for Zip<T> {
     }
 }
 
+pin_project! {
+    /// Future for [`CompletionFutureExt::zip_with`](crate::CompletionFutureExt::zip_with).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct ZipWith<A: CompletionFuture, B: CompletionFuture, F> {
+        #[pin]
+        inner: Zip<(A, B)>,
+        f: Option<F>,
+    }
+}
+
+impl<A: CompletionFuture, B: CompletionFuture, F> ZipWith<A, B, F> {
+    pub(crate) fn new(a: A, b: B, f: F) -> Self {
+        Self {
+            inner: zip((a, b)),
+            f: Some(f),
+        }
+    }
+}
+
+impl<A, B, F> Debug for ZipWith<A, B, F>
+where
+    A: CompletionFuture,
+    B: CompletionFuture,
+    Zip<(A, B)>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipWith")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A, B, F, T> CompletionFuture for ZipWith<A, B, F>
+where
+    A: CompletionFuture,
+    B: CompletionFuture,
+    F: FnOnce(A::Output, B::Output) -> T,
+{
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let (a, b) = ready!(this.inner.poll(cx));
+        let f = this.f.take().expect("polled after completion");
+        Poll::Ready(f(a, b))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+}
+
 /// A tuple of futures that can be used in `Zip`.
 pub trait ZipTuple {
     /// The tuple that can be used with `Join`.
@@ -0,0 +1,114 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread::{self, JoinHandle};
+
+use completion_core::CompletionFuture;
+
+use super::{spawn_blocking, SpawnBlocking};
+
+/// Wait for a thread to finish, resolving with its result.
+///
+/// This resolves with the same value as [`JoinHandle::join`]: `Ok` with the thread's return value,
+/// or `Err` with the panic payload if the thread panicked.
+///
+/// A [`JoinHandle`] can't notify anyone when its thread finishes, so the join is performed on the
+/// [`spawn_blocking`] pool, which occupies one of its threads for as long as the joined thread
+/// runs. If the thread was spawned specifically to be awaited, using `spawn_blocking` directly is
+/// cheaper.
+///
+/// A thread can't be interrupted, so cancelling or dropping the returned future detaches it: the
+/// cancellation finishes immediately and the thread keeps running, as if its handle had been
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// let handle = thread::spawn(|| 5);
+/// assert_eq!(future::join_thread(handle).await.unwrap(), 5);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn join_thread<T: Send + 'static>(handle: JoinHandle<T>) -> JoinThread<T> {
+    JoinThread {
+        inner: spawn_blocking(move || handle.join()),
+    }
+}
+
+/// Future for [`join_thread`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct JoinThread<T> {
+    inner: SpawnBlocking<thread::Result<T>>,
+}
+
+impl<T> CompletionFuture for JoinThread<T> {
+    type Output = thread::Result<T>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Future::poll(self, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut self.get_mut().inner).poll_cancel(cx)
+    }
+}
+
+impl<T> Future for JoinThread<T> {
+    type Output = thread::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Future::poll(Pin::new(&mut self.get_mut().inner), cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use crate::future::block_on;
+
+    #[test]
+    fn returns_value() {
+        let handle = thread::spawn(|| {
+            thread::sleep(Duration::from_millis(10));
+            "done"
+        });
+        assert_eq!(block_on(join_thread(handle)).unwrap(), "done");
+    }
+
+    #[test]
+    fn panic_returned() {
+        let handle = thread::spawn(|| panic!("oh no"));
+        let payload = block_on(join_thread(handle)).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oh no"));
+    }
+
+    #[test]
+    fn cancel_detaches() {
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (done_tx, done_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            release_rx.recv().unwrap();
+            done_tx.send(()).unwrap();
+        });
+        let mut fut = join_thread(handle);
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(cx) }.is_ready());
+        drop(fut);
+
+        // The thread keeps running.
+        release_tx.send(()).unwrap();
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+}
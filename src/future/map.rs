@@ -0,0 +1,312 @@
+use core::convert::TryInto;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::map_into`](super::CompletionFutureExt::map_into).
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct MapInto<F, T> {
+        #[pin]
+        fut: F,
+        _output: PhantomData<fn() -> T>,
+    }
+}
+
+impl<F, T> MapInto<F, T> {
+    pub(super) fn new(fut: F) -> Self {
+        Self {
+            fut,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<F: Debug, T> Debug for MapInto<F, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapInto").field("fut", &self.fut).finish()
+    }
+}
+
+impl<F: Clone, T> Clone for MapInto<F, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.fut.clone())
+    }
+}
+
+impl<F, T> CompletionFuture for MapInto<F, T>
+where
+    F: CompletionFuture,
+    F::Output: Into<T>,
+{
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx).map(Into::into)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F, T> Future for MapInto<F, T>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    <F as CompletionFuture>::Output: Into<T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::try_map_into`](super::CompletionFutureExt::try_map_into).
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct TryMapInto<F, T> {
+        #[pin]
+        fut: F,
+        _output: PhantomData<fn() -> T>,
+    }
+}
+
+impl<F, T> TryMapInto<F, T> {
+    pub(super) fn new(fut: F) -> Self {
+        Self {
+            fut,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<F: Debug, T> Debug for TryMapInto<F, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryMapInto")
+            .field("fut", &self.fut)
+            .finish()
+    }
+}
+
+impl<F: Clone, T> Clone for TryMapInto<F, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.fut.clone())
+    }
+}
+
+impl<F, T> CompletionFuture for TryMapInto<F, T>
+where
+    F: CompletionFuture,
+    F::Output: TryInto<T>,
+{
+    type Output = Result<T, <F::Output as TryInto<T>>::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx).map(TryInto::try_into)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F, T> Future for TryMapInto<F, T>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    <F as CompletionFuture>::Output: TryInto<T>,
+{
+    type Output = Result<T, <<F as CompletionFuture>::Output as TryInto<T>>::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::err_into`](super::CompletionFutureExt::err_into).
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct ErrInto<F, E> {
+        #[pin]
+        fut: F,
+        _error: PhantomData<fn() -> E>,
+    }
+}
+
+impl<F, E> ErrInto<F, E> {
+    pub(super) fn new(fut: F) -> Self {
+        Self {
+            fut,
+            _error: PhantomData,
+        }
+    }
+}
+
+impl<F: Debug, E> Debug for ErrInto<F, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrInto").field("fut", &self.fut).finish()
+    }
+}
+
+impl<F: Clone, E> Clone for ErrInto<F, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.fut.clone())
+    }
+}
+
+impl<F, T, E, E2> CompletionFuture for ErrInto<F, E2>
+where
+    F: CompletionFuture<Output = Result<T, E>>,
+    E: Into<E2>,
+{
+    type Output = Result<T, E2>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project()
+            .fut
+            .poll(cx)
+            .map(|res| res.map_err(Into::into))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F, T, E, E2> Future for ErrInto<F, E2>
+where
+    F: CompletionFuture<Output = Result<T, E>> + Future<Output = Result<T, E>>,
+    E: Into<E2>,
+{
+    type Output = Result<T, E2>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::unwrap_or`](super::CompletionFutureExt::unwrap_or).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct UnwrapOr<F, T> {
+        #[pin]
+        fut: F,
+        default: Option<T>,
+    }
+}
+
+impl<F, T> UnwrapOr<F, T> {
+    pub(super) fn new(fut: F, default: T) -> Self {
+        Self {
+            fut,
+            default: Some(default),
+        }
+    }
+}
+
+impl<F, T> CompletionFuture for UnwrapOr<F, T>
+where
+    F: CompletionFuture<Output = Option<T>>,
+{
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = ready!(this.fut.poll(cx));
+        let default = this.default.take().expect("polled after completion");
+        Poll::Ready(output.unwrap_or(default))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F, T> Future for UnwrapOr<F, T>
+where
+    F: CompletionFuture<Output = Option<T>> + Future<Output = Option<T>>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::map_or`](super::CompletionFutureExt::map_or).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct MapOr<F, T, G> {
+        #[pin]
+        fut: F,
+        // The default and the function, taken when the future completes.
+        state: Option<(T, G)>,
+    }
+}
+
+impl<F, T, G> MapOr<F, T, G> {
+    pub(super) fn new(fut: F, default: T, f: G) -> Self {
+        Self {
+            fut,
+            state: Some((default, f)),
+        }
+    }
+}
+
+impl<F, T, U, G> CompletionFuture for MapOr<F, T, G>
+where
+    F: CompletionFuture<Output = Option<U>>,
+    G: FnOnce(U) -> T,
+{
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = ready!(this.fut.poll(cx));
+        let (default, f) = this.state.take().expect("polled after completion");
+        Poll::Ready(output.map_or(default, f))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F, T, U, G> Future for MapOr<F, T, G>
+where
+    F: CompletionFuture<Output = Option<U>> + Future<Output = Option<U>>,
+    G: FnOnce(U) -> T,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[derive(Debug, PartialEq)]
+    struct ParseError;
+
+    #[derive(Debug, PartialEq)]
+    enum ModuleError {
+        Parse(ParseError),
+    }
+
+    impl From<ParseError> for ModuleError {
+        fn from(e: ParseError) -> Self {
+            Self::Parse(e)
+        }
+    }
+
+    #[test]
+    fn err_into_widens() {
+        let fut = Yield::once(ready(Err::<u32, _>(ParseError))).err_into::<_, _, ModuleError>();
+        assert_eq!(block_on(fut.check()), Err(ModuleError::Parse(ParseError)));
+
+        let fut = Yield::once(ready(Ok::<_, ParseError>(5))).err_into::<_, _, ModuleError>();
+        assert_eq!(block_on(fut.check()), Ok(5));
+    }
+}
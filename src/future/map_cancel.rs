@@ -0,0 +1,91 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::map_cancel`](super::CompletionFutureExt::map_cancel).
+    #[derive(Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct MapCancel<F, C> {
+        #[pin]
+        fut: F,
+        on_cancel_poll: C,
+    }
+}
+
+impl<F, C> MapCancel<F, C> {
+    pub(super) fn new(fut: F, on_cancel_poll: C) -> Self {
+        Self {
+            fut,
+            on_cancel_poll,
+        }
+    }
+}
+
+impl<F: Debug, C> Debug for MapCancel<F, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapCancel")
+            .field("fut", &self.fut)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: CompletionFuture, C: FnMut()> CompletionFuture for MapCancel<F, C> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        (this.on_cancel_poll)();
+        this.fut.poll_cancel(cx)
+    }
+}
+
+impl<F, C> Future for MapCancel<F, C>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    C: FnMut(),
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    use crate::future::{pending, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    fn fires_per_cancel_poll() {
+        let calls = Cell::new(0);
+        // After one poll, cancelling this takes three polls.
+        let mut fut = Yield::new(3, pending::<()>()).map_cancel(|| calls.set(calls.get() + 1));
+
+        let cx = &mut crate::noop_cx();
+        let mut fut = unsafe { Pin::new_unchecked(&mut fut) };
+        unsafe {
+            assert!(CompletionFuture::poll(fut.as_mut(), cx).is_pending());
+            assert_eq!(calls.get(), 0);
+
+            for i in 1..=2 {
+                assert!(fut.as_mut().poll_cancel(cx).is_pending());
+                assert_eq!(calls.get(), i);
+            }
+            assert!(fut.as_mut().poll_cancel(cx).is_ready());
+            assert_eq!(calls.get(), 3);
+        }
+    }
+}
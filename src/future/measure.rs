@@ -0,0 +1,67 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::measure`](super::CompletionFutureExt::measure).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Measure<F> {
+        #[pin]
+        fut: F,
+        start: Option<Instant>,
+    }
+}
+
+impl<F> Measure<F> {
+    pub(super) fn new(fut: F) -> Self {
+        Self { fut, start: None }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for Measure<F> {
+    type Output = (F::Output, Duration);
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let start = *this.start.get_or_insert_with(Instant::now);
+        let output = ready!(this.fut.poll(cx));
+        Poll::Ready((output, start.elapsed()))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+
+impl<F> Future for Measure<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = (<F as CompletionFuture>::Output, Duration);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use crate::future::{block_on, ready, CompletionFutureExt, FutureExt};
+
+    #[test]
+    fn starts_on_first_poll() {
+        let fut = ready(()).into_completion().measure();
+        thread::sleep(Duration::from_millis(50));
+        let ((), elapsed) = block_on(fut);
+        assert!(elapsed < Duration::from_millis(50));
+    }
+}
@@ -4,17 +4,26 @@
 //! [`zip`], [`race`], [`zip_all`], etc) in this module adopt an efficient polling strategy, where
 //! they only poll the futures that issued wakeups, instead of polling every single future whenever
 //! just one of them issues a wakeup. This reduces their complexity from `O(n^2)` to `O(n)`, making
-//! them suitable for large numbers of futures.
+//! them suitable for large numbers of futures. The exceptions are [`zip_array`] and
+//! [`race_array`], which poll all their futures on every wakeup so that they don't need to
+//! allocate.
 
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
 #[cfg(feature = "std")]
 use core::any::Any;
+use core::convert::TryInto;
 use core::future::Future;
 use core::pin::Pin;
-use core::task::{Context, Poll};
+use core::task::{Context, Poll, Waker};
 #[cfg(feature = "std")]
 use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
+#[cfg(feature = "std")]
+use std::time::Duration;
 
 #[doc(no_inline)]
 pub use core::future::{pending, ready, Pending, Ready};
@@ -30,72 +39,1237 @@ use super::{Adapter, MustComplete};
 #[cfg(feature = "std")]
 mod block_on;
 #[cfg(feature = "std")]
-pub use block_on::block_on;
+pub use block_on::{block_on, block_on_all};
 
 #[cfg(feature = "std")]
-mod join;
+mod sleep;
+#[cfg(feature = "std")]
+pub(crate) use sleep::{saturating_add, sleep_until};
+#[cfg(feature = "std")]
+pub use sleep::{sleep, Sleep};
+
+#[cfg(feature = "std")]
+mod spawn_blocking;
+#[cfg(feature = "std")]
+pub use spawn_blocking::{spawn_blocking, SpawnBlocking};
+
+#[cfg(feature = "std")]
+mod join_thread;
 #[cfg(feature = "std")]
+pub use join_thread::{join_thread, JoinThread};
+
+mod join;
+#[cfg(feature = "alloc")]
 pub use join::{
-    race, race_all, race_ok, race_ok_all, try_zip, try_zip_all, zip, zip_all, Race, RaceAll,
-    RaceOk, RaceOkAll, RaceOkAllErrors, TryZip, TryZipAll, TryZipAllOutput, Zip, ZipAll,
-    ZipAllOutput,
+    cancel_group, race, race_all, race_ok, race_ok_all, race_ok_all_lazy, try_zip, try_zip_all,
+    try_zip_all_partial, zip, zip_all, zip_all_lazy, CancelGroup, CancelRemaining, Preference,
+    Race, RaceAll, RaceEager, RaceOk, RaceOkAll, RaceOkAllErrors, RaceOkAllLazy, RaceOkErrors,
+    RaceOkPair, RacePreferring, SelectOk, TryZip, TryZipAll, TryZipAllOutput, TryZipAllPartial,
+    Zip, ZipAll, ZipAllLazy, ZipAllOutput, ZipWith,
 };
+pub use join::{race_array, zip_array, RaceArray, ZipArray};
+
+mod into_future;
+pub use into_future::IntoCompletionFuture;
+
+mod chain;
+pub use chain::Chain;
+
+mod either;
+pub use either::Either;
 
 mod now_or_never;
 pub use now_or_never::NowOrNever;
 
-/// Extension trait for [`CompletionFuture`].
-pub trait CompletionFutureExt: CompletionFuture {
-    /// A convenience for calling [`CompletionFuture::poll`] on [`Unpin`] futures.
+mod never;
+pub use never::{never, Never};
+
+mod yield_now;
+pub use yield_now::{yield_now, YieldNow};
+
+mod named;
+pub use named::Named;
+
+mod ensure;
+pub use ensure::Ensure;
+
+mod catch_cancel;
+pub use catch_cancel::CatchCancel;
+
+mod map_cancel;
+pub use map_cancel::MapCancel;
+
+mod assert_never_cancelled;
+pub use assert_never_cancelled::AssertNeverCancelled;
+
+mod unpin;
+pub use unpin::AssertUnpin;
+
+mod map;
+pub use map::{ErrInto, MapInto, MapOr, TryMapInto, UnwrapOr};
+
+mod with_budget;
+pub use with_budget::WithBudget;
+
+mod retry;
+pub use retry::{retry_if, RetryIf};
+
+#[cfg(feature = "alloc")]
+mod with_waker_hook;
+#[cfg(feature = "alloc")]
+pub use with_waker_hook::WithWakerHook;
+
+mod with_waker;
+pub use with_waker::{MapWaker, WithWaker};
+
+#[cfg(feature = "alloc")]
+mod flatten_join;
+#[cfg(feature = "alloc")]
+pub use flatten_join::FlattenJoin;
+
+mod std_future;
+pub use std_future::{PollCancelFreeOnDrop, StdFuture};
+
+#[cfg(feature = "std")]
+mod measure;
+#[cfg(feature = "std")]
+pub use measure::Measure;
+
+#[cfg(all(feature = "std", feature = "allocator_api"))]
+mod box_pool;
+#[cfg(all(feature = "std", feature = "allocator_api"))]
+pub use box_pool::BoxPool;
+
+#[cfg(feature = "std")]
+mod timeout_keep;
+#[cfg(feature = "std")]
+pub use timeout_keep::TimeoutKeep;
+
+#[cfg(feature = "std")]
+mod with_deadline;
+#[cfg(feature = "std")]
+pub use with_deadline::{DeadlineHandle, TimedOut, WithDeadline};
+
+#[cfg(feature = "std")]
+mod catch_unwind_full;
+#[cfg(feature = "std")]
+pub use catch_unwind_full::CatchUnwindFull;
+
+#[cfg(feature = "std")]
+mod unzip;
+#[cfg(feature = "std")]
+pub use unzip::{FutureHalf, Pair};
+
+#[cfg(feature = "std")]
+mod scoped;
+#[cfg(feature = "std")]
+pub use scoped::Scoped;
+
+#[cfg(feature = "std")]
+mod scope;
+#[cfg(feature = "std")]
+pub use scope::{scope, Scope, Spawner};
+
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+pub use shared::{Shared, SharedPanicked, WeakShared};
+
+#[cfg(feature = "test-util")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test-util")))]
+pub mod test;
+
+#[cfg(feature = "alloc")]
+mod erased;
+#[cfg(feature = "alloc")]
+pub use erased::{ErasedCompletionFuture, ErasedVTable};
+
+#[cfg(feature = "alloc")]
+mod small_box;
+#[cfg(feature = "alloc")]
+pub use small_box::{SmallBoxCompletionFuture, SMALL_BOX_SIZE};
+
+#[cfg(feature = "alloc")]
+mod count_polls;
+#[cfg(feature = "alloc")]
+pub use count_polls::{CountPolls, PollCounts};
+
+/// Extension trait for [`CompletionFuture`].
+pub trait CompletionFutureExt: CompletionFuture {
+    /// A convenience for calling [`CompletionFuture::poll`] on [`Unpin`] futures.
+    ///
+    /// # Safety
+    ///
+    /// Identical to [`CompletionFuture::poll`].
+    unsafe fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Self::Output>
+    where
+        Self: Unpin,
+    {
+        Pin::new(self).poll(cx)
+    }
+
+    /// A convenience for calling [`CompletionFuture::poll_cancel`] on [`Unpin`] futures.
+    ///
+    /// # Safety
+    ///
+    /// Identical to [`CompletionFuture::poll_cancel`].
+    unsafe fn poll_cancel(&mut self, cx: &mut Context<'_>) -> Poll<()>
+    where
+        Self: Unpin,
+    {
+        Pin::new(self).poll_cancel(cx)
+    }
+
+    /// Make sure that the future will complete. Any requests to cancel the future through
+    /// [`poll_cancel`](CompletionFuture::poll_cancel) will be ignored.
+    fn must_complete(self) -> MustComplete<Self>
+    where
+        Self: Sized,
+    {
+        MustComplete { inner: self }
+    }
+
+    /// Assert that the future is never cancelled.
+    ///
+    /// With debug assertions enabled, the returned future panics if
+    /// [`poll_cancel`](CompletionFuture::poll_cancel) is ever called on it. This is useful for
+    /// auditing code to catch places where a future that is believed to never be cancelled is
+    /// being cancelled. Without debug assertions, this behaves like
+    /// [`must_complete`](Self::must_complete) and ignores all requests to cancel the future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// assert_eq!(completion_async!(5).assert_never_cancelled().await, 5);
+    /// # });
+    /// ```
+    fn assert_never_cancelled(self) -> AssertNeverCancelled<Self>
+    where
+        Self: Sized,
+    {
+        AssertNeverCancelled::new(self)
+    }
+
+    /// Assert that the future is [`Unpin`].
+    ///
+    /// This does nothing at runtime. It is useful in generic code to require `Unpin` at the point
+    /// where the future is created, so that a future that isn't `Unpin` is reported there instead
+    /// of deep inside whichever combinator needs it. To make a future that isn't `Unpin` usable
+    /// in those places, use [`unsync_unpin`](Self::unsync_unpin).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// # future::block_on(completion::completion_async! {
+    /// let mut fut = future::ready(5).assert_unpin();
+    /// assert_eq!((&mut fut).await, 5);
+    /// # });
+    /// ```
+    fn assert_unpin(self) -> AssertUnpin<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        AssertUnpin::new(self)
+    }
+
+    /// Convert the future into a regular [`Future`].
+    ///
+    /// This is only available for futures that can be soundly dropped at any time, as expressed by
+    /// the [`PollCancelFreeOnDrop`] trait. To use a future that must complete with an API that
+    /// takes a regular future, see [`AssertCompletes`](crate::AssertCompletes) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// let fut = future::ready(5).into_std_future();
+    /// assert_eq!(futures_lite::future::block_on(fut), 5);
+    /// ```
+    ///
+    /// Futures that must complete can't be converted:
+    ///
+    /// ```compile_fail
+    /// use completion::{completion_async, CompletionFutureExt};
+    ///
+    /// let fut = completion_async!(5).into_std_future();
+    /// ```
+    fn into_std_future(self) -> StdFuture<Self>
+    where
+        Self: Sized + PollCancelFreeOnDrop,
+    {
+        StdFuture::new(self)
+    }
+
+    /// Box the future as a regular [`Future`], erasing its type.
+    ///
+    /// This is [`into_std_future`](Self::into_std_future) followed by boxing, and so is only
+    /// available for futures that can be soundly dropped at any time. The result can be stored in
+    /// places that expect a boxed regular future, such as the `BoxFuture` type of the `futures`
+    /// crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::future::Future;
+    /// use std::pin::Pin;
+    ///
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+    ///
+    /// let a: BoxFuture<'_, i32> = future::ready(1).boxed_std();
+    /// let b: BoxFuture<'_, i32> = future::ready(2).boxed_std();
+    /// assert_eq!(futures_lite::future::block_on(futures_lite::future::zip(a, b)), (1, 2));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn boxed_std<'a>(self) -> Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>
+    where
+        Self: Sized + PollCancelFreeOnDrop + Send + 'a,
+    {
+        Box::pin(StdFuture::new(self))
+    }
+
+    /// Make the future safe to drop by cancelling it in its destructor.
+    ///
+    /// If the returned future is dropped after it has been polled but before it has completed or
+    /// been cancelled, its destructor drives [`poll_cancel`](CompletionFuture::poll_cancel) to
+    /// completion, so the inner future is always cancelled rather than leaked or dropped midway.
+    /// Because of this it also implements [`Future`] when the inner future is `'static`, and can be
+    /// passed to APIs that take regular futures. The bound is needed because safe code can still
+    /// leak the returned future with [`mem::forget`](core::mem::forget), skipping its destructor,
+    /// which is only sound if the inner future doesn't borrow anything.
+    ///
+    /// Note that this cancellation **blocks the current thread** until it finishes, parking it
+    /// between polls like [`block_on`]. Dropping a `Scoped` future within an async context can
+    /// therefore stall the executor, and if the cancellation depends on that same executor making
+    /// progress it will deadlock. Prefer cancelling futures asynchronously where possible, and use
+    /// this as a safety net for futures that own resources which must be released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// let fut = completion_async!(5).scoped();
+    /// assert_eq!(futures_lite::future::block_on(fut), 5);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn scoped(self) -> Scoped<Self>
+    where
+        Self: Sized,
+    {
+        Scoped::new(self)
+    }
+
+    /// Get the future's output if it's ready, or cancel it if it's not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// assert_eq!(future::ready(5).now_or_never().await, Some(5));
+    /// assert_eq!(future::pending::<()>().now_or_never().await, None);
+    /// # });
+    /// ```
+    fn now_or_never(self) -> NowOrNever<Self>
+    where
+        Self: Sized,
+    {
+        NowOrNever::new(self)
+    }
+
+    /// Convert the output of the future using [`Into`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use std::io;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(io::Error::new(io::ErrorKind::Other, "oh no"));
+    /// let error: Box<dyn Error> = fut.map_into().await;
+    /// assert_eq!(error.to_string(), "oh no");
+    /// # });
+    /// ```
+    fn map_into<T>(self) -> MapInto<Self, T>
+    where
+        Self: Sized,
+        Self::Output: Into<T>,
+    {
+        MapInto::new(self)
+    }
+
+    /// Convert the output of the future using [`TryInto`].
+    ///
+    /// The conversion is only run when the future completes, never when it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, future};
+    ///
+    /// # future::block_on(completion::completion_async! {
+    /// assert_eq!(future::ready(200_u32).try_map_into::<u8>().await, Ok(200));
+    /// assert!(future::ready(300_u32).try_map_into::<u8>().await.is_err());
+    /// # });
+    /// ```
+    fn try_map_into<T>(self) -> TryMapInto<Self, T>
+    where
+        Self: Sized,
+        Self::Output: TryInto<T>,
+    {
+        TryMapInto::new(self)
+    }
+
+    /// Convert the error of the future's [`Result`] output using [`Into`].
+    ///
+    /// This is useful for awaiting a lower-level operation and propagating its error with `?` as a
+    /// wider error type. The conversion is only run when the future completes with an error, never
+    /// when it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use std::io;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(Err::<(), _>(io::Error::new(io::ErrorKind::Other, "oh no")));
+    /// let res: Result<(), Box<dyn Error>> = fut.err_into().await;
+    /// assert_eq!(res.unwrap_err().to_string(), "oh no");
+    /// # });
+    /// ```
+    fn err_into<T, E, E2>(self) -> ErrInto<Self, E2>
+    where
+        Self: Sized + CompletionFuture<Output = Result<T, E>>,
+        E: Into<E2>,
+    {
+        ErrInto::new(self)
+    }
+
+    /// Unwrap the future's [`Option`] output, falling back to a default value if it is `None`.
+    ///
+    /// The default is dropped without being used if the future is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// assert_eq!(completion_async!(Some(5)).unwrap_or(0).await, 5);
+    /// assert_eq!(completion_async!(None).unwrap_or(0).await, 0);
+    /// # });
+    /// ```
+    fn unwrap_or<T>(self, default: T) -> UnwrapOr<Self, T>
+    where
+        Self: Sized + CompletionFuture<Output = Option<T>>,
+    {
+        UnwrapOr::new(self, default)
+    }
+
+    /// Map the future's [`Option`] output with a closure, falling back to a default value if it is
+    /// `None`.
+    ///
+    /// The closure is only called once the future has completed with `Some`; if the future is
+    /// cancelled, neither the closure nor the default is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// assert_eq!(completion_async!(Some("hello")).map_or(0, str::len).await, 5);
+    /// assert_eq!(completion_async!(None).map_or(0, str::len).await, 0);
+    /// # });
+    /// ```
+    fn map_or<T, U, F>(self, default: T, f: F) -> MapOr<Self, T, F>
+    where
+        Self: Sized + CompletionFuture<Output = Option<U>>,
+        F: FnOnce(U) -> T,
+    {
+        MapOr::new(self, default, f)
+    }
+
+    /// Observe cancellation of the future as a value.
+    ///
+    /// The returned future outputs `Some` if the inner future completes normally. If it is
+    /// cancelled instead, once [`poll_cancel`](CompletionFuture::poll_cancel) has returned
+    /// [`Poll::Ready`] the next call to [`poll`](CompletionFuture::poll) will output `None`.
+    ///
+    /// Note that this relaxes the usual contract of [`CompletionFuture`]: this future may be polled
+    /// once more after it has finished cancelling. Code that follows the normal contract never
+    /// does so, in which case this future behaves exactly like the inner one. It is still up to the
+    /// caller to uphold the safety requirements of polling the inner future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::pin::Pin;
+    /// use std::task::Poll;
+    ///
+    /// use completion::{future, CompletionFuture, CompletionFutureExt};
+    /// use futures_lite::future::poll_fn;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// assert_eq!(future::ready(5).catch_cancel().await, Some(5));
+    ///
+    /// let mut fut = future::pending::<()>().catch_cancel();
+    /// let output = poll_fn(|cx| unsafe {
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+    ///     Pin::new(&mut fut).poll(cx)
+    /// })
+    /// .await;
+    /// assert_eq!(output, None);
+    /// # });
+    /// ```
+    fn catch_cancel(self) -> CatchCancel<Self>
+    where
+        Self: Sized,
+    {
+        CatchCancel::new(self)
+    }
+
+    /// Call a closure every time the future is polled for cancellation.
+    ///
+    /// The closure is called before each call to
+    /// [`poll_cancel`](CompletionFuture::poll_cancel) is forwarded to the inner future, so a
+    /// future whose cancellation takes several polls will call it several times. Calls to
+    /// [`poll`](CompletionFuture::poll) are forwarded untouched. This is useful for tracing the
+    /// progress of a future's cancellation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::pin::Pin;
+    /// use std::task::Poll;
+    ///
+    /// use completion::{future, CompletionFuture, CompletionFutureExt};
+    /// use futures_lite::future::poll_fn;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut cancel_polls = 0;
+    /// let mut fut = future::pending::<()>().map_cancel(|| cancel_polls += 1);
+    /// poll_fn(|cx| unsafe {
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+    ///     Poll::Ready(())
+    /// })
+    /// .await;
+    /// drop(fut);
+    /// assert_eq!(cancel_polls, 1);
+    /// # });
+    /// ```
+    fn map_cancel<F>(self, on_each_cancel_poll: F) -> MapCancel<Self, F>
+    where
+        F: FnMut(),
+        Self: Sized,
+    {
+        MapCancel::new(self, on_each_cancel_poll)
+    }
+
+    /// Give the future a cooperative budget of polls.
+    ///
+    /// Once the inner future has been polled `budget` times in a row without completing, the
+    /// returned future yields to the executor by waking itself immediately and returning
+    /// [`Poll::Pending`] without polling the inner future. The budget is then reset, so progress
+    /// resumes on the next poll. Cancellation is forwarded to the inner future unchanged.
+    ///
+    /// This only makes a difference under executors that run several tasks, where it gives the
+    /// other tasks a chance to run; a single-future executor like [`block_on`] will just poll the
+    /// future again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::pin::Pin;
+    /// use std::task::Poll;
+    ///
+    /// use completion::{future, CompletionFuture, CompletionFutureExt};
+    /// use futures_lite::future::poll_fn;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let (fut, counts) = future::pending::<()>().count_polls();
+    /// let mut fut = fut.with_budget(2);
+    ///
+    /// poll_fn(|cx| unsafe {
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert_eq!(counts.polls(), 2);
+    ///
+    ///     // The budget is exhausted, so the inner future isn't polled.
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert_eq!(counts.polls(), 2);
+    ///
+    ///     assert!(Pin::new(&mut fut).poll(cx).is_pending());
+    ///     assert_eq!(counts.polls(), 3);
+    ///
+    ///     assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+    ///     Poll::Ready(())
+    /// })
+    /// .await;
+    /// # });
+    /// ```
+    fn with_budget(self, budget: usize) -> WithBudget<Self>
+    where
+        Self: Sized,
+    {
+        WithBudget::new(self, budget)
+    }
+
+    /// Call a function whenever the future wakes its task, for diagnosing missed wakeups.
+    ///
+    /// Every time the returned future is polled or cancelled, the waker in the [`Context`] is
+    /// wrapped in one that calls `on_wake` before forwarding the wakeup. This only observes
+    /// wakeups, not the inner future registering or cloning the waker.
+    ///
+    /// This is intended for debugging, not production use: each call to `poll` or `poll_cancel`
+    /// allocates a new waker and clones the original one, and every wakeup goes through an extra
+    /// indirection. The wrapped waker also never compares equal to the original, so
+    /// [`Waker::will_wake`](core::task::Waker::will_wake) optimizations in the inner future are
+    /// defeated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use futures_lite::future::yield_now;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async! {
+    ///     yield_now().await;
+    ///     5
+    /// };
+    /// let wakes = Arc::new(AtomicUsize::new(0));
+    /// let hook_wakes = Arc::clone(&wakes);
+    /// let fut = fut.with_waker_hook(move || {
+    ///     hook_wakes.fetch_add(1, Ordering::SeqCst);
+    /// });
+    /// assert_eq!(fut.await, 5);
+    /// assert_eq!(wakes.load(Ordering::SeqCst), 1);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn with_waker_hook<F>(self, on_wake: F) -> WithWakerHook<Self, F>
+    where
+        Self: Sized,
+        F: Fn() + Send + Sync + 'static,
+    {
+        WithWakerHook::new(self, on_wake)
+    }
+
+    /// Poll this future with a different waker.
+    ///
+    /// Every time the returned future is polled or cancelled, the inner future sees `waker` in its
+    /// [`Context`] instead of the caller's waker. This is useful for executors that need futures
+    /// to wake something other than the task that is currently polling them.
+    ///
+    /// Note that the caller's waker is never woken by the inner future, so it is up to the owner of
+    /// `waker` to poll the returned future again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use futures_lite::future::yield_now;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let waker = futures_lite::future::poll_fn(|cx| std::task::Poll::Ready(cx.waker().clone()))
+    ///     .await;
+    ///
+    /// let fut = completion_async! {
+    ///     yield_now().await;
+    ///     5
+    /// };
+    /// assert_eq!(fut.with_waker(waker).await, 5);
+    /// # });
+    /// ```
+    fn with_waker(self, waker: Waker) -> WithWaker<Self>
+    where
+        Self: Sized,
+    {
+        WithWaker::new(self, waker)
+    }
+
+    /// Replace the waker seen by this future on every poll.
+    ///
+    /// Every time the returned future is polled or cancelled, `f` is called with the caller's waker
+    /// and the inner future sees the waker it returns in its [`Context`]. This is the general form
+    /// of [`with_waker`](Self::with_waker), and can be used to wrap the caller's waker, for
+    /// example to attach per-task data to wakeups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use futures_lite::future::yield_now;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let mut polls = 0;
+    /// let fut = completion_async! {
+    ///     yield_now().await;
+    ///     5
+    /// };
+    /// let fut = fut.map_waker(|waker| {
+    ///     polls += 1;
+    ///     waker.clone()
+    /// });
+    /// assert_eq!(fut.await, 5);
+    /// assert_eq!(polls, 2);
+    /// # });
+    /// ```
+    fn map_waker<F>(self, f: F) -> MapWaker<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Waker) -> Waker,
+    {
+        MapWaker::new(self, f)
+    }
+
+    /// Run this future to completion, discarding its output, then run another.
+    ///
+    /// This is like `then(|_| next)`, but doesn't need a closure. The `next` future is not polled
+    /// until this one has completed, and cancelling the returned future cancels whichever of the
+    /// two is currently running.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let flushed = Cell::new(false);
+    /// let fut = completion_async!(flushed.set(true)).chain(completion_async!("closed"));
+    /// assert_eq!(fut.await, "closed");
+    /// assert!(flushed.get());
+    /// # });
+    /// ```
+    fn chain<F>(self, next: F) -> Chain<Self, F::IntoFut>
+    where
+        F: IntoCompletionFuture,
+        Self: Sized,
+    {
+        Chain::new(self, next.into_completion_future())
+    }
+
+    /// Run a cleanup future to completion after this future completes or is cancelled.
+    ///
+    /// This is useful for asynchronous resource release that has to happen however the operation
+    /// ends. The cleanup future is not polled until this future has completed or finished
+    /// cancelling, and the returned future only outputs once the cleanup has completed.
+    ///
+    /// # Cancellation
+    ///
+    /// Cancelling the returned future cancels this future if it is still running, and then runs
+    /// the cleanup future to completion. The cleanup future itself is never cancelled, so
+    /// cancellation can take as long as the cleanup does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let closed = Cell::new(false);
+    /// let fut = completion_async!("response").ensure(completion_async!(closed.set(true)));
+    /// assert_eq!(fut.await, "response");
+    /// assert!(closed.get());
+    /// # });
+    /// ```
+    fn ensure<C>(self, cleanup: C) -> Ensure<Self, C>
+    where
+        C: CompletionFuture<Output = ()>,
+        Self: Sized,
+    {
+        Ensure::new(self, cleanup)
+    }
+
+    /// Wait for this future to output a collection of futures, then wait for all of those to
+    /// complete.
+    ///
+    /// This is equivalent to passing this future's output to [`zip_all`], and outputs a `Vec` of
+    /// the results in the same order as the futures. Cancelling the returned future cancels this
+    /// future if it hasn't completed yet, and all the inner futures otherwise.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionFutureExt, completion_async};
+    ///
+    /// # future::block_on(completion_async! {
+    /// let fut = completion_async! {
+    ///     let ids = vec![1, 2, 3];
+    ///     ids.into_iter().map(|id| future::ready(id * 10)).collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(fut.flatten_join().await, [10, 20, 30]);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn flatten_join(self) -> FlattenJoin<Self>
+    where
+        Self: Sized,
+        Self::Output: IntoIterator,
+        <Self::Output as IntoIterator>::Item: CompletionFuture,
+    {
+        FlattenJoin::new(self)
+    }
+
+    /// Wait for both futures to complete, outputting both their results.
+    ///
+    /// This is a method form of [`zip`] for two futures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(5).zip(completion_async!("hello"));
+    /// assert_eq!(fut.await, (5, "hello"));
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn zip<F>(self, other: F) -> Zip<(Self, F::IntoFut)>
+    where
+        Self: Sized,
+        F: IntoCompletionFuture,
+    {
+        zip((self, other.into_completion_future()))
+    }
+
+    /// Wait for both futures to complete, combining their outputs with a function.
+    ///
+    /// This is equivalent to `.zip(other).map(|(a, b)| f(a, b))`. Cancelling the returned future
+    /// cancels both futures, and `f` is only called if they both complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, future};
+    ///
+    /// # future::block_on(completion::completion_async! {
+    /// let fut = future::ready(5).zip_with(future::ready(6), |a, b| a + b);
+    /// assert_eq!(fut.await, 11);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn zip_with<B, F, T>(self, other: B, f: F) -> ZipWith<Self, B::IntoFut, F>
+    where
+        Self: Sized,
+        B: IntoCompletionFuture,
+        F: FnOnce(Self::Output, B::Output) -> T,
+    {
+        ZipWith::new(self, other.into_completion_future(), f)
+    }
+
+    /// Wait for both futures to successfully complete or for one of them to return an error.
+    ///
+    /// This is a method form of [`try_zip`] for two futures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(Ok::<_, ()>(5)).try_zip(completion_async!(Ok(6)));
+    /// assert_eq!(fut.await, Ok((5, 6)));
+    ///
+    /// let fut = completion_async!(Ok(5)).try_zip(completion_async!(Err::<(), _>(6)));
+    /// assert_eq!(fut.await, Err(6));
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn try_zip<F, T, U, E>(self, other: F) -> TryZip<(Self, F::IntoFut)>
+    where
+        Self: Sized + CompletionFuture<Output = Result<T, E>>,
+        F: IntoCompletionFuture<Output = Result<U, E>>,
+    {
+        try_zip((self, other.into_completion_future()))
+    }
+
+    /// Wait for the first of the two futures to complete, and cancel the other one.
+    ///
+    /// This is a method form of [`race`] for two futures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use futures_lite::future::yield_now;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let slow = completion_async! {
+    ///     yield_now().await;
+    ///     0
+    /// };
+    /// assert_eq!(slow.race(completion_async!(1)).await, 1);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn race<F>(self, other: F) -> Race<(Self, F::IntoFut)>
+    where
+        Self: Sized,
+        F: IntoCompletionFuture<Output = Self::Output>,
+    {
+        race((self, other.into_completion_future()))
+    }
+
+    /// Wait for the first of two futures to complete, returning its output without waiting for the
+    /// other one to be cancelled.
+    ///
+    /// [`race`](Self::race) only resolves once the losing future has finished cancelling, which is
+    /// what makes it safe to use on its own, but it means the winner's output is held back for as
+    /// long as the loser takes to cancel. This future instead resolves as soon as one of the
+    /// futures completes, outputting the winner's output alongside a [`CancelRemaining`] future
+    /// that cancels the loser.
+    ///
+    /// The caller is responsible for driving the [`CancelRemaining`] to completion. If it is
+    /// dropped early, the loser is leaked rather than dropped, so any resources it holds are never
+    /// released. Because of this both futures must be `'static`, as a leaked future that borrows
+    /// data could otherwise keep using it after the borrow ends.
+    ///
+    /// Both futures must be [`Unpin`], since the loser is moved into the [`CancelRemaining`] after
+    /// it has been polled. Futures that aren't can be pinned with [`Box::pin`] first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use futures_lite::future::yield_now;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let slow = Box::pin(completion_async! {
+    ///     yield_now().await;
+    ///     0
+    /// });
+    /// let (output, cancel) = slow.race_eager(Box::pin(completion_async!(1))).await;
+    /// assert_eq!(output, 1);
+    /// cancel.await;
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn race_eager<F>(self, other: F) -> RaceEager<Self, F::IntoFut>
+    where
+        Self: Sized + Unpin + 'static,
+        F: IntoCompletionFuture<Output = Self::Output>,
+        F::IntoFut: Unpin + 'static,
+    {
+        RaceEager::new(self, other.into_completion_future())
+    }
+
+    /// Wait for the first of two futures to complete, choosing which one wins when both are ready
+    /// at the same time.
+    ///
+    /// Both futures are polled every time the returned future is woken, in an order determined by
+    /// the [`Preference`]; the first one found to be ready wins, and the other is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use completion::future::Preference;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(1).race_preferring(completion_async!(2), Preference::Second);
+    /// assert_eq!(fut.await, 2);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn race_preferring<F>(
+        self,
+        other: F,
+        preference: Preference,
+    ) -> RacePreferring<Self, F::IntoFut>
+    where
+        Self: Sized,
+        F: IntoCompletionFuture<Output = Self::Output>,
+    {
+        RacePreferring::new(self, other.into_completion_future(), preference)
+    }
+
+    /// Wait for the first of two futures to successfully complete.
+    ///
+    /// This is a method form of [`race_ok`] for two futures. If both futures fail, their errors
+    /// are returned in a [`RaceOkErrors`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use completion::future::RaceOkErrors;
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(Err::<i32, _>("oh no"))
+    ///     .race_ok(completion_async!(Ok::<_, ()>(5)));
+    /// assert_eq!(fut.await, Ok(5));
+    ///
+    /// let fut = completion_async!(Err::<(), _>(1)).race_ok(completion_async!(Err("two")));
+    /// assert_eq!(fut.await, Err(RaceOkErrors { first: 1, second: "two" }));
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn race_ok<F, T, E, U>(self, other: F) -> RaceOkPair<Self, F::IntoFut>
+    where
+        Self: Sized + CompletionFuture<Output = Result<T, E>>,
+        F: IntoCompletionFuture<Output = Result<T, U>>,
+    {
+        RaceOkPair::new(self, other.into_completion_future())
+    }
+
+    /// Wait for the first of two futures with different success types to successfully complete.
+    ///
+    /// This is like [`race_ok`](Self::race_ok), but the futures may succeed with different types,
+    /// so the first success is returned in an [`Either`]. Once one future succeeds the other is
+    /// cancelled. If both futures fail, their errors are returned in a [`RaceOkErrors`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use completion::future::{Either, RaceOkErrors};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(Err::<i32, _>("oh no"))
+    ///     .select_ok(completion_async!(Ok::<_, &str>("five")));
+    /// assert_eq!(fut.await, Ok(Either::Right("five")));
+    ///
+    /// let fut = completion_async!(Err::<(), _>(1)).select_ok(completion_async!(Err::<u8, _>(2)));
+    /// assert_eq!(fut.await, Err(RaceOkErrors { first: 1, second: 2 }));
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn select_ok<F, T, E, U, V>(self, other: F) -> SelectOk<Self, F::IntoFut>
+    where
+        Self: Sized + CompletionFuture<Output = Result<T, E>>,
+        F: IntoCompletionFuture<Output = Result<U, V>>,
+    {
+        SelectOk::new(self, other.into_completion_future())
+    }
+
+    /// Split a future that outputs a pair into two futures, each outputting one element of it.
+    ///
+    /// Both halves share the same underlying future; whichever half is polled drives it, and once
+    /// it completes each half takes its own element. The elements are moved out of the shared slot
+    /// rather than cloned, so neither needs to implement `Clone`.
+    ///
+    /// Cancelling or dropping one half leaves the future running for the other half. The
+    /// underlying future is only cancelled once neither half wants its output any more; if one
+    /// half is cancelled after the future has started while the other half hasn't been polled yet,
+    /// the cancelled half keeps driving the future until it completes, so that the other half can
+    /// still be dropped at any time.
+    ///
+    /// Polling a half after the underlying future was cancelled panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let (number, string) = completion_async!((5, String::from("hello"))).unzip_future();
+    /// assert_eq!(string.await, "hello");
+    /// assert_eq!(number.await, 5);
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn unzip_future<A, B>(self) -> (FutureHalf<Self, 0>, FutureHalf<Self, 1>)
+    where
+        Self: Sized + CompletionFuture<Output = (A, B)>,
+    {
+        unzip::unzip(self)
+    }
+
+    /// Turn this future into one that can be cloned, with every clone receiving a clone of its
+    /// output.
     ///
-    /// # Safety
+    /// The underlying future is driven by whichever handles are polling it. Cancelling a handle
+    /// while another one is still polling detaches it; if other handles exist but none are being
+    /// polled, the cancelled handle keeps driving the future until it completes so that they can
+    /// still be dropped at any time. The underlying future is only cancelled once no strong
+    /// handles want its output any more.
     ///
-    /// Identical to [`CompletionFuture::poll`].
-    unsafe fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Self::Output>
+    /// [`Shared::downgrade`] creates a [`WeakShared`] handle, which doesn't keep the future alive
+    /// and can be [upgraded](WeakShared::upgrade) back into a strong handle as long as the future
+    /// hasn't been cancelled.
+    ///
+    /// Polling a handle after the underlying future was cancelled panics. If the underlying future
+    /// panics, the handle driving it continues unwinding with that panic, and every other handle
+    /// panics with a [`SharedPanicked`] payload when it is next polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let a = completion_async!(String::from("hello")).shared();
+    /// let weak = a.downgrade();
+    /// let b = weak.upgrade().unwrap();
+    /// assert_eq!(a.await, "hello");
+    /// assert_eq!(b.await, "hello");
+    ///
+    /// // All the strong handles are gone.
+    /// assert!(weak.upgrade().is_none());
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn shared(self) -> Shared<Self>
     where
-        Self: Unpin,
+        Self: Sized,
+        Self::Output: Clone,
     {
-        Pin::new(self).poll(cx)
+        Shared::new(self)
     }
 
-    /// A convenience for calling [`CompletionFuture::poll_cancel`] on [`Unpin`] futures.
+    /// Measure how long the future takes to complete.
     ///
-    /// # Safety
+    /// The output is the future's output alongside the wall-clock time between the first call to
+    /// `poll` and the future's completion. The timer starts when the future is first polled, not
+    /// when this method is called. The elapsed time includes any time spent waiting to be polled
+    /// again after the future returns [`Poll::Pending`], so a future that sits idle for a long
+    /// time before being woken will report that idle time too.
     ///
-    /// Identical to [`CompletionFuture::poll_cancel`].
-    unsafe fn poll_cancel(&mut self, cx: &mut Context<'_>) -> Poll<()>
+    /// Cancelling the future forwards the cancellation and produces no measurement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// # future::block_on(completion::completion_async! {
+    /// let ((), elapsed) = future::sleep(Duration::from_millis(10)).measure().await;
+    /// assert!(elapsed >= Duration::from_millis(10));
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn measure(self) -> Measure<Self>
     where
-        Self: Unpin,
+        Self: Sized,
     {
-        Pin::new(self).poll_cancel(cx)
+        Measure::new(self)
     }
 
-    /// Make sure that the future will complete. Any requests to cancel the future through
-    /// [`poll_cancel`](CompletionFuture::poll_cancel) will be ignored.
-    fn must_complete(self) -> MustComplete<Self>
+    /// Stop polling the future if it doesn't complete within a duration, handing it back instead
+    /// of cancelling it.
+    ///
+    /// If the future completes in time, its output is returned in `Ok`. Otherwise the future is
+    /// returned in `Err`, uncompleted and uncancelled, so that it can be retried or given a new
+    /// deadline. The timer starts when this function is called.
+    ///
+    /// The future handed back in `Err` may already have been polled, so unless it also implements
+    /// [`Future`] the caller is responsible for either driving it to completion or cancelling it,
+    /// just as if they had polled it themselves.
+    ///
+    /// The future must be [`Unpin`] since it is moved out after being polled. Futures that aren't
+    /// can be pinned with [`Box::pin`] first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use completion::future;
+    ///
+    /// # future::block_on(completion_async! {
+    /// let slow = future::sleep(Duration::from_millis(200));
+    /// let slow = match slow.timeout_keep(Duration::from_millis(1)).await {
+    ///     Ok(()) => unreachable!(),
+    ///     Err(slow) => slow,
+    /// };
+    /// // Give it more time.
+    /// assert!(slow.timeout_keep(Duration::from_secs(10)).await.is_ok());
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn timeout_keep(self, duration: Duration) -> TimeoutKeep<Self>
     where
-        Self: Sized,
+        Self: Sized + Unpin,
     {
-        MustComplete { inner: self }
+        TimeoutKeep::new(self, duration)
     }
 
-    /// Get the future's output if it's ready, or cancel it if it's not.
+    /// Cancel the future if it doesn't complete before a deadline that can be extended.
+    ///
+    /// The deadline starts `initial` from now, and can be pushed out using the returned
+    /// [`DeadlineHandle`], for example whenever a heartbeat is received. If the deadline passes
+    /// before the future completes, the future is cancelled and this outputs [`Err(TimedOut)`]
+    /// once the cancellation has finished.
     ///
     /// # Examples
     ///
     /// ```
-    /// use completion::{future, CompletionFutureExt};
+    /// use std::time::Duration;
     ///
-    /// # completion::future::block_on(completion::completion_async! {
-    /// assert_eq!(future::ready(5).now_or_never().await, Some(5));
-    /// assert_eq!(future::pending::<()>().now_or_never().await, None);
+    /// use completion::{CompletionFutureExt, completion_async};
+    /// use completion::future::{self, TimedOut};
+    ///
+    /// # future::block_on(completion_async! {
+    /// let (fut, handle) = future::sleep(Duration::from_millis(50))
+    ///     .with_deadline(Duration::from_millis(1));
+    /// handle.extend(Duration::from_secs(10));
+    /// assert_eq!(fut.await, Ok(()));
+    ///
+    /// let (fut, _) = future::pending::<()>().with_deadline(Duration::from_millis(1));
+    /// assert_eq!(fut.await, Err(TimedOut));
     /// # });
     /// ```
-    fn now_or_never(self) -> NowOrNever<Self>
+    ///
+    /// [`Err(TimedOut)`]: TimedOut
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn with_deadline(self, initial: Duration) -> (WithDeadline<Self>, DeadlineHandle)
     where
         Self: Sized,
     {
-        NowOrNever::new(self)
+        WithDeadline::new(self, initial)
     }
 
     /// Catch panics in the future.
@@ -119,8 +1293,130 @@ pub trait CompletionFutureExt: CompletionFuture {
         CatchUnwind { inner: self }
     }
 
+    /// Catch panics in the future, including those that occur while it is being cancelled.
+    ///
+    /// Unlike [`catch_unwind`](Self::catch_unwind), which discards panics that occur in
+    /// [`poll_cancel`](CompletionFuture::poll_cancel), this records them:
+    ///
+    /// - If the inner future panics while being polled, this future outputs `Err` with the panic
+    ///   payload. The inner future is never polled or cancelled again.
+    /// - If the inner future panics while being cancelled, the cancellation finishes immediately
+    ///   and the panic payload is stored. If this future is polled again afterwards, it outputs
+    ///   `Err` with that payload instead of polling the inner future.
+    ///
+    /// Either way, the inner future is never touched again after it has panicked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let future = completion_async!(panic!());
+    /// assert!(future.catch_unwind_full().await.is_err());
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn catch_unwind_full(self) -> CatchUnwindFull<Self>
+    where
+        Self: Sized + UnwindSafe,
+    {
+        CatchUnwindFull::new(self)
+    }
+
+    /// Count the number of times the future is polled and cancelled.
+    ///
+    /// This returns the wrapped future along with a shared handle to its counters, which is useful
+    /// for checking in tests that combinators don't poll their futures more than necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionFutureExt};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let (fut, counts) = future::pending::<()>().count_polls();
+    /// assert_eq!(fut.now_or_never().await, None);
+    ///
+    /// assert_eq!(counts.polls(), 1);
+    /// assert_eq!(counts.cancels(), 1);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn count_polls(self) -> (CountPolls<Self>, Arc<PollCounts>)
+    where
+        Self: Sized,
+    {
+        CountPolls::new(self)
+    }
+
+    /// Wrap this future in the left side of an [`Either`].
+    ///
+    /// Together with [`right_future`](Self::right_future), this allows returning different futures
+    /// from the arms of an `if` or `match` without boxing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// # let some_condition = true;
+    /// // These futures are different types, but `Either` makes them the same type.
+    /// let fut = if some_condition {
+    ///     completion_async!(5).left_future()
+    /// } else {
+    ///     completion::future::ready(6).right_future()
+    /// };
+    /// assert_eq!(fut.await, 5);
+    /// # });
+    /// ```
+    fn left_future<B>(self) -> Either<Self, B>
+    where
+        Self: Sized,
+    {
+        Either::Left(self)
+    }
+
+    /// Wrap this future in the right side of an [`Either`].
+    ///
+    /// See [`left_future`](Self::left_future) for more details.
+    fn right_future<A>(self) -> Either<A, Self>
+    where
+        Self: Sized,
+    {
+        Either::Right(self)
+    }
+
+    /// Attach a name to this future, which is shown in its [`Debug`](core::fmt::Debug) output.
+    ///
+    /// This is useful for telling futures apart in logs when their types aren't informative, such
+    /// as when storing type-erased futures. Polling and cancellation are forwarded unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// let fut = completion_async!(5).debug_named("five");
+    /// assert_eq!(format!("{:?}", fut), r#"Named { name: "five", .. }"#);
+    /// ```
+    fn debug_named(self, name: &'static str) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        Named::new(self, name)
+    }
+
     /// Box the future, erasing its type.
     ///
+    /// This allocates with the global allocator every time. On hot paths where boxed futures are
+    /// repeatedly created and dropped, you can reuse allocations with `boxed_pooled` or by boxing
+    /// into your own pool allocator with `boxed_in` (both behind the `allocator_api` feature), or
+    /// by using a global allocator with thread-local caches.
+    ///
     /// # Examples
     ///
     /// ```
@@ -158,6 +1454,30 @@ pub trait CompletionFutureExt: CompletionFuture {
     ///     completion_async!(6).boxed_local()
     /// };
     /// ```
+    ///
+    /// The boxed future doesn't need to be `Send` or `'static`, so it can hold non-`Send` values
+    /// and borrow local data.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use completion::{CompletionFutureExt, LocalBoxCompletionFuture, completion_async_move};
+    ///
+    /// fn fill(buf: &mut Vec<u8>) -> LocalBoxCompletionFuture<'_, usize> {
+    ///     let data = Rc::new([1, 2, 3]);
+    ///     completion_async_move! {
+    ///         buf.extend_from_slice(&*data);
+    ///         buf.len()
+    ///     }
+    ///     .boxed_local()
+    /// }
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut buf = Vec::new();
+    /// assert_eq!(fill(&mut buf).await, 3);
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// # });
+    /// ```
     #[cfg(feature = "alloc")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     fn boxed_local<'a>(self) -> LocalBoxCompletionFuture<'a, Self::Output>
@@ -166,6 +1486,161 @@ pub trait CompletionFutureExt: CompletionFuture {
     {
         Box::pin(self)
     }
+
+    /// Pin the future on the heap, making it [`Unpin`].
+    ///
+    /// Unlike [`boxed`](Self::boxed) and [`boxed_local`](Self::boxed_local) this doesn't erase the
+    /// future's type, and unlike `boxed` the future doesn't need to be `Send`. This allows
+    /// futures that aren't `Unpin`, such as those created by [`completion_async!`], to be used
+    /// with APIs that need `Unpin`, like polling through a `&mut` reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let mut fut = completion_async!(5).unsync_unpin();
+    /// assert_eq!((&mut fut).await, 5);
+    /// # });
+    /// ```
+    ///
+    /// [`completion_async!`]: crate::completion_async
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn unsync_unpin(self) -> Pin<Box<Self>>
+    where
+        Self: Sized,
+    {
+        Box::pin(self)
+    }
+
+    /// Erase the future's type, storing it inline if it is small enough and in a heap allocation
+    /// otherwise.
+    ///
+    /// Futures of up to [`SMALL_BOX_SIZE`] (64) bytes with an alignment of at most 16 are stored
+    /// inline, which avoids allocating for small futures; larger futures are boxed like
+    /// [`boxed`](Self::boxed). Both cases produce the same type. Note that unlike
+    /// [`BoxCompletionFuture`], the result doesn't implement [`Unpin`], so it has to be pinned
+    /// before it can be polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// # let some_condition = true;
+    /// let fut = if some_condition {
+    ///     completion_async!(5).small_boxed()
+    /// } else {
+    ///     completion_async!([6; 100][0]).small_boxed()
+    /// };
+    /// assert!(fut.is_inline());
+    /// assert_eq!(fut.await, 5);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn small_boxed<'a>(self) -> SmallBoxCompletionFuture<'a, Self::Output>
+    where
+        Self: Sized + Send + 'a,
+    {
+        SmallBoxCompletionFuture::new(self)
+    }
+
+    /// Box the future behind a `#[repr(C)]` pointer and vtable, erasing its type.
+    ///
+    /// Unlike [`boxed`](Self::boxed), this doesn't produce a `dyn` trait object, whose layout isn't
+    /// stable, so the resulting [`ErasedCompletionFuture`] can be passed across FFI or plugin
+    /// boundaries. See its documentation for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(5).erase();
+    /// assert_eq!(fut.await, 5);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn erase<'a>(self) -> ErasedCompletionFuture<'a, Self::Output>
+    where
+        Self: Sized + Send + 'a,
+    {
+        ErasedCompletionFuture::new(self)
+    }
+
+    /// Box the future in a custom allocator, erasing its type.
+    ///
+    /// This can be used with arena or pool allocators to avoid going through the global allocator
+    /// for short-lived boxed futures.
+    ///
+    /// The allocator must be `'static` because the future is pinned: if the allocator could be
+    /// dropped first, the future's memory could be reused without its destructor having run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// use completion::{CompletionFutureExt, completion_async};
+    ///
+    /// # completion::future::block_on(completion_async! {
+    /// let fut = completion_async!(5).boxed_in(System);
+    /// assert_eq!(fut.await, 5);
+    /// # });
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "allocator_api")))]
+    fn boxed_in<'a, A>(
+        self,
+        alloc: A,
+    ) -> Pin<Box<dyn CompletionFuture<Output = Self::Output> + Send + 'a, A>>
+    where
+        Self: Sized + Send + 'a,
+        A: Allocator + 'static,
+    {
+        Box::pin_in(self, alloc)
+    }
+
+    /// Box the future, erasing its type, reusing a recently freed allocation if there is one.
+    ///
+    /// This boxes the future in the [`BoxPool`] allocator, which keeps a small cache of freed
+    /// allocations on each thread. When futures of the same type are repeatedly boxed and dropped,
+    /// such as when a combinator is rebuilt on every iteration of a loop, this avoids going through
+    /// the global allocator each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use completion::{CompletionFutureExt, completion_async_move};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// for i in 0..3 {
+    ///     // After the first iteration, this reuses the previous future's allocation.
+    ///     let fut = completion_async_move!(i * 2).boxed_pooled();
+    ///     assert_eq!(fut.await, i * 2);
+    /// }
+    /// # });
+    /// ```
+    #[cfg(all(feature = "std", feature = "allocator_api"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "allocator_api"))))]
+    fn boxed_pooled<'a>(
+        self,
+    ) -> Pin<Box<dyn CompletionFuture<Output = Self::Output> + Send + 'a, BoxPool>>
+    where
+        Self: Sized + Send + 'a,
+    {
+        Box::pin_in(self, BoxPool)
+    }
 }
 
 impl<T: CompletionFuture + ?Sized> CompletionFutureExt for T {}
@@ -214,6 +1689,37 @@ pub type BoxCompletionFuture<'a, T> = Pin<Box<dyn CompletionFuture<Output = T> +
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type LocalBoxCompletionFuture<'a, T> = Pin<Box<dyn CompletionFuture<Output = T> + 'a>>;
 
+/// A type-erased completion future that doesn't require allocation.
+///
+/// Behind a [`Pin<&mut _>`](Pin) this is itself a completion future, allowing futures pinned on
+/// the stack to be dynamically dispatched to.
+///
+/// # Examples
+///
+/// ```
+/// use core::pin::Pin;
+///
+/// use completion::future::{self, DynCompletionFuture};
+/// use completion::completion_async;
+///
+/// # future::block_on(completion_async! {
+/// let a = completion_async!(1);
+/// let b = completion_async! {
+///     future::pending::<()>().await;
+///     2
+/// };
+/// completion::pin!(a, b);
+///
+/// let futures: [Pin<&mut DynCompletionFuture<'_, i32>>; 2] = [a, b];
+/// assert_eq!(future::race_array(futures).await, 1);
+/// # });
+/// ```
+pub type DynCompletionFuture<'a, T> = dyn CompletionFuture<Output = T> + Send + 'a;
+
+/// A type-erased completion future that doesn't require allocation and cannot be sent across
+/// threads.
+pub type LocalDynCompletionFuture<'a, T> = dyn CompletionFuture<Output = T> + 'a;
+
 /// Extension trait for converting [`Future`]s to [`CompletionFuture`]s.
 pub trait FutureExt: Future + Sized {
     /// Convert this future into a [`CompletionFuture`].
@@ -0,0 +1,79 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::debug_named`](super::CompletionFutureExt::debug_named).
+    #[derive(Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Named<F> {
+        #[pin]
+        fut: F,
+        name: &'static str,
+    }
+}
+
+impl<F> Named<F> {
+    pub(super) fn new(fut: F, name: &'static str) -> Self {
+        Self { fut, name }
+    }
+
+    /// Get the name of the future.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<F> Debug for Named<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Named")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for Named<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+
+impl<F> Future for Named<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::format;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    #[test]
+    fn name_in_debug() {
+        let fut = Yield::new(3, ready(5)).debug_named("fetch config");
+        assert_eq!(fut.name(), "fetch config");
+        assert_eq!(
+            format!("{:?}", fut),
+            r#"Named { name: "fetch config", .. }"#
+        );
+        assert_eq!(block_on(fut.check()), 5);
+    }
+}
@@ -0,0 +1,94 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// Create a future that never resolves, but has the given output type.
+///
+/// This behaves like [`pending`](super::pending) and is most useful in `match` or `if` arms that
+/// never finish, where the output type has to unify with that of the other arms. Cancelling it
+/// completes immediately.
+///
+/// # Examples
+///
+/// ```
+/// use completion::future::{self, Either};
+///
+/// # future::block_on(completion::completion_async! {
+/// # let enabled = true;
+/// let fut = if enabled {
+///     Either::Left(future::ready(5))
+/// } else {
+///     // This branch waits forever, but its type still fits in the `Either`.
+///     Either::Right(future::never::<i32>())
+/// };
+/// assert_eq!(fut.await, 5);
+/// # });
+/// ```
+#[must_use = "futures do nothing unless you use them"]
+pub fn never<T>() -> Never<T> {
+    Never {
+        _output: PhantomData,
+    }
+}
+
+/// Future for [`never`].
+#[must_use = "futures do nothing unless you use them"]
+pub struct Never<T> {
+    _output: PhantomData<fn() -> T>,
+}
+
+impl<T> Debug for Never<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("Never")
+    }
+}
+
+impl<T> Clone for Never<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Never<T> {}
+
+impl<T> CompletionFuture for Never<T> {
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Pending
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
+
+impl<T> Future for Never<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Pending
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use crate::future::{block_on, ready, Either, Ready};
+
+    #[test]
+    fn never_in_either_arm() {
+        let mut fut: Either<Ready<&str>, _> = Either::Right(never());
+        let cx = &mut crate::noop_cx();
+        for _ in 0..3 {
+            assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+        }
+        assert!(unsafe { CompletionFuture::poll_cancel(Pin::new(&mut fut), cx) }.is_ready());
+
+        let fut: Either<_, Never<&str>> = Either::Left(ready("done"));
+        assert_eq!(block_on(fut), "done");
+    }
+}
@@ -0,0 +1,188 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+/// Retry a fallible operation when it fails with an error that the predicate accepts.
+///
+/// `factory` is called to create each attempt. If an attempt fails and `predicate` returns `true`
+/// for its error, a new attempt is started, up to `retries` times after the first attempt. Errors
+/// that the predicate rejects are returned immediately, as is the error of the last attempt once
+/// the retries have run out. This allows transient errors like timeouts to be retried while fatal
+/// ones like authentication failures are not.
+///
+/// The first attempt is only created once the returned future is first polled. Cancelling the
+/// returned future cancels the attempt that is currently running.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{future, completion_async_move};
+///
+/// # future::block_on(completion::completion_async! {
+/// let mut attempts = 0;
+/// let res = future::retry_if(
+///     || {
+///         attempts += 1;
+///         let attempt = attempts;
+///         completion_async_move! {
+///             match attempt {
+///                 1 => Err("timeout"),
+///                 2 => Err("auth failure"),
+///                 _ => Ok(()),
+///             }
+///         }
+///     },
+///     5,
+///     |&e| e == "timeout",
+/// )
+/// .await;
+/// assert_eq!(res, Err("auth failure"));
+/// assert_eq!(attempts, 2);
+/// # });
+/// ```
+pub fn retry_if<F, Fut, P, T, E>(factory: F, retries: usize, predicate: P) -> RetryIf<F, Fut, P>
+where
+    F: FnMut() -> Fut,
+    Fut: CompletionFuture<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    RetryIf {
+        factory,
+        predicate,
+        retries,
+        fut: None,
+    }
+}
+
+pin_project! {
+    /// Future for [`retry_if`].
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct RetryIf<F, Fut, P> {
+        factory: F,
+        predicate: P,
+        // The number of retries left.
+        retries: usize,
+        // The attempt that is currently running.
+        #[pin]
+        fut: Option<Fut>,
+    }
+}
+
+impl<F, Fut: Debug, P> Debug for RetryIf<F, Fut, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryIf")
+            .field("retries", &self.retries)
+            .field("fut", &self.fut)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, Fut, P, T, E> CompletionFuture for RetryIf<F, Fut, P>
+where
+    F: FnMut() -> Fut,
+    Fut: CompletionFuture<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    type Output = Result<T, E>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            if this.fut.is_none() {
+                this.fut.set(Some((this.factory)()));
+            }
+
+            let res = ready!(this.fut.as_mut().as_pin_mut().unwrap().poll(cx));
+            this.fut.set(None);
+            match res {
+                Err(e) if *this.retries > 0 && (this.predicate)(&e) => *this.retries -= 1,
+                res => return Poll::Ready(res),
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut this = self.project();
+        if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+            ready!(fut.poll_cancel(cx));
+            this.fut.set(None);
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<F, Fut, P, T, E> Future for RetryIf<F, Fut, P>
+where
+    F: FnMut() -> Fut,
+    Fut: CompletionFuture<Output = Result<T, E>> + Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    /// Run `retry_if` over a sequence of results, returning its output and the number of attempts.
+    fn run(
+        results: &[Result<i32, &'static str>],
+        retries: usize,
+    ) -> (Result<i32, &'static str>, usize) {
+        let attempts = Cell::new(0);
+        let res = block_on(retry_if(
+            || {
+                attempts.set(attempts.get() + 1);
+                Yield::once(ready(results[attempts.get() - 1]))
+            },
+            retries,
+            |&e| e == "timeout",
+        ));
+        (res, attempts.get())
+    }
+
+    #[test]
+    fn retryable_errors_retried() {
+        assert_eq!(run(&[Err("timeout"), Err("timeout"), Ok(5)], 5), (Ok(5), 3));
+        assert_eq!(run(&[Ok(1)], 0), (Ok(1), 1));
+    }
+
+    #[test]
+    fn fatal_error_returned_immediately() {
+        let results = [Err("timeout"), Err("auth"), Ok(5)];
+        assert_eq!(run(&results, 5), (Err("auth"), 2));
+    }
+
+    #[test]
+    fn retries_run_out() {
+        let results = [Err("timeout"); 4];
+        assert_eq!(run(&results, 2), (Err("timeout"), 3));
+    }
+
+    #[test]
+    fn cancel() {
+        let (fut, counts) = Yield::new(5, ready(Err::<(), _>("timeout"))).count_polls();
+        let mut fut = Some(fut);
+        let mut retry = retry_if(move || fut.take().unwrap(), 1, |_| true);
+
+        let cx = &mut crate::noop_cx();
+        let mut retry = unsafe { Pin::new_unchecked(&mut retry) };
+        assert!(unsafe { CompletionFuture::poll(retry.as_mut(), cx) }.is_pending());
+        while unsafe { retry.as_mut().poll_cancel(cx) }.is_pending() {}
+        assert_ne!(counts.cancels(), 0);
+    }
+}
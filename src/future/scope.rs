@@ -0,0 +1,326 @@
+use std::boxed::Box;
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::vec::Vec;
+
+use atomic_waker::AtomicWaker;
+use completion_core::{CompletionFuture, CompletionStream};
+use pin_project_lite::pin_project;
+
+use super::BoxCompletionFuture;
+use crate::stream::Unordered;
+
+/// Run a future that can spawn child futures borrowing local data, and cancel them all before
+/// finishing.
+///
+/// The closure is given a [`Spawner`], which the future it returns (the body) can use to spawn
+/// child futures. The children are driven concurrently with the body, and once the body
+/// completes, any children that are still running are cancelled. This future only finishes after
+/// every child that was polled has completed or finished cancelling, so children can safely borrow
+/// any data that outlives the scope.
+///
+/// Cancelling this future cancels the body and every child concurrently.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// use completion::{future, completion_async_move};
+///
+/// # future::block_on(completion::completion_async! {
+/// let data = vec![1, 2, 3];
+/// let sum = AtomicU32::new(0);
+///
+/// future::scope(|s| {
+///     let (data, sum) = (&data, &sum);
+///     completion_async_move! {
+///         for x in data {
+///             s.spawn(completion_async_move! {
+///                 sum.fetch_add(*x, Ordering::SeqCst);
+///             });
+///         }
+///         // Give the children a chance to run before they are cancelled.
+///         futures_lite::future::yield_now().await;
+///     }
+/// })
+/// .await;
+///
+/// assert_eq!(sum.into_inner(), 6);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn scope<'a, F, Fut>(f: F) -> Scope<'a, Fut>
+where
+    F: FnOnce(Spawner<'a>) -> Fut,
+    Fut: CompletionFuture,
+{
+    let shared = Arc::new(SpawnerShared {
+        pending: Mutex::new(Vec::new()),
+        waker: AtomicWaker::new(),
+    });
+    Scope {
+        body: f(Spawner {
+            shared: Arc::clone(&shared),
+        }),
+        output: None,
+        body_done: false,
+        children: Unordered::new(),
+        shared,
+    }
+}
+
+pin_project! {
+    /// Future for [`scope`].
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Scope<'a, Fut>
+    where
+        Fut: CompletionFuture,
+    {
+        #[pin]
+        body: Fut,
+        output: Option<Fut::Output>,
+        body_done: bool,
+        children: Unordered<BoxCompletionFuture<'a, ()>>,
+        shared: Arc<SpawnerShared<'a>>,
+    }
+}
+
+impl<Fut: CompletionFuture + Debug> Debug for Scope<'_, Fut> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("body", &self.body)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, Fut: CompletionFuture> Scope<'a, Fut> {
+    /// Move the newly spawned futures into the set of children, returning whether there were any.
+    fn take_pending(
+        shared: &SpawnerShared<'a>,
+        children: &mut Unordered<BoxCompletionFuture<'a, ()>>,
+    ) -> bool {
+        let pending = mem::take(
+            &mut *shared
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        let any = !pending.is_empty();
+        // Futures spawned once the children are being cancelled have never been polled, so they
+        // can just be dropped.
+        if !children.is_closed() {
+            for fut in pending {
+                children.push(fut);
+            }
+        }
+        any
+    }
+}
+
+impl<Fut: CompletionFuture> CompletionFuture for Scope<'_, Fut> {
+    type Output = Fut::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.shared.waker.register(cx.waker());
+
+        if !*this.body_done {
+            if let Poll::Ready(output) = this.body.poll(cx) {
+                *this.output = Some(output);
+                *this.body_done = true;
+                this.children.cancel_all();
+            }
+        }
+
+        loop {
+            Self::take_pending(this.shared, this.children);
+            match this.children.poll_next_eager(cx) {
+                Poll::Ready(Some(())) => {}
+                Poll::Ready(None) => break,
+                Poll::Pending => {
+                    // Children may have spawned more futures while they were polled.
+                    if !Self::take_pending(this.shared, this.children) {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        Poll::Ready(this.output.take().expect("polled after completion"))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        this.shared.waker.register(cx.waker());
+
+        this.children.cancel_all();
+        if !*this.body_done && this.body.poll_cancel(cx).is_ready() {
+            *this.body_done = true;
+        }
+        Self::take_pending(this.shared, this.children);
+        let children_done = Pin::new(&mut *this.children).poll_cancel(cx).is_ready();
+
+        if *this.body_done && children_done {
+            *this.output = None;
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A handle used to spawn child futures in a [`scope`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Clone)]
+pub struct Spawner<'a> {
+    shared: Arc<SpawnerShared<'a>>,
+}
+
+struct SpawnerShared<'a> {
+    /// Futures that have been spawned but not yet added to the scope's children.
+    pending: Mutex<Vec<BoxCompletionFuture<'a, ()>>>,
+    /// The waker of the scope.
+    waker: AtomicWaker,
+}
+
+impl<'a> Spawner<'a> {
+    /// Spawn a child future in the scope.
+    ///
+    /// The future will be run concurrently with the scope's body and its other children. If the
+    /// scope has already finished or is being cancelled, the future is dropped without being
+    /// polled.
+    pub fn spawn<F>(&self, fut: F)
+    where
+        F: CompletionFuture<Output = ()> + Send + 'a,
+    {
+        self.shared
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::pin(fut));
+        self.shared.waker.wake();
+    }
+}
+
+impl Debug for Spawner<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spawner").finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "macro"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::future::{block_on, ready};
+    use crate::test_utils::Yield;
+
+    /// A child that never completes, borrows some data, and takes two polls to cancel.
+    struct Child<'a> {
+        data: &'a [usize],
+        index: usize,
+        polls: &'a AtomicUsize,
+        cancelled: &'a AtomicUsize,
+        cancelling: bool,
+    }
+
+    impl CompletionFuture for Child<'_> {
+        type Output = ();
+
+        unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            self.polls.fetch_add(1, Ordering::SeqCst);
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_mut();
+            if !this.cancelling {
+                this.cancelling = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.cancelled
+                .fetch_add(this.data[this.index], Ordering::SeqCst);
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn children_cancelled_before_return() {
+        let data = vec![1, 10, 100];
+        let polls = AtomicUsize::new(0);
+        let cancelled = AtomicUsize::new(0);
+
+        let output = block_on(scope(|s| {
+            let (data, polls, cancelled) = (&data, &polls, &cancelled);
+            crate::completion_async_move! {
+                for index in 0..data.len() {
+                    s.spawn(Child { data, index, polls, cancelled, cancelling: false });
+                }
+                Yield::once(ready(())).await;
+                "done"
+            }
+        }));
+
+        assert_eq!(output, "done");
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+        assert_eq!(cancelled.load(Ordering::SeqCst), 111);
+    }
+
+    #[test]
+    fn children_run_concurrently_with_body() {
+        let data = vec![1, 2, 3];
+        let sum = AtomicUsize::new(0);
+
+        block_on(scope(|s| {
+            let (data, sum) = (&data, &sum);
+            crate::completion_async_move! {
+                for x in data {
+                    // Children can spawn children too.
+                    let inner = s.clone();
+                    s.spawn(crate::completion_async_move! {
+                        sum.fetch_add(*x, Ordering::SeqCst);
+                        inner.spawn(crate::completion_async_move! {
+                            sum.fetch_add(*x * 10, Ordering::SeqCst);
+                        });
+                    });
+                }
+                Yield::new(3, ready(())).await;
+                assert_eq!(sum.load(Ordering::SeqCst), 66);
+            }
+        }));
+    }
+
+    #[test]
+    fn cancel_scope() {
+        let data = vec![5];
+        let polls = AtomicUsize::new(0);
+        let cancelled = AtomicUsize::new(0);
+
+        let mut fut = scope(|s| {
+            let (data, polls, cancelled) = (&data, &polls, &cancelled);
+            crate::completion_async_move! {
+                s.spawn(Child { data, index: 0, polls, cancelled, cancelling: false });
+                Yield::new(5, ready(())).await;
+            }
+        });
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            let mut fut = Pin::new_unchecked(&mut fut);
+            assert!(fut.as_mut().poll(cx).is_pending());
+            assert_eq!(polls.load(Ordering::SeqCst), 1);
+            assert!(fut.as_mut().poll_cancel(cx).is_pending());
+            assert!(fut.as_mut().poll_cancel(cx).is_ready());
+        }
+        assert_eq!(cancelled.load(Ordering::SeqCst), 5);
+    }
+}
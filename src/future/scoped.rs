@@ -0,0 +1,112 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+use super::block_on::cancel_blocking;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::scoped`](super::CompletionFutureExt::scoped).
+    ///
+    /// If this is dropped after being polled but before it has completed or been cancelled, it
+    /// blocks the current thread in its destructor until the inner future has been cancelled.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Scoped<F: CompletionFuture> {
+        #[pin]
+        inner: F,
+        // Whether the inner future has been polled and hasn't yet completed or been cancelled.
+        running: bool,
+    }
+
+    impl<F: CompletionFuture> PinnedDrop for Scoped<F> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            if *this.running {
+                unsafe { cancel_blocking(this.inner) };
+            }
+        }
+    }
+}
+
+impl<F: CompletionFuture> Scoped<F> {
+    pub(super) fn new(inner: F) -> Self {
+        Self {
+            inner,
+            running: false,
+        }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for Scoped<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        *this.running = true;
+        let poll = this.inner.poll(cx);
+        if poll.is_ready() {
+            *this.running = false;
+        }
+        poll
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if !*this.running {
+            return Poll::Ready(());
+        }
+        let poll = this.inner.poll_cancel(cx);
+        if poll.is_ready() {
+            *this.running = false;
+        }
+        poll
+    }
+}
+
+impl<F: CompletionFuture + 'static> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: The destructor cancels the inner future if it is dropped early, and since it is
+        // `'static` leaking it instead is sound.
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future::{ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    fn drop_cancels() {
+        let (fut, counts) = Yield::new(3, ready(())).count_polls();
+        let mut fut = Box::pin(fut.scoped());
+
+        let cx = &mut crate::noop_cx();
+        assert!(Future::poll(fut.as_mut(), cx).is_pending());
+        assert_eq!(counts.cancels(), 0);
+
+        drop(fut);
+        assert_ne!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn unpolled_not_cancelled() {
+        let (fut, counts) = Yield::once(ready(())).count_polls();
+        drop(fut.scoped());
+        assert_eq!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn completed_not_cancelled() {
+        let (fut, counts) = ready(5).count_polls();
+        assert_eq!(futures_lite::future::block_on(fut.scoped()), 5);
+        assert_eq!(counts.cancels(), 0);
+    }
+}
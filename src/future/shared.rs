@@ -0,0 +1,524 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::{Arc, Weak};
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, MutexGuard};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+
+/// Future for [`CompletionFutureExt::shared`](super::CompletionFutureExt::shared).
+///
+/// Each clone of this type is a strong handle to the inner future, which is driven by whichever
+/// handles are polling it. Cancelling a handle detaches it from the future if another handle is
+/// still polling it. Otherwise, if other strong handles exist, the cancelled handle runs the
+/// future to completion so they can still receive its output; if no other strong handles exist,
+/// the future is cancelled.
+///
+/// # Panics
+///
+/// If the inner future panics, the handle that was driving it at the time continues unwinding
+/// with the original panic. Every other handle that polls the future afterwards panics with a
+/// [`SharedPanicked`] payload, which carries the original panic message if it had one.
+#[must_use = "futures do nothing unless you use them"]
+pub struct Shared<F: CompletionFuture> {
+    inner: Arc<Inner<F>>,
+    id: usize,
+    state: HandleState,
+}
+
+/// The panic payload of a [`Shared`] handle that was polled after the inner future panicked.
+///
+/// This can be obtained by catching the panic and downcasting its payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedPanicked {
+    message: Option<String>,
+}
+
+impl SharedPanicked {
+    fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = if let Some(&message) = payload.downcast_ref::<&str>() {
+            Some(message.into())
+        } else {
+            payload.downcast_ref::<String>().cloned()
+        };
+        Self { message }
+    }
+
+    /// Get the message of the original panic, if it was a string.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl Display for SharedPanicked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("shared future panicked")?;
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SharedPanicked {}
+
+/// A weak handle to a [`Shared`] future, created by [`Shared::downgrade`].
+///
+/// This does not keep the future alive: once all the strong handles have been cancelled or
+/// dropped, the future is cancelled and [`upgrade`](Self::upgrade) returns [`None`].
+pub struct WeakShared<F: CompletionFuture> {
+    inner: Weak<Inner<F>>,
+}
+
+/// State shared between all the handles.
+struct Inner<F: CompletionFuture> {
+    state: Mutex<State<F>>,
+    /// The wakers of all the handles waiting on the future.
+    wakers: Arc<Wakers>,
+    /// The waker passed to the inner future, which wakes every handle.
+    waker: Waker,
+}
+
+struct State<F: CompletionFuture> {
+    fut: FutState<F>,
+    /// Whether the inner future has been polled, and so must now be run to completion.
+    started: bool,
+    /// Whether the inner future is being cancelled.
+    cancelling: bool,
+    /// The number of handles that are in the [`HandleState::Polled`] state.
+    polling: usize,
+    next_id: usize,
+}
+
+type Panic = Box<dyn Any + Send>;
+
+enum FutState<F: CompletionFuture> {
+    /// The future is running. It is pinned as it will not be moved until it is dropped.
+    Running(F),
+    /// The future has completed, and each handle receives a clone of its output.
+    Done(F::Output),
+    /// The future has been cancelled.
+    Cancelled,
+    /// The future panicked.
+    Panicked(SharedPanicked),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandleState {
+    /// The handle has not been polled yet, so it is allowed to be dropped at any time.
+    Unpolled,
+    /// The handle has been polled, so it will be driven until it completes or is cancelled.
+    Polled,
+    /// The handle has completed or been cancelled.
+    Finished,
+}
+
+#[derive(Debug)]
+struct Wakers(Mutex<Vec<(usize, Waker)>>);
+
+impl Wakers {
+    fn register(&self, id: usize, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        if let Some((_, old)) = wakers.iter_mut().find(|(i, _)| *i == id) {
+            if !old.will_wake(waker) {
+                old.clone_from(waker);
+            }
+        } else {
+            wakers.push((id, waker.clone()));
+        }
+    }
+    fn remove(&self, id: usize) {
+        self.0.lock().unwrap().retain(|(i, _)| *i != id);
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = core::mem::take(&mut *self.0.lock().unwrap());
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl<F: CompletionFuture> Shared<F> {
+    pub(super) fn new(fut: F) -> Self {
+        let wakers = Arc::new(Wakers(Mutex::new(Vec::new())));
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    fut: FutState::Running(fut),
+                    started: false,
+                    cancelling: false,
+                    polling: 0,
+                    next_id: 1,
+                }),
+                waker: Waker::from(Arc::clone(&wakers)),
+                wakers,
+            }),
+            id: 0,
+            state: HandleState::Unpolled,
+        }
+    }
+
+    /// Create a weak handle to this future.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakShared<F> {
+        WeakShared {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<F>> {
+        self.inner.state.lock().unwrap()
+    }
+
+    /// Create a new handle from the shared state.
+    fn from_inner(inner: Arc<Inner<F>>, state: &mut State<F>) -> Self {
+        let id = state.next_id;
+        state.next_id += 1;
+        Self {
+            inner,
+            id,
+            state: HandleState::Unpolled,
+        }
+    }
+
+    /// Poll the running inner future, storing its output once it completes.
+    ///
+    /// If the future panics, the panic is recorded and its payload returned.
+    unsafe fn poll_fut(inner: &Inner<F>, state: &mut State<F>) -> Poll<Result<(), Panic>> {
+        state.started = true;
+        let fut = match &mut state.fut {
+            FutState::Running(fut) => Pin::new_unchecked(fut),
+            _ => unreachable!(),
+        };
+        let cx = &mut Context::from_waker(&inner.waker);
+        match panic::catch_unwind(AssertUnwindSafe(|| fut.poll(cx))) {
+            Ok(Poll::Ready(output)) => state.fut = FutState::Done(output),
+            Ok(Poll::Pending) => return Poll::Pending,
+            Err(payload) => {
+                Self::poison(inner, state, &*payload);
+                return Poll::Ready(Err(payload));
+            }
+        }
+        inner.waker.wake_by_ref();
+        Poll::Ready(Ok(()))
+    }
+
+    /// Cancel the running inner future.
+    ///
+    /// If the future panics, the panic is recorded and its payload returned.
+    unsafe fn cancel_fut(inner: &Inner<F>, state: &mut State<F>) -> Poll<Result<(), Panic>> {
+        state.cancelling = true;
+        if state.started {
+            let fut = match &mut state.fut {
+                FutState::Running(fut) => Pin::new_unchecked(fut),
+                _ => unreachable!(),
+            };
+            let cx = &mut Context::from_waker(&inner.waker);
+            match panic::catch_unwind(AssertUnwindSafe(|| fut.poll_cancel(cx))) {
+                Ok(Poll::Ready(())) => {}
+                Ok(Poll::Pending) => return Poll::Pending,
+                Err(payload) => {
+                    Self::poison(inner, state, &*payload);
+                    return Poll::Ready(Err(payload));
+                }
+            }
+        }
+        state.fut = FutState::Cancelled;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Record that the inner future panicked, and wake the other handles so they observe it.
+    fn poison(inner: &Inner<F>, state: &mut State<F>, payload: &(dyn Any + Send)) {
+        state.fut = FutState::Panicked(SharedPanicked::new(payload));
+        inner.waker.wake_by_ref();
+    }
+
+    /// Finish this handle and continue unwinding a panic, releasing the lock first so that it
+    /// isn't poisoned.
+    fn resume(&mut self, mut state: MutexGuard<'_, State<F>>, payload: Panic) -> ! {
+        self.finish(&mut state);
+        drop(state);
+        panic::resume_unwind(payload)
+    }
+
+    /// Mark this handle as no longer waiting on the future.
+    fn finish(&mut self, state: &mut State<F>) {
+        if self.state == HandleState::Polled {
+            state.polling -= 1;
+        }
+        self.state = HandleState::Finished;
+        self.inner.wakers.remove(self.id);
+    }
+}
+
+impl<F: CompletionFuture> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        let mut state = self.lock();
+        Self::from_inner(Arc::clone(&self.inner), &mut state)
+    }
+}
+
+impl<F: CompletionFuture> Drop for Shared<F> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.inner.state.lock() {
+            if self.state != HandleState::Finished {
+                if self.state == HandleState::Polled {
+                    state.polling -= 1;
+                }
+                // Other handles may be waiting on us to stop wanting the output.
+                drop(state);
+                self.inner.wakers.remove(self.id);
+                self.inner.waker.wake_by_ref();
+            }
+        }
+    }
+}
+
+impl<F> CompletionFuture for Shared<F>
+where
+    F: CompletionFuture,
+    F::Output: Clone,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.inner.wakers.register(this.id, cx.waker());
+        let inner = Arc::clone(&this.inner);
+        let mut state = inner.state.lock().unwrap();
+
+        if this.state == HandleState::Unpolled {
+            this.state = HandleState::Polled;
+            state.polling += 1;
+        }
+
+        match &state.fut {
+            FutState::Running(_) if !state.cancelling => {
+                if let Err(payload) = ready!(Self::poll_fut(&inner, &mut state)) {
+                    this.resume(state, payload);
+                }
+            }
+            FutState::Done(_) => {}
+            FutState::Panicked(panicked) => {
+                let panicked = panicked.clone();
+                this.finish(&mut state);
+                drop(state);
+                panic::panic_any(panicked);
+            }
+            FutState::Running(_) | FutState::Cancelled => {
+                this.finish(&mut state);
+                drop(state);
+                panic!("polled `Shared` after the future was cancelled");
+            }
+        }
+        this.finish(&mut state);
+
+        match &state.fut {
+            FutState::Done(output) => Poll::Ready(output.clone()),
+            _ => unreachable!(),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        this.inner.wakers.register(this.id, cx.waker());
+        let inner = Arc::clone(&this.inner);
+        let mut state = inner.state.lock().unwrap();
+
+        if this.state == HandleState::Polled {
+            this.state = HandleState::Unpolled;
+            state.polling -= 1;
+        }
+
+        if let FutState::Running(_) = state.fut {
+            // The other strong handles, ignoring the one we just cloned.
+            let others = Arc::strong_count(&inner) - 2;
+
+            let res = if state.cancelling || others == 0 {
+                // Nobody else wants the output, so we cancel the future.
+                ready!(Self::cancel_fut(&inner, &mut state))
+            } else if state.started && state.polling == 0 {
+                // Other handles want the output but aren't obliged to drive the future, so we
+                // must run it to completion ourselves.
+                ready!(Self::poll_fut(&inner, &mut state))
+            } else {
+                Ok(())
+            };
+            if let Err(payload) = res {
+                this.resume(state, payload);
+            }
+            // Otherwise, the future is either not started or is being driven by another handle.
+        }
+
+        this.finish(&mut state);
+        Poll::Ready(())
+    }
+}
+
+impl<F> Future for Shared<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    <F as CompletionFuture>::Output: Clone,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+impl<F: CompletionFuture> Debug for Shared<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: CompletionFuture> WeakShared<F> {
+    /// Attempt to create a strong handle to the future.
+    ///
+    /// Returns [`None`] if all the strong handles are gone, the future has been cancelled or it
+    /// panicked.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Shared<F>> {
+        let inner = self.inner.upgrade()?;
+        let mut state = inner.state.lock().ok()?;
+        if state.cancelling || matches!(state.fut, FutState::Cancelled) {
+            return None;
+        }
+        let handle = Shared::from_inner(Arc::clone(&inner), &mut state);
+        drop(state);
+        Some(handle)
+    }
+}
+
+impl<F: CompletionFuture> Clone for WeakShared<F> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Weak::clone(&self.inner),
+        }
+    }
+}
+
+impl<F: CompletionFuture> Debug for WeakShared<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad("WeakShared")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future::{self, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    fn poll<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<F::Output> {
+        unsafe { Pin::new(fut).poll(&mut crate::noop_cx()) }
+    }
+    fn poll_cancel<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<()> {
+        unsafe { Pin::new(fut).poll_cancel(&mut crate::noop_cx()) }
+    }
+
+    #[test]
+    fn clones_share_output() {
+        let mut a = Shared::new(Yield::once(future::ready(5)).check().max_polls(2));
+        let mut b = a.clone();
+        assert_eq!(poll(&mut b), Poll::Pending);
+        assert_eq!(poll(&mut a), Poll::Ready(5));
+        assert_eq!(poll(&mut b), Poll::Ready(5));
+        assert_eq!(poll(&mut a.clone()), Poll::Ready(5));
+    }
+
+    #[test]
+    fn upgrade_after_completion() {
+        let mut a = Shared::new(future::ready(5).check());
+        let weak = a.downgrade();
+        assert_eq!(poll(&mut a), Poll::Ready(5));
+        assert_eq!(poll(&mut weak.upgrade().unwrap()), Poll::Ready(5));
+    }
+
+    #[test]
+    fn cancel_detaches() {
+        let mut a = Shared::new(Yield::new(2, future::ready(5)).check().max_cancels(0));
+        let mut b = a.clone();
+        assert_eq!(poll(&mut a), Poll::Pending);
+        assert_eq!(poll(&mut b), Poll::Pending);
+        assert_eq!(poll_cancel(&mut a), Poll::Ready(()));
+        assert_eq!(poll(&mut b), Poll::Ready(5));
+    }
+
+    #[test]
+    fn cancelled_when_strong_handles_gone() {
+        let (fut, counts) = Yield::new(3, future::pending::<()>()).count_polls();
+        let mut a = Shared::new(fut);
+        let b = a.clone();
+        let weak = a.downgrade();
+
+        assert_eq!(poll(&mut a), Poll::Pending);
+
+        // `b` might still want the output, so `a` keeps driving the future.
+        assert_eq!(poll_cancel(&mut a), Poll::Pending);
+        assert_eq!(counts.cancels(), 0);
+
+        drop(b);
+        while poll_cancel(&mut a).is_pending() {}
+        assert!(counts.cancels() > 0);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn poisoned_by_panic() {
+        struct Panicking;
+        impl CompletionFuture for Panicking {
+            type Output = ();
+            unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                panic!("oh no");
+            }
+            unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                Poll::Ready(())
+            }
+        }
+
+        let mut a = Shared::new(Yield::once(Panicking));
+        let mut b = a.clone();
+        let c = a.clone();
+        assert_eq!(poll(&mut b), Poll::Pending);
+
+        // The handle that drives the future gets the original panic.
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| poll(&mut a))).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oh no"));
+
+        // The others get a `SharedPanicked`.
+        for handle in &mut [b, c] {
+            let payload = panic::catch_unwind(AssertUnwindSafe(|| poll(handle))).unwrap_err();
+            let panicked = payload.downcast_ref::<SharedPanicked>().unwrap();
+            assert_eq!(panicked.message(), Some("oh no"));
+            assert_eq!(panicked.to_string(), "shared future panicked: oh no");
+        }
+    }
+
+    #[test]
+    fn unpolled_dropped() {
+        let a = Shared::new(future::pending::<()>().check().max_polls(0));
+        let weak = a.downgrade();
+        drop(a);
+        assert!(weak.upgrade().is_none());
+    }
+}
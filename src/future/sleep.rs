@@ -0,0 +1,241 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use atomic_waker::AtomicWaker;
+use completion_core::CompletionFuture;
+
+/// Wait until a duration has elapsed.
+///
+/// Timers are driven by a single background thread which is started the first time a [`Sleep`]
+/// is polled. Cancelling or dropping the returned future removes its entry from the timer
+/// immediately.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// let start = Instant::now();
+/// future::sleep(Duration::from_millis(10)).await;
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn sleep(duration: Duration) -> Sleep {
+    sleep_until(saturating_add(Instant::now(), duration))
+}
+
+/// Add a duration to an instant. If that overflows, this returns an instant so far in the future
+/// that it will never be reached instead.
+pub(crate) fn saturating_add(instant: Instant, duration: Duration) -> Instant {
+    instant.checked_add(duration).unwrap_or_else(|| {
+        // About 30 years.
+        const FAR_FUTURE: Duration = Duration::from_secs(1_000_000_000);
+        cmp::max(instant, Instant::now() + FAR_FUTURE)
+    })
+}
+
+/// Wait until a deadline has been reached.
+pub(crate) fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep {
+        deadline,
+        entry: None,
+    }
+}
+
+/// Future for [`sleep`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct Sleep {
+    deadline: Instant,
+    entry: Option<(EntryKey, Arc<Entry>)>,
+}
+
+impl Sleep {
+    /// Get the instant at which this future will complete.
+    #[must_use]
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Remove this future's entry from the timer, if it has one.
+    fn deregister(&mut self) {
+        if let Some((key, _)) = self.entry.take() {
+            timer().entries.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+impl CompletionFuture for Sleep {
+    type Output = ();
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Future::poll(self, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        self.get_mut().deregister();
+        Poll::Ready(())
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if Instant::now() >= this.deadline {
+            this.deregister();
+            return Poll::Ready(());
+        }
+
+        let deadline = this.deadline;
+        let (_, entry) = this.entry.get_or_insert_with(|| timer().register(deadline));
+        entry.waker.register(cx.waker());
+        if entry.fired.load(Ordering::SeqCst) {
+            this.entry = None;
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.deregister();
+    }
+}
+
+/// The key of an entry in the timer, ordered by deadline. The second element makes keys unique.
+type EntryKey = (Instant, u64);
+
+#[derive(Debug)]
+struct Entry {
+    fired: AtomicBool,
+    waker: AtomicWaker,
+}
+
+/// The global timer, holding all the registered entries.
+struct Timer {
+    entries: Mutex<BTreeMap<EntryKey, Arc<Entry>>>,
+    next_id: AtomicU64,
+    /// Notified when an entry is added that may be earlier than the current earliest one.
+    changed: Condvar,
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+
+    TIMER.get_or_init(|| {
+        thread::Builder::new()
+            .name("completion-timer".to_owned())
+            .spawn(|| timer().run())
+            .expect("failed to spawn timer thread");
+
+        Timer {
+            entries: Mutex::new(BTreeMap::new()),
+            next_id: AtomicU64::new(0),
+            changed: Condvar::new(),
+        }
+    })
+}
+
+impl Timer {
+    fn register(&self, deadline: Instant) -> (EntryKey, Arc<Entry>) {
+        let key = (deadline, self.next_id.fetch_add(1, Ordering::Relaxed));
+        let entry = Arc::new(Entry {
+            fired: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+        });
+        self.entries.lock().unwrap().insert(key, Arc::clone(&entry));
+        self.changed.notify_one();
+        (key, entry)
+    }
+
+    /// Run the timer thread.
+    fn run(&self) -> ! {
+        let mut entries = self.entries.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            while let Some((&key, _)) = entries.iter().next() {
+                if key.0 > now {
+                    break;
+                }
+                let entry = entries.remove(&key).unwrap();
+                entry.fired.store(true, Ordering::SeqCst);
+                entry.waker.wake();
+            }
+
+            entries = match entries.keys().next() {
+                Some(&(deadline, _)) => {
+                    self.changed
+                        .wait_timeout(entries, deadline - now)
+                        .unwrap()
+                        .0
+                }
+                None => self.changed.wait(entries).unwrap(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future::block_on;
+    use crate::CompletionFutureExt;
+
+    #[test]
+    fn resolves_after_duration() {
+        let start = Instant::now();
+        block_on(sleep(Duration::from_millis(50)));
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn zero_duration() {
+        assert_eq!(
+            block_on(sleep(Duration::from_secs(0)).now_or_never()),
+            Some(())
+        );
+    }
+
+    #[test]
+    fn huge_duration() {
+        let mut fut = sleep(Duration::MAX);
+        assert!(fut.deadline() > Instant::now() + Duration::from_secs(100_000_000));
+
+        let mut cx = crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), &mut cx) }.is_pending());
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(&mut cx) }.is_ready());
+    }
+
+    #[test]
+    fn cancel_is_instant() {
+        let mut fut = sleep(Duration::from_secs(30));
+        let mut cx = crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), &mut cx) }.is_pending());
+        let key = fut.entry.as_ref().unwrap().0;
+        assert!(timer().entries.lock().unwrap().contains_key(&key));
+
+        let start = Instant::now();
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(&mut cx) }.is_ready());
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(!timer().entries.lock().unwrap().contains_key(&key));
+    }
+}
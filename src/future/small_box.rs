@@ -0,0 +1,211 @@
+use alloc::boxed::Box;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::ptr;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// The maximum size of a future that can be stored inline in a [`SmallBoxCompletionFuture`].
+pub const SMALL_BOX_SIZE: usize = 64;
+
+/// The inline storage of a small box.
+#[repr(C, align(16))]
+struct Storage(MaybeUninit<[u8; SMALL_BOX_SIZE]>);
+
+/// Future for [`CompletionFutureExt::small_boxed`](super::CompletionFutureExt::small_boxed).
+///
+/// This is a type-erased completion future that stores the future inline if it is at most
+/// [`SMALL_BOX_SIZE`] bytes large and has an alignment of at most 16, and in a heap allocation
+/// otherwise.
+///
+/// Since the future may be stored inline, this type does not implement [`Unpin`], and must be
+/// pinned before it is polled.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[must_use = "futures do nothing unless you use them"]
+pub struct SmallBoxCompletionFuture<'a, T> {
+    storage: Storage,
+    inline: bool,
+    poll: unsafe fn(*mut (), &mut Context<'_>) -> Poll<T>,
+    poll_cancel: unsafe fn(*mut (), &mut Context<'_>) -> Poll<()>,
+    drop: unsafe fn(*mut ()),
+    _lifetime: PhantomData<&'a ()>,
+    _pinned: PhantomPinned,
+}
+
+impl<'a, T> SmallBoxCompletionFuture<'a, T> {
+    pub(super) fn new<F>(fut: F) -> Self
+    where
+        F: CompletionFuture<Output = T> + Send + 'a,
+    {
+        if fits::<F>() {
+            Self::new_inline(fut, true)
+        } else {
+            Self::new_inline(Box::pin(fut), false)
+        }
+    }
+
+    fn new_inline<F>(fut: F, inline: bool) -> Self
+    where
+        F: CompletionFuture<Output = T> + Send + 'a,
+    {
+        assert!(fits::<F>());
+
+        let mut storage = Storage(MaybeUninit::uninit());
+        unsafe { storage.0.as_mut_ptr().cast::<F>().write(fut) };
+
+        Self {
+            storage,
+            inline,
+            poll: poll::<F>,
+            poll_cancel: poll_cancel::<F>,
+            drop: drop::<F>,
+            _lifetime: PhantomData,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Whether the future is stored inline, rather than in a heap allocation.
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        self.inline
+    }
+
+    fn ptr(self: Pin<&mut Self>) -> *mut () {
+        unsafe { self.get_unchecked_mut() }
+            .storage
+            .0
+            .as_mut_ptr()
+            .cast()
+    }
+}
+
+/// Whether a future of type `F` can be stored inline.
+fn fits<F>() -> bool {
+    size_of::<F>() <= SMALL_BOX_SIZE && align_of::<F>() <= align_of::<Storage>()
+}
+
+unsafe fn poll<F: CompletionFuture>(ptr: *mut (), cx: &mut Context<'_>) -> Poll<F::Output> {
+    Pin::new_unchecked(&mut *ptr.cast::<F>()).poll(cx)
+}
+
+unsafe fn poll_cancel<F: CompletionFuture>(ptr: *mut (), cx: &mut Context<'_>) -> Poll<()> {
+    Pin::new_unchecked(&mut *ptr.cast::<F>()).poll_cancel(cx)
+}
+
+unsafe fn drop<F>(ptr: *mut ()) {
+    ptr::drop_in_place(ptr.cast::<F>());
+}
+
+// SAFETY: The small box can only be constructed from `Send` futures, and it doesn't allow any
+// access to the future through a shared reference.
+unsafe impl<T> Send for SmallBoxCompletionFuture<'_, T> {}
+unsafe impl<T> Sync for SmallBoxCompletionFuture<'_, T> {}
+
+impl<T> CompletionFuture for SmallBoxCompletionFuture<'_, T> {
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let poll = self.poll;
+        poll(self.ptr(), cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let poll_cancel = self.poll_cancel;
+        poll_cancel(self.ptr(), cx)
+    }
+}
+
+impl<T> Drop for SmallBoxCompletionFuture<'_, T> {
+    fn drop(&mut self) {
+        // The future is dropped in place, so this doesn't move it even if it is pinned.
+        unsafe { (self.drop)(self.storage.0.as_mut_ptr().cast()) };
+    }
+}
+
+impl<T> Debug for SmallBoxCompletionFuture<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmallBoxCompletionFuture")
+            .field("inline", &self.inline)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    /// A future that counts how many times it has been dropped.
+    struct CountDrops<F> {
+        fut: F,
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl<F: CompletionFuture + Unpin> CompletionFuture for CountDrops<F> {
+        type Output = F::Output;
+
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.get_mut().fut).poll(cx)
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            Pin::new(&mut self.get_mut().fut).poll_cancel(cx)
+        }
+    }
+
+    impl<F> Drop for CountDrops<F> {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn inline() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let fut = CountDrops {
+            fut: Yield::new(3, ready(5)),
+            drops: Arc::clone(&drops),
+        }
+        .small_boxed();
+        assert!(fut.is_inline());
+        assert_eq!(block_on(fut.check()), 5);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn spilled() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let fut = CountDrops {
+            fut: Yield::new(3, ready([7_u8; SMALL_BOX_SIZE])),
+            drops: Arc::clone(&drops),
+        }
+        .small_boxed();
+        assert!(!fut.is_inline());
+        assert_eq!(block_on(fut.check()), [7; SMALL_BOX_SIZE]);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    fn check_cancel<T: Send>(output: T, inline: bool) {
+        let (fut, counts) = Yield::new(5, ready(output)).count_polls();
+        let mut fut = fut.small_boxed();
+        assert_eq!(fut.is_inline(), inline);
+
+        let cx = &mut crate::noop_cx();
+        let mut fut = unsafe { Pin::new_unchecked(&mut fut) };
+        assert!(unsafe { fut.as_mut().poll(cx) }.is_pending());
+        while unsafe { fut.as_mut().poll_cancel(cx) }.is_pending() {}
+        assert_ne!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn cancel() {
+        check_cancel((), true);
+        check_cancel([0_u8; 128], false);
+    }
+}
@@ -0,0 +1,261 @@
+use std::boxed::Box;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+use atomic_waker::AtomicWaker;
+use completion_core::CompletionFuture;
+
+/// Run a blocking closure on a shared thread pool, resolving with its result.
+///
+/// The closure starts running as soon as this function is called, on one of a pool of background
+/// threads. The pool starts new threads on demand, up to a limit of 64, after which closures queue
+/// up waiting for a free thread; threads that have been idle for ten seconds exit.
+///
+/// A blocking closure can't be interrupted, so cancelling or dropping the returned future detaches
+/// it instead: the cancellation finishes immediately, but the closure still runs to completion on
+/// the pool and its result is discarded. If the closure panics, the panic is resumed when the
+/// future is polled.
+///
+/// # Panics
+///
+/// Panics if the pool has no threads and a new one can't be spawned.
+///
+/// # Examples
+///
+/// ```
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// let sum = future::spawn_blocking(|| (1..=10).sum::<u32>()).await;
+/// assert_eq!(sum, 55);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn spawn_blocking<F, T>(f: F) -> SpawnBlocking<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let shared = Arc::new(Shared {
+        result: Mutex::new(None),
+        waker: AtomicWaker::new(),
+    });
+
+    let job_shared = Arc::clone(&shared);
+    pool().spawn(Box::new(move || {
+        let result = catch_unwind(AssertUnwindSafe(f));
+        *job_shared.result.lock().unwrap() = Some(result);
+        job_shared.waker.wake();
+    }));
+
+    SpawnBlocking {
+        shared: Some(shared),
+    }
+}
+
+/// Future for [`spawn_blocking`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct SpawnBlocking<T> {
+    /// The state shared with the job, or `None` once the result has been taken.
+    shared: Option<Arc<Shared<T>>>,
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    result: Mutex<Option<thread::Result<T>>>,
+    waker: AtomicWaker,
+}
+
+impl<T> CompletionFuture for SpawnBlocking<T> {
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Future::poll(self, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        // Detach the job; it keeps its own handle to the shared state.
+        self.get_mut().shared = None;
+        Poll::Ready(())
+    }
+}
+
+impl<T> Future for SpawnBlocking<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let shared = this
+            .shared
+            .as_ref()
+            .expect("`SpawnBlocking` polled after completion");
+
+        shared.waker.register(cx.waker());
+        let result = shared.result.lock().unwrap().take();
+        match result {
+            Some(result) => {
+                this.shared = None;
+                Poll::Ready(result.unwrap_or_else(|payload| resume_unwind(payload)))
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Unpin for SpawnBlocking<T> {}
+
+/// The maximum number of threads in the pool.
+const MAX_THREADS: usize = 64;
+
+/// How long a thread waits for new work before exiting.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The global blocking thread pool.
+struct Pool {
+    state: Mutex<PoolState>,
+    /// Notified when a job is queued.
+    work_available: Condvar,
+}
+
+struct PoolState {
+    queue: VecDeque<Job>,
+    threads: usize,
+    idle: usize,
+}
+
+fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+
+    POOL.get_or_init(|| Pool {
+        state: Mutex::new(PoolState {
+            queue: VecDeque::new(),
+            threads: 0,
+            idle: 0,
+        }),
+        work_available: Condvar::new(),
+    })
+}
+
+impl Pool {
+    fn spawn(&'static self, job: Job) {
+        let mut state = self.state.lock().unwrap();
+        state.queue.push_back(job);
+
+        if state.idle > 0 {
+            self.work_available.notify_one();
+        }
+        // Idle threads that have been notified but haven't woken up yet are still counted, so only
+        // start a thread if there are more queued jobs than threads to take them.
+        if state.queue.len() > state.idle && state.threads < MAX_THREADS {
+            state.threads += 1;
+            let res = thread::Builder::new()
+                .name("completion-blocking".to_owned())
+                .spawn(move || self.run());
+            if let Err(e) = res {
+                state.threads -= 1;
+                let threads = state.threads;
+                // Don't poison the lock by panicking while holding it.
+                drop(state);
+                // Jobs can still run on the existing threads.
+                assert_ne!(threads, 0, "failed to spawn blocking thread: {e}");
+            }
+        }
+    }
+
+    /// Run a pool thread.
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (new_state, timeout) = self.work_available.wait_timeout(state, KEEP_ALIVE).unwrap();
+            state = new_state;
+            state.idle -= 1;
+
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    use crate::future::block_on;
+
+    #[test]
+    fn returns_result() {
+        assert_eq!(block_on(spawn_blocking(|| 5)), 5);
+
+        let outputs: Vec<_> = (0..8)
+            .map(|i| spawn_blocking(move || i * 2))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(block_on)
+            .collect();
+        assert_eq!(outputs, [0, 2, 4, 6, 8, 10, 12, 14]);
+    }
+
+    #[test]
+    fn cancel_detaches() {
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (done_tx, done_rx) = mpsc::channel();
+        let mut fut = spawn_blocking(move || {
+            release_rx.recv().unwrap();
+            done_tx.send(()).unwrap();
+        });
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+
+        let start = Instant::now();
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(cx) }.is_ready());
+        assert!(start.elapsed() < Duration::from_secs(1));
+        drop(fut);
+
+        // The closure still runs to completion.
+        release_tx.send(()).unwrap();
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    #[test]
+    fn concurrent_jobs_dont_wait_for_each_other() {
+        // Make sure at least one thread is idle.
+        block_on(spawn_blocking(|| ()));
+
+        // Each job only finishes once the next one has started, so they must run at the same time.
+        let (tx, rx) = mpsc::channel::<()>();
+        let first = spawn_blocking(move || rx.recv_timeout(Duration::from_secs(5)).is_ok());
+        let second = spawn_blocking(move || tx.send(()).unwrap());
+        block_on(second);
+        assert!(block_on(first));
+    }
+
+    #[test]
+    fn panic_resumed() {
+        let fut = spawn_blocking(|| panic!("oh no"));
+        let payload = catch_unwind(AssertUnwindSafe(|| block_on(fut))).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oh no"));
+    }
+}
@@ -0,0 +1,77 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+/// A completion future that can be dropped at any time without being cancelled.
+///
+/// Types implementing this trait can be converted into regular [`Future`]s with
+/// [`CompletionFutureExt::into_std_future`](super::CompletionFutureExt::into_std_future).
+///
+/// This is implemented for all completion futures that also implement [`Future`], as the contract
+/// of [`CompletionFuture`] requires those to be cancellable by dropping them.
+///
+/// # Safety
+///
+/// Dropping the future at any point, including after it has been polled but before it has
+/// completed, must be sound and must not leak any resources or leave them in an invalid state.
+pub unsafe trait PollCancelFreeOnDrop: CompletionFuture {}
+
+unsafe impl<F: CompletionFuture + Future + ?Sized> PollCancelFreeOnDrop for F {}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::into_std_future`](super::CompletionFutureExt::into_std_future).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct StdFuture<F> {
+        #[pin]
+        fut: F,
+    }
+}
+
+impl<F> StdFuture<F> {
+    pub(super) fn new(fut: F) -> Self {
+        Self { fut }
+    }
+
+    /// Take the inner future.
+    pub fn into_inner(self) -> F {
+        self.fut
+    }
+}
+
+impl<F: PollCancelFreeOnDrop> Future for StdFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `F` is fine with being dropped at any time, so we don't need to make sure it
+        // completes.
+        unsafe { self.project().fut.poll(cx) }
+    }
+}
+impl<F: PollCancelFreeOnDrop> CompletionFuture for StdFuture<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().fut.poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::future::{ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    #[test]
+    fn boxed_std_in_join() {
+        let a = Yield::new(3, ready(1)).boxed_std();
+        let b = ready("b").boxed_std();
+        let output = futures_lite::future::block_on(futures_lite::future::zip(a, b));
+        assert_eq!(output, (1, "b"));
+    }
+}
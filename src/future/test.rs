@@ -0,0 +1,172 @@
+//! Utilities for testing completion futures.
+//!
+//! # Examples
+//!
+//! Check that the losing future of a [`race`](super::race) is cancelled:
+//!
+//! ```
+//! use completion::future::{self, test::AssertCancelled};
+//! use completion::completion_async;
+//! use futures_lite::future::yield_now;
+//!
+//! # future::block_on(completion_async! {
+//! let winner = completion_async! {
+//!     yield_now().await;
+//!     5
+//! };
+//! let mut loser = AssertCancelled::new(future::pending::<i32>());
+//! assert_eq!(future::race((winner, &mut loser)).await, 5);
+//! assert!(loser.was_cancelled());
+//! # });
+//! ```
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::thread;
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A future that panics if it is dropped while its inner future is in the middle of running.
+    ///
+    /// A future is considered to be in the middle of running once it has returned
+    /// [`Poll::Pending`] from [`poll`](CompletionFuture::poll), until it either completes or
+    /// [`poll_cancel`](CompletionFuture::poll_cancel) returns [`Poll::Ready`]. Dropping it at
+    /// that point is a violation of the completion future contract that this wrapper catches.
+    ///
+    /// This deliberately doesn't implement [`Future`](core::future::Future), since that would
+    /// make dropping it at any time allowed.
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct AssertCancelled<F> {
+        #[pin]
+        fut: F,
+        guard: Guard,
+    }
+}
+
+impl<F> AssertCancelled<F> {
+    /// Wrap a future.
+    pub fn new(fut: F) -> Self {
+        Self {
+            fut,
+            guard: Guard::new("future"),
+        }
+    }
+
+    /// Whether the inner future has been cancelled.
+    #[must_use]
+    pub fn was_cancelled(&self) -> bool {
+        self.guard.was_cancelled()
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for AssertCancelled<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let poll = this.fut.poll(cx);
+        this.guard.polled(poll.is_pending());
+        poll
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let poll = this.fut.poll_cancel(cx);
+        if poll.is_ready() {
+            this.guard.cancel();
+        }
+        poll
+    }
+}
+
+/// Tracks whether a future or stream is running, and panics if it is dropped while it is.
+///
+/// This is shared with [`stream::test`](crate::stream::test).
+#[derive(Debug)]
+pub(crate) struct Guard {
+    running: bool,
+    cancelled: bool,
+    // What is being guarded, for the panic message.
+    kind: &'static str,
+}
+
+impl Guard {
+    pub(crate) fn new(kind: &'static str) -> Self {
+        Self {
+            running: false,
+            cancelled: false,
+            kind,
+        }
+    }
+
+    /// Record the result of a poll.
+    pub(crate) fn polled(&mut self, pending: bool) {
+        self.running = pending;
+    }
+
+    /// Record that cancellation has finished.
+    pub(crate) fn cancel(&mut self) {
+        self.running = false;
+        self.cancelled = true;
+    }
+
+    pub(crate) fn was_cancelled(&self) -> bool {
+        self.cancelled
+    }
+}
+
+/// A clone starts out fresh, since the clone of a running future hasn't been polled itself.
+impl Clone for Guard {
+    fn clone(&self) -> Self {
+        Self::new(self.kind)
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        assert!(
+            !self.running || thread::panicking(),
+            "{} was dropped while running without being cancelled",
+            self.kind,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future::{pending, CompletionFutureExt};
+
+    #[test]
+    fn cancelled() {
+        let mut fut = AssertCancelled::new(pending::<()>());
+        unsafe {
+            assert!(fut.poll(&mut crate::noop_cx()).is_pending());
+            assert!(fut.poll_cancel(&mut crate::noop_cx()).is_ready());
+        }
+        assert!(fut.was_cancelled());
+    }
+
+    #[test]
+    fn dropped_unpolled() {
+        drop(AssertCancelled::new(pending::<()>()));
+    }
+
+    #[test]
+    #[should_panic = "future was dropped while running without being cancelled"]
+    fn dropped_while_running() {
+        let mut fut = AssertCancelled::new(pending::<()>());
+        assert!(unsafe { fut.poll(&mut crate::noop_cx()) }.is_pending());
+    }
+
+    #[test]
+    fn clone_while_running() {
+        let mut fut = AssertCancelled::new(pending::<()>());
+        assert!(unsafe { fut.poll(&mut crate::noop_cx()) }.is_pending());
+        drop(fut.clone());
+        assert!(unsafe { fut.poll_cancel(&mut crate::noop_cx()) }.is_ready());
+    }
+}
@@ -0,0 +1,109 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::Duration;
+
+use completion_core::CompletionFuture;
+
+use super::{sleep, Sleep};
+
+/// Future for [`CompletionFutureExt::timeout_keep`](super::CompletionFutureExt::timeout_keep).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct TimeoutKeep<F> {
+    // `None` once the future has been handed back.
+    fut: Option<F>,
+    sleep: Sleep,
+}
+
+impl<F> TimeoutKeep<F> {
+    pub(super) fn new(fut: F, duration: Duration) -> Self {
+        Self {
+            fut: Some(fut),
+            sleep: sleep(duration),
+        }
+    }
+}
+
+impl<F: CompletionFuture + Unpin> CompletionFuture for TimeoutKeep<F> {
+    type Output = Result<F::Output, F>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let fut = this.fut.as_mut().expect("polled after completion");
+
+        if let Poll::Ready(output) = Pin::new(fut).poll(cx) {
+            this.fut = None;
+            return Poll::Ready(Ok(output));
+        }
+        if Future::poll(Pin::new(&mut this.sleep), cx).is_ready() {
+            return Poll::Ready(Err(this.fut.take().unwrap()));
+        }
+        Poll::Pending
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if let Some(fut) = &mut this.fut {
+            futures_core::ready!(Pin::new(fut).poll_cancel(cx));
+            this.fut = None;
+        }
+        Pin::new(&mut this.sleep).poll_cancel(cx)
+    }
+}
+
+impl<F> Future for TimeoutKeep<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output> + Unpin,
+{
+    type Output = Result<<F as CompletionFuture>::Output, F>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    use futures_lite::future::poll_fn;
+
+    use crate::future::{block_on, ready, CompletionFutureExt, FutureExt};
+    use crate::test_utils::CompletionFutureExt as _;
+
+    #[test]
+    fn completes_in_time() {
+        let fut = ready(5)
+            .check()
+            .max_cancels(0)
+            .timeout_keep(Duration::from_secs(10));
+        assert!(matches!(block_on(fut), Ok(5)));
+    }
+
+    #[test]
+    fn reawait_after_timeout() {
+        // This future is only ready once it has been handed back.
+        let handed_back = Cell::new(false);
+        let slow = poll_fn(|_| {
+            if handed_back.get() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .into_completion()
+        .check()
+        .max_cancels(0);
+
+        let fut = match block_on(slow.timeout_keep(Duration::from_millis(1))) {
+            Ok(()) => panic!("future completed before the timeout"),
+            Err(fut) => fut,
+        };
+        handed_back.set(true);
+
+        // The future was not cancelled, so it can be driven to completion.
+        assert!(block_on(fut.timeout_keep(Duration::from_secs(10))).is_ok());
+    }
+}
@@ -0,0 +1,91 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// Future for [`CompletionFutureExt::assert_unpin`](super::CompletionFutureExt::assert_unpin).
+#[derive(Debug, Clone)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct AssertUnpin<F> {
+    fut: F,
+}
+
+impl<F: Unpin> AssertUnpin<F> {
+    pub(super) fn new(fut: F) -> Self {
+        Self { fut }
+    }
+
+    /// Get the inner future.
+    pub fn into_inner(self) -> F {
+        self.fut
+    }
+}
+
+impl<F: CompletionFuture + Unpin> CompletionFuture for AssertUnpin<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().fut).poll(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut self.get_mut().fut).poll_cancel(cx)
+    }
+}
+
+impl<F> Future for AssertUnpin<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output> + Unpin,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use core::marker::PhantomPinned;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+
+    /// A future that isn't `Unpin`.
+    struct Pinned<F> {
+        fut: F,
+        _pinned: PhantomPinned,
+    }
+
+    impl<F: CompletionFuture + Unpin> CompletionFuture for Pinned<F> {
+        type Output = F::Output;
+
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.get_unchecked_mut().fut).poll(cx)
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            Pin::new(&mut self.get_unchecked_mut().fut).poll_cancel(cx)
+        }
+    }
+
+    #[test]
+    fn assert_unpin() {
+        let mut fut = Yield::new(2, ready(5)).assert_unpin();
+        assert_eq!(block_on(&mut fut), 5);
+    }
+
+    #[test]
+    fn unsync_unpin() {
+        let fut = Pinned {
+            fut: Yield::new(2, ready(5)),
+            _pinned: PhantomPinned,
+        };
+        let mut fut = fut.unsync_unpin();
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionFutureExt::poll(&mut fut, cx) }.is_pending());
+        assert_eq!(block_on(&mut fut), 5);
+    }
+}
@@ -0,0 +1,364 @@
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::{Mutex, MutexGuard};
+
+use completion_core::CompletionFuture;
+use futures_core::ready;
+
+/// One half of a future split by
+/// [`CompletionFutureExt::unzip_future`](super::CompletionFutureExt::unzip_future).
+///
+/// `I` is the index of the element of the output tuple that this half resolves to.
+#[must_use = "futures do nothing unless you use them"]
+pub struct FutureHalf<F, const I: usize>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    shared: Arc<Shared<F>>,
+}
+
+/// The output of a future that can be unzipped with
+/// [`CompletionFutureExt::unzip_future`](super::CompletionFutureExt::unzip_future).
+///
+/// This is implemented for all two-element tuples.
+pub trait Pair {
+    /// The first element.
+    type First;
+    /// The second element.
+    type Second;
+    /// Split the pair into its elements.
+    fn split(self) -> (Self::First, Self::Second);
+}
+impl<A, B> Pair for (A, B) {
+    type First = A;
+    type Second = B;
+    fn split(self) -> (A, B) {
+        self
+    }
+}
+
+/// State shared between the two halves.
+struct Shared<F>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    state: Mutex<State<F>>,
+    /// The wakers of both halves.
+    wakers: Arc<Wakers>,
+    /// The waker passed to the inner future, which wakes both halves.
+    waker: Waker,
+}
+
+struct State<F>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    fut: FutState<F>,
+    /// Whether the inner future has been polled, and so must now be run to completion.
+    started: bool,
+    halves: [HalfState; 2],
+}
+
+enum FutState<F>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    /// The future is running. It is pinned as it will not be moved until it is dropped.
+    Running(F),
+    /// The future has completed. Each element is taken by its half, so neither needs to be
+    /// `Clone`.
+    Done(
+        Option<<F::Output as Pair>::First>,
+        Option<<F::Output as Pair>::Second>,
+    ),
+    /// The future has been cancelled.
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HalfState {
+    /// The half has not been polled yet, so it is allowed to be dropped at any time.
+    Unpolled,
+    /// The half has been polled, so it will be driven until it completes or is cancelled.
+    Polled,
+    /// The half has completed, been cancelled or been dropped.
+    Finished,
+}
+
+#[derive(Debug)]
+struct Wakers(Mutex<[Option<Waker>; 2]>);
+
+impl Wakers {
+    fn register(&self, i: usize, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        match &wakers[i] {
+            Some(old) if old.will_wake(waker) => {}
+            _ => wakers[i] = Some(waker.clone()),
+        }
+    }
+    fn wake_one(&self, i: usize) {
+        let waker = self.0.lock().unwrap()[i].take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_one(0);
+        self.wake_one(1);
+    }
+}
+
+pub(super) fn unzip<F>(fut: F) -> (FutureHalf<F, 0>, FutureHalf<F, 1>)
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    let wakers = Arc::new(Wakers(Mutex::new([None, None])));
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            fut: FutState::Running(fut),
+            started: false,
+            halves: [HalfState::Unpolled; 2],
+        }),
+        waker: Waker::from(Arc::clone(&wakers)),
+        wakers,
+    });
+    (
+        FutureHalf {
+            shared: Arc::clone(&shared),
+        },
+        FutureHalf { shared },
+    )
+}
+
+impl<F, const I: usize> FutureHalf<F, I>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    /// Poll the inner future, returning the locked state once it has completed.
+    unsafe fn poll_shared(&self, cx: &mut Context<'_>) -> Poll<MutexGuard<'_, State<F>>> {
+        self.shared.wakers.register(I, cx.waker());
+        let mut state = self.shared.state.lock().unwrap();
+        state.halves[I] = HalfState::Polled;
+
+        match &state.fut {
+            FutState::Running(_) => ready!(self.poll_fut(&mut state)),
+            FutState::Done(..) => {}
+            FutState::Cancelled => panic!("polled `FutureHalf` after the future was cancelled"),
+        }
+        state.halves[I] = HalfState::Finished;
+        Poll::Ready(state)
+    }
+
+    /// Poll the running inner future, storing its output once it completes.
+    unsafe fn poll_fut(&self, state: &mut State<F>) -> Poll<()> {
+        state.started = true;
+        let fut = match &mut state.fut {
+            FutState::Running(fut) => Pin::new_unchecked(fut),
+            _ => unreachable!(),
+        };
+        let (first, second) =
+            ready!(fut.poll(&mut Context::from_waker(&self.shared.waker))).split();
+        state.fut = FutState::Done(Some(first), Some(second));
+        self.shared.wakers.wake_one(1 - I);
+        Poll::Ready(())
+    }
+
+    unsafe fn poll_cancel_shared(&self, cx: &mut Context<'_>) -> Poll<()> {
+        self.shared.wakers.register(I, cx.waker());
+        let mut state = self.shared.state.lock().unwrap();
+
+        if let FutState::Running(_) = state.fut {
+            match (state.started, state.halves[1 - I]) {
+                // The other half still wants the output, so we leave the future to it.
+                (false, HalfState::Unpolled | HalfState::Polled) | (true, HalfState::Polled) => {}
+                // The other half still wants the output but isn't obliged to drive the future, so
+                // we must run it to completion ourselves.
+                (true, HalfState::Unpolled) => ready!(self.poll_fut(&mut state)),
+                // Nobody wants the output, so we can drop the future.
+                (false, HalfState::Finished) => state.fut = FutState::Cancelled,
+                // Nobody wants the output, so we cancel the started future.
+                (true, HalfState::Finished) => {
+                    let fut = match &mut state.fut {
+                        FutState::Running(fut) => Pin::new_unchecked(fut),
+                        _ => unreachable!(),
+                    };
+                    ready!(fut.poll_cancel(&mut Context::from_waker(&self.shared.waker)));
+                    state.fut = FutState::Cancelled;
+                }
+            }
+        }
+
+        Self::finish(&mut state);
+        Poll::Ready(())
+    }
+
+    /// Mark this half as finished, dropping its element of the output if it's there.
+    fn finish(state: &mut State<F>) {
+        state.halves[I] = HalfState::Finished;
+        if let FutState::Done(first, second) = &mut state.fut {
+            if I == 0 {
+                *first = None;
+            } else {
+                *second = None;
+            }
+        }
+    }
+}
+
+impl<F, const I: usize> Drop for FutureHalf<F, I>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            if state.halves[I] != HalfState::Finished {
+                Self::finish(&mut state);
+                // The other half may be waiting on us to stop wanting the output.
+                drop(state);
+                self.shared.wakers.wake_one(1 - I);
+            }
+        }
+    }
+}
+
+impl<F, A, B> CompletionFuture for FutureHalf<F, 0>
+where
+    F: CompletionFuture<Output = (A, B)>,
+{
+    type Output = A;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = ready!(self.poll_shared(cx));
+        match &mut state.fut {
+            FutState::Done(first, _) => Poll::Ready(first.take().unwrap()),
+            _ => unreachable!(),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll_cancel_shared(cx)
+    }
+}
+
+impl<F, A, B> CompletionFuture for FutureHalf<F, 1>
+where
+    F: CompletionFuture<Output = (A, B)>,
+{
+    type Output = B;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = ready!(self.poll_shared(cx));
+        match &mut state.fut {
+            FutState::Done(_, second) => Poll::Ready(second.take().unwrap()),
+            _ => unreachable!(),
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll_cancel_shared(cx)
+    }
+}
+
+impl<F, A, B> Future for FutureHalf<F, 0>
+where
+    F: CompletionFuture<Output = (A, B)> + Future<Output = (A, B)>,
+{
+    type Output = A;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+impl<F, A, B> Future for FutureHalf<F, 1>
+where
+    F: CompletionFuture<Output = (A, B)> + Future<Output = (A, B)>,
+{
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+impl<F, const I: usize> Debug for FutureHalf<F, I>
+where
+    F: CompletionFuture,
+    F::Output: Pair,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FutureHalf").field("index", &I).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::future;
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+
+    fn poll<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<F::Output> {
+        unsafe { Pin::new(fut).poll(&mut crate::noop_cx()) }
+    }
+    fn poll_cancel<F: CompletionFuture + Unpin>(fut: &mut F) -> Poll<()> {
+        unsafe { Pin::new(fut).poll_cancel(&mut crate::noop_cx()) }
+    }
+
+    #[test]
+    fn both_halves() {
+        let (mut a, mut b) = unzip(Yield::once(future::ready((1, 2))).check().max_polls(2));
+        assert_eq!(poll(&mut b), Poll::Pending);
+        assert_eq!(poll(&mut a), Poll::Ready(1));
+        assert_eq!(poll(&mut b), Poll::Ready(2));
+    }
+
+    #[test]
+    fn cancel_both() {
+        let (mut a, mut b) = unzip(Yield::new(2, future::pending::<((), ())>()).check());
+        assert_eq!(poll(&mut a), Poll::Pending);
+        assert_eq!(poll_cancel(&mut b), Poll::Ready(()));
+        assert_eq!(poll_cancel(&mut a), Poll::Pending);
+        assert_eq!(poll_cancel(&mut a), Poll::Ready(()));
+    }
+
+    #[test]
+    fn cancel_before_start() {
+        let (mut a, b) = unzip(future::pending::<((), ())>().check().max_polls(0));
+        assert_eq!(poll_cancel(&mut a), Poll::Ready(()));
+        drop(b);
+    }
+
+    #[test]
+    fn cancel_drives_for_other_half() {
+        let (mut a, mut b) = unzip(Yield::new(2, future::ready((1, 2))).check().max_cancels(0));
+        assert_eq!(poll(&mut a), Poll::Pending);
+        assert_eq!(poll_cancel(&mut a), Poll::Pending);
+        assert_eq!(poll_cancel(&mut a), Poll::Ready(()));
+        assert_eq!(poll(&mut b), Poll::Ready(2));
+    }
+
+    #[test]
+    fn cancel_after_other_half_dropped() {
+        let (mut a, b) = unzip(Yield::new(2, future::pending::<((), ())>()).check());
+        assert_eq!(poll(&mut a), Poll::Pending);
+        assert_eq!(poll_cancel(&mut a), Poll::Pending);
+        drop(b);
+        assert_eq!(poll_cancel(&mut a), Poll::Ready(()));
+    }
+}
@@ -0,0 +1,62 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::with_budget`](super::CompletionFutureExt::with_budget).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct WithBudget<F> {
+        #[pin]
+        fut: F,
+        budget: usize,
+        remaining: usize,
+    }
+}
+
+impl<F> WithBudget<F> {
+    pub(super) fn new(fut: F, budget: usize) -> Self {
+        assert_ne!(budget, 0, "budget must be non-zero");
+        Self {
+            fut,
+            budget,
+            remaining: budget,
+        }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for WithBudget<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if *this.remaining == 0 {
+            *this.remaining = *this.budget;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let poll = this.fut.poll(cx);
+        if poll.is_pending() {
+            *this.remaining -= 1;
+        }
+        poll
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().fut.poll_cancel(cx)
+    }
+}
+impl<F> Future for WithBudget<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
@@ -0,0 +1,186 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use atomic_waker::AtomicWaker;
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+use super::{saturating_add, sleep_until, Sleep};
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::with_deadline`](super::CompletionFutureExt::with_deadline).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct WithDeadline<F> {
+        #[pin]
+        fut: F,
+        sleep: Sleep,
+        shared: Arc<DeadlineShared>,
+        // Whether the deadline has passed and the future is being cancelled.
+        timed_out: bool,
+    }
+}
+
+/// A handle used to extend the deadline of a [`WithDeadline`] future.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone)]
+pub struct DeadlineHandle {
+    shared: Arc<DeadlineShared>,
+}
+
+#[derive(Debug)]
+struct DeadlineShared {
+    deadline: Mutex<Instant>,
+    /// The waker of the future, woken when the deadline changes.
+    waker: AtomicWaker,
+}
+
+impl<F> WithDeadline<F> {
+    pub(super) fn new(fut: F, initial: Duration) -> (Self, DeadlineHandle) {
+        let deadline = saturating_add(Instant::now(), initial);
+        let shared = Arc::new(DeadlineShared {
+            deadline: Mutex::new(deadline),
+            waker: AtomicWaker::new(),
+        });
+        let fut = Self {
+            fut,
+            sleep: sleep_until(deadline),
+            shared: Arc::clone(&shared),
+            timed_out: false,
+        };
+        (fut, DeadlineHandle { shared })
+    }
+}
+
+impl DeadlineHandle {
+    /// Push the deadline out by the given duration.
+    ///
+    /// This has no effect once the future has noticed that the deadline passed and started
+    /// cancelling.
+    pub fn extend(&self, duration: Duration) {
+        let mut deadline = self.shared.lock();
+        *deadline = saturating_add(*deadline, duration);
+        drop(deadline);
+        self.shared.waker.wake();
+    }
+
+    /// Get the current deadline.
+    #[must_use]
+    pub fn deadline(&self) -> Instant {
+        *self.shared.lock()
+    }
+}
+
+impl DeadlineShared {
+    fn lock(&self) -> MutexGuard<'_, Instant> {
+        self.deadline.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for WithDeadline<F> {
+    type Output = Result<F::Output, TimedOut>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if !*this.timed_out {
+            if let Poll::Ready(output) = this.fut.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+
+            this.shared.waker.register(cx.waker());
+            let deadline = *this.shared.lock();
+            if this.sleep.deadline() != deadline {
+                *this.sleep = sleep_until(deadline);
+            }
+            if Future::poll(Pin::new(&mut *this.sleep), cx).is_pending() {
+                return Poll::Pending;
+            }
+            *this.timed_out = true;
+        }
+
+        futures_core::ready!(this.fut.poll_cancel(cx));
+        Poll::Ready(Err(TimedOut))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        futures_core::ready!(this.fut.poll_cancel(cx));
+        Pin::new(this.sleep).poll_cancel(cx)
+    }
+}
+
+impl<F> Future for WithDeadline<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = Result<<F as CompletionFuture>::Output, TimedOut>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+/// The error returned by [`WithDeadline`] when the deadline passes before the future completes.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl Error for TimedOut {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use crate::future::{block_on, pending, sleep, CompletionFutureExt};
+
+    #[test]
+    fn extended_past_original_expiry() {
+        let start = Instant::now();
+        let (fut, handle) =
+            sleep(Duration::from_millis(100)).with_deadline(Duration::from_millis(30));
+
+        let extender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            handle.extend(Duration::from_secs(10));
+        });
+        assert_eq!(block_on(fut), Ok(()));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        extender.join().unwrap();
+    }
+
+    #[test]
+    fn huge_durations() {
+        let (mut fut, handle) = pending::<()>().with_deadline(Duration::MAX);
+        handle.extend(Duration::MAX);
+        assert!(handle.deadline() > Instant::now() + Duration::from_secs(100_000_000));
+
+        let mut cx = crate::noop_cx();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), &mut cx) }.is_pending());
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(&mut cx) }.is_ready());
+    }
+
+    #[test]
+    fn times_out() {
+        let (fut, counts) = pending::<()>().count_polls();
+        let (fut, handle) = fut.with_deadline(Duration::from_millis(10));
+        let deadline = handle.deadline();
+
+        assert_eq!(block_on(fut), Err(TimedOut));
+        assert!(Instant::now() >= deadline);
+        assert_eq!(counts.cancels(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::with_waker`](super::CompletionFutureExt::with_waker).
+    #[derive(Debug, Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct WithWaker<F> {
+        #[pin]
+        fut: F,
+        waker: Waker,
+    }
+}
+
+impl<F> WithWaker<F> {
+    pub(super) fn new(fut: F, waker: Waker) -> Self {
+        Self { fut, waker }
+    }
+}
+
+impl<F: CompletionFuture> CompletionFuture for WithWaker<F> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.fut.poll(&mut Context::from_waker(this.waker))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        this.fut.poll_cancel(&mut Context::from_waker(this.waker))
+    }
+}
+
+impl<F> Future for WithWaker<F>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionFutureExt::map_waker`](super::CompletionFutureExt::map_waker).
+    #[derive(Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct MapWaker<F, W> {
+        #[pin]
+        fut: F,
+        f: W,
+    }
+}
+
+impl<F, W> MapWaker<F, W> {
+    pub(super) fn new(fut: F, f: W) -> Self {
+        Self { fut, f }
+    }
+}
+
+impl<F: Debug, W> Debug for MapWaker<F, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapWaker")
+            .field("fut", &self.fut)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, W> CompletionFuture for MapWaker<F, W>
+where
+    F: CompletionFuture,
+    W: FnMut(&Waker) -> Waker,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let waker = (this.f)(cx.waker());
+        this.fut.poll(&mut Context::from_waker(&waker))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let waker = (this.f)(cx.waker());
+        this.fut.poll_cancel(&mut Context::from_waker(&waker))
+    }
+}
+
+impl<F, W> Future for MapWaker<F, W>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    W: FnMut(&Waker) -> Waker,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    use crate::future::CompletionFutureExt;
+
+    /// A future that never completes, checks that it is given a particular waker and wakes it when
+    /// polled.
+    struct Expect(Waker);
+
+    impl CompletionFuture for Expect {
+        type Output = ();
+
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            assert!(cx.waker().will_wake(&self.0));
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            assert!(cx.waker().will_wake(&self.0));
+            Poll::Ready(())
+        }
+    }
+
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn with_waker() {
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut fut = Expect(waker.clone()).with_waker(waker);
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            assert!(Pin::new(&mut fut).poll(cx).is_pending());
+            assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+        }
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn map_waker() {
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut calls = 0;
+        let mut fut = Expect(waker.clone()).map_waker(|_| {
+            calls += 1;
+            waker.clone()
+        });
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            assert!(Pin::new(&mut fut).poll(cx).is_pending());
+            assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+        }
+        drop(fut);
+        assert_eq!(calls, 2);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use completion_core::CompletionFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for
+    /// [`CompletionFutureExt::with_waker_hook`](super::CompletionFutureExt::with_waker_hook).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[derive(Clone)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct WithWakerHook<F, H> {
+        #[pin]
+        fut: F,
+        hook: Arc<H>,
+    }
+}
+
+impl<F, H> WithWakerHook<F, H> {
+    pub(super) fn new(fut: F, hook: H) -> Self {
+        Self {
+            fut,
+            hook: Arc::new(hook),
+        }
+    }
+}
+
+impl<F: Debug, H> Debug for WithWakerHook<F, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithWakerHook")
+            .field("fut", &self.fut)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A waker that calls the hook before forwarding the wakeup.
+struct HookWaker<H> {
+    waker: Waker,
+    hook: Arc<H>,
+}
+
+impl<H: Fn() + Send + Sync + 'static> Wake for HookWaker<H> {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        (self.hook)();
+        self.waker.wake_by_ref();
+    }
+}
+
+/// Construct a context whose waker calls the hook, and pass it to the given function.
+fn with_hooked_cx<H, R>(hook: &Arc<H>, cx: &Context<'_>, f: impl FnOnce(&mut Context<'_>) -> R) -> R
+where
+    H: Fn() + Send + Sync + 'static,
+{
+    let waker = Waker::from(Arc::new(HookWaker {
+        waker: cx.waker().clone(),
+        hook: Arc::clone(hook),
+    }));
+    f(&mut Context::from_waker(&waker))
+}
+
+impl<F, H> CompletionFuture for WithWakerHook<F, H>
+where
+    F: CompletionFuture,
+    H: Fn() + Send + Sync + 'static,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let fut = this.fut;
+        with_hooked_cx(this.hook, cx, |cx| fut.poll(cx))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let fut = this.fut;
+        with_hooked_cx(this.hook, cx, |cx| fut.poll_cancel(cx))
+    }
+}
+
+impl<F, H> Future for WithWakerHook<F, H>
+where
+    F: CompletionFuture + Future<Output = <F as CompletionFuture>::Output>,
+    H: Fn() + Send + Sync + 'static,
+{
+    type Output = <F as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use crate::future::CompletionFutureExt;
+
+    /// A future that stores its waker, to be woken manually.
+    #[derive(Default)]
+    struct Manual {
+        waker: Arc<Mutex<Option<Waker>>>,
+    }
+
+    impl CompletionFuture for Manual {
+        type Output = ();
+
+        unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            *self.waker.lock().unwrap() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            *self.waker.lock().unwrap() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn hook_fires_on_wake() {
+        let fut = Manual::default();
+        let waker = Arc::clone(&fut.waker);
+
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let wakes_2 = Arc::clone(&wakes);
+        let mut fut = fut.with_waker_hook(move || {
+            wakes_2.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+        assert_eq!(wakes.load(Ordering::SeqCst), 0);
+
+        waker.lock().unwrap().take().unwrap().wake();
+        assert_eq!(wakes.load(Ordering::SeqCst), 1);
+
+        // Cancellation wakers are hooked too.
+        assert!(unsafe { Pin::new(&mut fut).poll_cancel(cx) }.is_pending());
+        waker.lock().unwrap().as_ref().unwrap().wake_by_ref();
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+    }
+}
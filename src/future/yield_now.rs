@@ -0,0 +1,90 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionFuture;
+
+/// Yield to the executor once.
+///
+/// The first time the returned future is polled it wakes its task and returns
+/// [`Poll::Pending`], and the second time it completes. This lets a long-running loop give other
+/// tasks a chance to run between chunks of work. Cancelling it completes immediately.
+///
+/// # Examples
+///
+/// ```
+/// use completion::future;
+///
+/// # future::block_on(completion::completion_async! {
+/// for chunk in 0..4 {
+///     // Do some work on `chunk`...
+///     future::yield_now().await;
+/// }
+/// # });
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Future for [`yield_now`].
+#[derive(Debug, Clone)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl CompletionFuture for YieldNow {
+    type Output = ();
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Future::poll(self, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.yielded {
+            Poll::Ready(())
+        } else {
+            this.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn pending_then_ready() {
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let cx = &mut Context::from_waker(&waker);
+
+        let mut fut = yield_now();
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_pending());
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        assert!(unsafe { CompletionFuture::poll(Pin::new(&mut fut), cx) }.is_ready());
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -11,10 +11,15 @@
 //!
 //! - `std`: Enables features that require the standard library, on by default.
 //! - `alloc`: Enables features that require allocation, on by default.
-//! - `macro`: Enables the [`completion`], [`completion_async`], [`completion_async_move`] and
-//! [`completion_stream`] macros, on by default.
+//! - `macro`: Enables the [`completion`], [`completion_async`], [`completion_async_move`],
+//!   [`completion_stream`] and [`completion_generator`] macros, on by default.
+//! - `test-util`: Enables the [`future::test`] and [`stream::test`] modules of utilities for
+//!   testing completion futures and streams.
+//! - `allocator_api`: Enables support for boxing futures in custom allocators. This requires a
+//!   nightly compiler.
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 #![warn(
     clippy::pedantic,
     clippy::wrong_pub_self_convention,
@@ -42,7 +47,7 @@ use core::task::Waker;
 use core::task::{Context, Poll};
 
 #[doc(no_inline)]
-pub use completion_core::{CompletionFuture, CompletionStream};
+pub use completion_core::{CompletionFuture, CompletionSink, CompletionStream};
 use futures_core::Stream;
 use pin_project_lite::pin_project;
 
@@ -51,6 +56,8 @@ pub mod future;
 pub use self::future::{BoxCompletionFuture, LocalBoxCompletionFuture};
 #[doc(no_inline)]
 pub use self::future::{CompletionFutureExt, FutureExt};
+#[doc(no_inline)]
+pub use self::future::{DynCompletionFuture, LocalDynCompletionFuture};
 
 pub mod stream;
 #[cfg(feature = "alloc")]
@@ -58,6 +65,10 @@ pub use self::stream::{BoxCompletionStream, LocalBoxCompletionStream};
 #[doc(no_inline)]
 pub use self::stream::{CompletionStreamExt, StreamExt};
 
+pub mod sink;
+#[doc(no_inline)]
+pub use self::sink::CompletionSinkExt;
+
 #[cfg(feature = "macro")]
 mod macros;
 #[cfg(feature = "macro")]
@@ -67,6 +78,65 @@ pub use macros::*;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 pub mod io;
 
+/// Pin a value to the stack.
+///
+/// This shadows each given variable with a [`Pin<&mut T>`](Pin) to its value, which can then be
+/// used to call [`CompletionFuture::poll`] and [`CompletionFuture::poll_cancel`] without having to
+/// box the future or write any unsafe code to pin it.
+///
+/// Remember that once a completion future has been polled, it must be polled to completion before
+/// it is dropped.
+///
+/// # Examples
+///
+/// Drive a future to completion by hand:
+///
+/// ```
+/// use std::pin::Pin;
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll, Wake, Waker};
+///
+/// use completion::{completion_async, CompletionFuture};
+/// use futures_lite::future::yield_now;
+///
+/// /// Poll a pinned future once, returning its output if it is ready.
+/// ///
+/// /// # Safety
+/// ///
+/// /// The future must be polled until it completes, as with `CompletionFuture::poll`.
+/// unsafe fn poll_once<F: CompletionFuture>(fut: Pin<&mut F>) -> Option<F::Output> {
+///     struct NoopWaker;
+///     impl Wake for NoopWaker {
+///         fn wake(self: Arc<Self>) {}
+///     }
+///     let waker = Waker::from(Arc::new(NoopWaker));
+///     match fut.poll(&mut Context::from_waker(&waker)) {
+///         Poll::Ready(output) => Some(output),
+///         Poll::Pending => None,
+///     }
+/// }
+///
+/// let fut = completion_async! {
+///     yield_now().await;
+///     5
+/// };
+/// completion::pin!(fut);
+///
+/// // SAFETY: We poll the future until it completes.
+/// unsafe {
+///     assert_eq!(poll_once(fut.as_mut()), None);
+///     assert_eq!(poll_once(fut.as_mut()), Some(5));
+/// }
+/// ```
+#[macro_export]
+macro_rules! pin {
+    ($($x:ident),* $(,)?) => { $(
+        let mut $x = $x;
+        #[allow(unused_mut)]
+        let mut $x = unsafe { ::core::pin::Pin::new_unchecked(&mut $x) };
+    )* }
+}
+
 pin_project! {
     /// Unsafely assert that the inner future or stream will complete.
     ///
@@ -1,4 +1,5 @@
 use core::cell::Cell;
+use core::fmt::{self, Debug, Formatter};
 use core::future::Future;
 use core::marker::PhantomData;
 use core::pin::Pin;
@@ -8,6 +9,8 @@ use core::task::{Context, Poll};
 use completion_core::{CompletionFuture, CompletionStream};
 use pin_project_lite::pin_project;
 
+#[doc(hidden)]
+pub use completion_macro::completion_generator_inner as __completion_generator_inner;
 #[doc(hidden)]
 pub use completion_macro::completion_stream_inner as __completion_stream_inner;
 
@@ -50,6 +53,50 @@ macro_rules! completion_stream {
     }
 }
 
+/// A bang macro to generate completion async streams that also produce a final value.
+///
+/// This works like [`completion_stream!`], except that the body can `return` a value once it has
+/// finished yielding items. It evaluates to a [`GeneratorStream`], whose
+/// [`take_return`](GeneratorStream::take_return) method gives the returned value after the stream
+/// has ended. The `?` operator returns the error as the final value instead of yielding it, so it
+/// can only be used if the body returns an [`Option`] or [`Result`].
+///
+/// If the stream is cancelled, the body is cancelled at the `.await` it is suspended at and no
+/// value is returned.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{completion_generator, CompletionStreamExt};
+///
+/// # completion::future::block_on(completion::completion_async! {
+/// let stream = completion_generator! {
+///     let mut total = 0;
+///     for i in 1..=3 {
+///         yield i;
+///         total += i;
+///     }
+///     total
+/// };
+///
+/// # use futures_lite::pin;
+/// pin!(stream);
+///
+/// assert_eq!(stream.next().await, Some(1));
+/// assert_eq!(stream.next().await, Some(2));
+/// assert_eq!(stream.next().await, Some(3));
+/// assert_eq!(stream.next().await, None);
+/// assert_eq!(stream.take_return(), Some(6));
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "macro", feature = "std"))))]
+#[macro_export]
+macro_rules! completion_generator {
+    ($($tt:tt)*) => {
+        $crate::__completion_generator_inner!(($crate) $($tt)*)
+    }
+}
+
 #[doc(hidden)]
 pub fn __completion_stream<T, F>(
     generator: F,
@@ -58,9 +105,19 @@ pub fn __completion_stream<T, F>(
 where
     F: CompletionFuture<Output = ()>,
 {
-    Wrapper {
+    __completion_generator(generator, item)
+}
+
+#[doc(hidden)]
+pub fn __completion_generator<T, F>(generator: F, _item: PhantomData<T>) -> GeneratorStream<T, F>
+where
+    F: CompletionFuture,
+{
+    GeneratorStream {
         generator,
-        _item: item,
+        returned: None,
+        done: false,
+        _item: PhantomData,
     }
 }
 
@@ -69,17 +126,40 @@ thread_local! {
 }
 
 pin_project! {
-    struct Wrapper<T, F> {
+    /// Stream for [`completion_generator!`].
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "macro", feature = "std"))))]
+    #[must_use = "streams do nothing unless you use them"]
+    pub struct GeneratorStream<T, F: CompletionFuture> {
         #[pin]
         generator: F,
+        // The value returned by the body, if it has finished and it hasn't been taken yet.
+        returned: Option<F::Output>,
+        // Whether the body has finished.
+        done: bool,
         _item: PhantomData<T>,
     }
 }
 
-impl<T, F> CompletionStream for Wrapper<T, F>
-where
-    F: CompletionFuture<Output = ()>,
-{
+impl<T, F: CompletionFuture> GeneratorStream<T, F> {
+    /// Take the value returned by the stream's body.
+    ///
+    /// This returns `None` if the stream hasn't ended yet, if it was cancelled before it ended, or
+    /// if the value has already been taken.
+    #[must_use]
+    pub fn take_return(self: Pin<&mut Self>) -> Option<F::Output> {
+        self.project().returned.take()
+    }
+}
+
+impl<T, F: CompletionFuture> Debug for GeneratorStream<T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeneratorStream")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F: CompletionFuture> CompletionStream for GeneratorStream<T, F> {
     type Item = T;
 
     unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
@@ -91,6 +171,9 @@ where
         }
 
         let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
 
         let mut yielded = None;
         let yielded_ptr: *mut () = (&mut yielded as *mut Option<T>).cast();
@@ -101,13 +184,21 @@ where
 
         match (yielded, res) {
             (Some(yielded), Poll::Pending) => Poll::Ready(Some(yielded)),
-            (None, Poll::Ready(())) => Poll::Ready(None),
+            (None, Poll::Ready(returned)) => {
+                *this.done = true;
+                *this.returned = Some(returned);
+                Poll::Ready(None)
+            }
             (None, Poll::Pending) => Poll::Pending,
             _ => unreachable!(),
         }
     }
     unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
-        self.project().generator.poll_cancel(cx)
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(());
+        }
+        this.generator.poll_cancel(cx)
     }
 }
 
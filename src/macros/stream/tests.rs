@@ -33,7 +33,10 @@ use completion_core::CompletionStream;
 use futures_lite::future::yield_now;
 use futures_lite::pin;
 
-use crate::{completion_async, completion_stream, future::block_on, CompletionStreamExt};
+use crate::{
+    completion_async, completion_generator, completion_stream, future::block_on, test_utils,
+    CompletionStreamExt,
+};
 
 #[test]
 fn empty() {
@@ -182,3 +185,56 @@ fn send() {
         yield_now().await;
     });
 }
+
+#[test]
+fn generator_return() {
+    block_on(completion_async! {
+        let stream = completion_generator! {
+            yield 1;
+            yield_now().await;
+            yield 2;
+            "summary"
+        };
+        pin!(stream);
+
+        assert_eq!(stream.next().await, Some(1));
+        assert_eq!(stream.as_mut().take_return(), None);
+        assert_eq!(stream.next().await, Some(2));
+        assert_eq!(stream.next().await, None);
+        assert_eq!(stream.as_mut().take_return(), Some("summary"));
+        assert_eq!(stream.as_mut().take_return(), None);
+        assert_eq!(stream.next().await, None);
+    });
+}
+
+#[test]
+fn generator_try() {
+    block_on(completion_async! {
+        let stream = completion_generator! {
+            yield 1;
+            Err::<(), _>(5)?;
+            yield 2;
+            Ok(())
+        };
+        pin!(stream);
+
+        assert_eq!(stream.next().await, Some(1));
+        assert_eq!(stream.next().await, None);
+        assert_eq!(stream.take_return(), Some(Err(5)));
+    });
+}
+
+#[test]
+fn generator_cancel() {
+    let stream = completion_generator! {
+        yield 1;
+        crate::future::pending::<()>().await;
+        "unreachable"
+    };
+    pin!(stream);
+
+    assert_eq!(test_utils::poll_once(stream.next()), Some(Some(1)));
+    assert_eq!(test_utils::poll_once(stream.next()), None);
+    assert!(test_utils::poll_cancel_once(stream.next()));
+    assert_eq!(stream.take_return(), None);
+}
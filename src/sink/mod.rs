@@ -0,0 +1,64 @@
+//! Utilities for the [`CompletionSink`] trait.
+
+#[doc(no_inline)]
+pub use completion_core::CompletionSink;
+
+use completion_core::CompletionStream;
+
+mod send;
+pub use send::*;
+
+/// Extension trait for [`CompletionSink`].
+pub trait CompletionSinkExt<Item>: CompletionSink<Item> {
+    /// Send a value to the sink, flushing it afterwards.
+    ///
+    /// If the returned future is cancelled before the value has been passed to
+    /// [`start_send`](CompletionSink::start_send), the value is dropped and the sink never sees
+    /// any part of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::CompletionSinkExt;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut sink = Vec::new();
+    /// sink.send(1).await.unwrap();
+    /// sink.send(2).await.unwrap();
+    /// assert_eq!(sink, [1, 2]);
+    /// # });
+    /// ```
+    fn send(&mut self, item: Item) -> Send<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Send::new(self, item)
+    }
+
+    /// Send all the items of a stream to the sink, flushing it once the stream is exhausted.
+    ///
+    /// Cancelling the returned future cancels the stream if it is in the middle of yielding an
+    /// item and the sink if it is in the middle of an operation. An item that has been taken from
+    /// the stream but not yet passed to the sink is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionSinkExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut sink = Vec::new();
+    /// sink.send_all(stream::iter(0..5).into_completion()).await.unwrap();
+    /// assert_eq!(sink, [0, 1, 2, 3, 4]);
+    /// # });
+    /// ```
+    fn send_all<St>(&mut self, stream: St) -> SendAll<'_, Self, St>
+    where
+        St: CompletionStream<Item = Item>,
+        Self: Unpin,
+    {
+        SendAll::new(self, stream)
+    }
+}
+impl<T: CompletionSink<Item> + ?Sized, Item> CompletionSinkExt<Item> for T {}
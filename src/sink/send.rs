@@ -0,0 +1,259 @@
+//! `Send` and `SendAll`.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionSink, CompletionStream};
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+/// Future for [`CompletionSinkExt::send`](super::CompletionSinkExt::send).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you use them"]
+pub struct Send<'a, Si: ?Sized, Item> {
+    sink: &'a mut Si,
+    item: Option<Item>,
+    /// Whether the sink is in the middle of an operation.
+    busy: bool,
+}
+
+impl<'a, Si: ?Sized, Item> Send<'a, Si, Item> {
+    pub(super) fn new(sink: &'a mut Si, item: Item) -> Self {
+        Self {
+            sink,
+            item: Some(item),
+            busy: false,
+        }
+    }
+}
+
+impl<Si: ?Sized, Item> Unpin for Send<'_, Si, Item> {}
+
+impl<Si, Item> CompletionFuture for Send<'_, Si, Item>
+where
+    Si: CompletionSink<Item> + Unpin + ?Sized,
+{
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut sink = Pin::new(&mut *this.sink);
+
+        this.busy = true;
+        if this.item.is_some() {
+            if let Err(e) = ready!(sink.as_mut().poll_ready(cx)) {
+                this.busy = false;
+                return Poll::Ready(Err(e));
+            }
+            let item = this.item.take().unwrap();
+            if let Err(e) = sink.as_mut().start_send(item) {
+                this.busy = false;
+                return Poll::Ready(Err(e));
+            }
+        }
+        let res = ready!(sink.poll_flush(cx));
+        this.busy = false;
+        Poll::Ready(res)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if this.busy {
+            ready!(CompletionSink::poll_cancel(Pin::new(&mut *this.sink), cx));
+            this.busy = false;
+        }
+        this.item = None;
+        Poll::Ready(())
+    }
+}
+
+pin_project! {
+    /// Future for [`CompletionSinkExt::send_all`](super::CompletionSinkExt::send_all).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct SendAll<'a, Si: ?Sized, St: CompletionStream> {
+        sink: &'a mut Si,
+        #[pin]
+        stream: St,
+        // An item taken from the stream that hasn't been passed to the sink yet.
+        buffered: Option<St::Item>,
+        // Whether the sink is in the middle of an operation.
+        sink_busy: bool,
+        // Whether the stream is in the middle of yielding an item.
+        stream_busy: bool,
+        stream_done: bool,
+    }
+}
+
+impl<'a, Si: ?Sized, St: CompletionStream> SendAll<'a, Si, St> {
+    pub(super) fn new(sink: &'a mut Si, stream: St) -> Self {
+        Self {
+            sink,
+            stream,
+            buffered: None,
+            sink_busy: false,
+            stream_busy: false,
+            stream_done: false,
+        }
+    }
+}
+
+impl<Si, St> CompletionFuture for SendAll<'_, Si, St>
+where
+    Si: CompletionSink<St::Item> + Unpin + ?Sized,
+    St: CompletionStream,
+{
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let mut sink = Pin::new(&mut **this.sink);
+
+        loop {
+            if this.buffered.is_some() {
+                *this.sink_busy = true;
+                let buffered = &mut *this.buffered;
+                let res = ready!(sink.as_mut().poll_ready(cx))
+                    .and_then(|()| sink.as_mut().start_send(buffered.take().unwrap()));
+                *this.sink_busy = false;
+                if let Err(e) = res {
+                    return Poll::Ready(Err(e));
+                }
+            }
+
+            if *this.stream_done {
+                break;
+            }
+            *this.stream_busy = true;
+            let item = ready!(this.stream.as_mut().poll_next(cx));
+            *this.stream_busy = false;
+            match item {
+                Some(item) => *this.buffered = Some(item),
+                None => *this.stream_done = true,
+            }
+        }
+
+        *this.sink_busy = true;
+        let res = ready!(sink.poll_flush(cx));
+        *this.sink_busy = false;
+        Poll::Ready(res)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if *this.stream_busy {
+            ready!(this.stream.poll_cancel(cx));
+            *this.stream_busy = false;
+            *this.stream_done = true;
+        }
+        if *this.sink_busy {
+            ready!(CompletionSink::poll_cancel(Pin::new(&mut **this.sink), cx));
+            *this.sink_busy = false;
+        }
+        *this.buffered = None;
+        Poll::Ready(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    use crate::future::block_on;
+    use crate::sink::CompletionSinkExt;
+    use crate::StreamExt;
+
+    /// A sink around a `Vec` that isn't ready the first time it's polled.
+    #[derive(Default)]
+    struct Slow {
+        items: Vec<i32>,
+        waited: bool,
+        cancels: usize,
+    }
+
+    impl CompletionSink<i32> for Slow {
+        type Error = Infallible;
+
+        unsafe fn poll_ready(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            if self.waited {
+                Poll::Ready(Ok(()))
+            } else {
+                self.waited = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+        fn start_send(mut self: Pin<&mut Self>, item: i32) -> Result<(), Self::Error> {
+            self.items.push(item);
+            Ok(())
+        }
+        unsafe fn poll_flush(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_close(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_cancel(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.cancels += 1;
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn send() {
+        let mut sink = Slow::default();
+        block_on(sink.send(5)).unwrap();
+        assert_eq!(sink.items, [5]);
+        assert_eq!(sink.cancels, 0);
+    }
+
+    #[test]
+    fn cancel_send() {
+        let mut sink = Slow::default();
+        let cx = &mut crate::noop_cx();
+
+        let mut fut = sink.send(5);
+        unsafe {
+            assert!(Pin::new(&mut fut).poll(cx).is_pending());
+            assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+        }
+        assert!(sink.items.is_empty());
+        assert_eq!(sink.cancels, 1);
+
+        // Cancelling before polling doesn't touch the sink.
+        let mut fut = sink.send(6);
+        unsafe { assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready()) };
+        assert!(sink.items.is_empty());
+        assert_eq!(sink.cancels, 1);
+    }
+
+    #[test]
+    fn send_all() {
+        let mut sink = Slow::default();
+        block_on(sink.send_all(futures_lite::stream::iter(0..4).into_completion())).unwrap();
+        assert_eq!(sink.items, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn cancel_send_all() {
+        let mut sink = Slow::default();
+        let cx = &mut crate::noop_cx();
+
+        let mut fut = sink.send_all(futures_lite::stream::iter(0..4).into_completion());
+        unsafe {
+            assert!(Pin::new(&mut fut).poll(cx).is_pending());
+            assert!(Pin::new(&mut fut).poll_cancel(cx).is_ready());
+        }
+        assert!(sink.items.is_empty());
+        assert_eq!(sink.cancels, 1);
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::collections::BinaryHeap;
+use core::cmp::{Ordering, Reverse};
+use core::fmt::{self, Debug, Formatter};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::stream::Unordered;
+
+pin_project! {
+    /// Stream for
+    /// [`CompletionStreamExt::buffered_by_key`](crate::CompletionStreamExt::buffered_by_key).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub struct BufferedByKey<S, K, F>
+    where
+        S: CompletionStream,
+        S::Item: CompletionFuture,
+    {
+        #[pin]
+        stream: S,
+        // Whether the stream is in the middle of yielding a future.
+        stream_busy: bool,
+        // Whether the stream has been exhausted or cancelled.
+        stream_done: bool,
+        in_flight: Unordered<S::Item>,
+        // Completed outputs that haven't been yielded yet, lowest key first.
+        buffer: BinaryHeap<Reverse<Keyed<K, <S::Item as CompletionFuture>::Output>>>,
+        limit: usize,
+        key_fn: F,
+    }
+}
+
+impl<S, K, F> BufferedByKey<S, K, F>
+where
+    S: CompletionStream,
+    S::Item: CompletionFuture,
+{
+    pub(crate) fn new(stream: S, limit: usize, key_fn: F) -> Self {
+        assert_ne!(limit, 0, "`buffered_by_key` limit must be non-zero");
+        Self {
+            stream,
+            stream_busy: false,
+            stream_done: false,
+            in_flight: Unordered::new(),
+            buffer: BinaryHeap::new(),
+            limit,
+            key_fn,
+        }
+    }
+}
+
+impl<S, K, F> Debug for BufferedByKey<S, K, F>
+where
+    S: CompletionStream + Debug,
+    S::Item: CompletionFuture,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedByKey")
+            .field("stream", &self.stream)
+            .field("in_flight", &self.in_flight)
+            .field("buffered", &self.buffer.len())
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An output ordered only by its key.
+struct Keyed<K, T> {
+    key: K,
+    value: T,
+}
+
+impl<K: Ord, T> PartialEq for Keyed<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+impl<K: Ord, T> Eq for Keyed<K, T> {}
+impl<K: Ord, T> PartialOrd for Keyed<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<K: Ord, T> Ord for Keyed<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<S, K, F> CompletionStream for BufferedByKey<S, K, F>
+where
+    S: CompletionStream,
+    S::Item: CompletionFuture,
+    K: Ord,
+    F: FnMut(&<S::Item as CompletionFuture>::Output) -> K,
+{
+    type Item = <S::Item as CompletionFuture>::Output;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while !*this.stream_done && this.in_flight.len() + this.buffer.len() < *this.limit {
+            *this.stream_busy = false;
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(fut)) => this.in_flight.push(fut),
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => {
+                    *this.stream_busy = true;
+                    break;
+                }
+            }
+        }
+
+        while !this.in_flight.is_empty() {
+            match Pin::new(&mut *this.in_flight).poll_next(cx) {
+                Poll::Ready(Some(value)) => {
+                    let key = (this.key_fn)(&value);
+                    this.buffer.push(Reverse(Keyed { key, value }));
+                }
+                Poll::Ready(None) => unreachable!("the set is never closed"),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        // The source stream must not be left in the middle of yielding a future.
+        if *this.stream_busy {
+            return Poll::Pending;
+        }
+
+        // Every future in the window has completed, so the lowest key can be yielded.
+        match this.buffer.pop() {
+            Some(Reverse(keyed)) => Poll::Ready(Some(keyed.value)),
+            None if *this.stream_done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut this = self.project();
+
+        if !*this.stream_done && this.stream.as_mut().poll_cancel(cx).is_ready() {
+            *this.stream_done = true;
+            *this.stream_busy = false;
+        }
+        let in_flight_done = Pin::new(&mut *this.in_flight).poll_cancel(cx).is_ready();
+        this.buffer.clear();
+
+        if *this.stream_done && in_flight_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.in_flight.len() + self.buffer.len();
+        if self.stream_done {
+            (pending, Some(pending))
+        } else {
+            let (lower, upper) = self.stream.size_hint();
+            (
+                lower.saturating_add(pending),
+                upper.and_then(|upper| upper.checked_add(pending)),
+            )
+        }
+    }
+}
+
+impl<S, K, F> Stream for BufferedByKey<S, K, F>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    <S as CompletionStream>::Item: CompletionFuture,
+    K: Ord,
+    F: FnMut(&<<S as CompletionStream>::Item as CompletionFuture>::Output) -> K,
+{
+    type Item = <Self as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::task::Poll;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn reassembles_sequence() {
+        // Each packet is out of place by fewer than the limit, and takes a different amount of
+        // time to arrive.
+        let packets = [2, 0, 1, 5, 3, 4, 8, 6, 7];
+        let stream = stream::iter(packets.iter().copied())
+            .into_completion()
+            .map(|seq| Yield::new((seq * 7) % 4, ready(seq)));
+        let v: Vec<_> = block_on(stream.buffered_by_key(3, |&seq| seq).collect());
+        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn waits_for_busy_source() {
+        // The source yields its first future immediately, but takes a poll to yield the second.
+        let source = stream::iter(vec![0, 1])
+            .into_completion()
+            .then(|x| Yield::new(x, ready(ready(x))));
+        let mut stream = source.buffered_by_key(2, |&x| x);
+
+        let cx = &mut crate::noop_cx();
+        // The first output is ready, but the source is in the middle of yielding the next future.
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert!(stream.stream_busy);
+        assert_eq!(
+            unsafe { CompletionStreamExt::poll_next(&mut stream, cx) },
+            Poll::Ready(Some(0))
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let (fut, counts) = Yield::new(5, ready(0)).count_polls();
+        let mut stream = stream::iter(Some(fut))
+            .into_completion()
+            .buffered_by_key(2, |&x| x);
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        while unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_pending() {}
+        assert_ne!(counts.cancels(), 0);
+        assert!(stream.in_flight.is_empty());
+    }
+}
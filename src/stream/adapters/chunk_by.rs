@@ -0,0 +1,97 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::chunk_by`](crate::CompletionStreamExt::chunk_by).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[derive(Debug, Clone)]
+    pub struct ChunkBy<S: CompletionStream, F, K> {
+        #[pin]
+        stream: S,
+        f: F,
+        // The group currently being built up, and the key its items share.
+        group: Vec<S::Item>,
+        key: Option<K>,
+    }
+}
+
+impl<S: CompletionStream, F, K> ChunkBy<S, F, K> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            group: Vec::new(),
+            key: None,
+        }
+    }
+}
+
+impl<S, F, K> CompletionStream for ChunkBy<S, F, K>
+where
+    S: CompletionStream,
+    F: FnMut(&S::Item) -> K,
+    K: PartialEq,
+{
+    type Item = Vec<S::Item>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(item) = ready!(this.stream.as_mut().poll_next(cx)) {
+                let key = (this.f)(&item);
+                if this.key.is_some() && this.key.as_ref() != Some(&key) {
+                    *this.key = Some(key);
+                    break Poll::Ready(Some(mem::replace(this.group, vec![item])));
+                }
+                *this.key = Some(key);
+                this.group.push(item);
+            } else {
+                *this.key = None;
+                break Poll::Ready(if this.group.is_empty() {
+                    None
+                } else {
+                    Some(mem::take(this.group))
+                });
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        ready!(this.stream.poll_cancel(cx));
+        this.group.clear();
+        *this.key = None;
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        let buffered = usize::from(!self.group.is_empty());
+        (
+            usize::from(buffered > 0 || lower > 0),
+            upper.and_then(|upper| upper.checked_add(buffered)),
+        )
+    }
+}
+
+impl<S, F, K> Stream for ChunkBy<S, F, K>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    F: FnMut(&<S as CompletionStream>::Item) -> K,
+    K: PartialEq,
+{
+    type Item = Vec<<S as CompletionStream>::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
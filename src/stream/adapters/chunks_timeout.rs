@@ -0,0 +1,274 @@
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::Duration;
+use std::vec::Vec;
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{sleep, Sleep};
+
+pin_project! {
+    /// Stream for
+    /// [`CompletionStreamExt::chunks_timeout`](crate::CompletionStreamExt::chunks_timeout).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub struct ChunksTimeout<S: CompletionStream> {
+        #[pin]
+        stream: S,
+        items: Vec<S::Item>,
+        size: usize,
+        duration: Duration,
+        // The timer for the current chunk, started when its first item arrives.
+        timer: Option<Sleep>,
+        // Whether the inner stream has been exhausted or cancelled.
+        done: bool,
+    }
+}
+
+impl<S: CompletionStream> ChunksTimeout<S> {
+    pub(crate) fn new(stream: S, size: usize, duration: Duration) -> Self {
+        assert_ne!(size, 0, "`chunks_timeout` size must be non-zero");
+        Self {
+            stream,
+            items: Vec::with_capacity(size),
+            size,
+            duration,
+            timer: None,
+            done: false,
+        }
+    }
+}
+
+impl<S: CompletionStream + Debug> Debug for ChunksTimeout<S>
+where
+    S::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksTimeout")
+            .field("stream", &self.stream)
+            .field("items", &self.items)
+            .field("size", &self.size)
+            .field("duration", &self.duration)
+            .field("timer", &self.timer)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<S> CompletionStream for ChunksTimeout<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+{
+    type Item = Vec<<S as CompletionStream>::Item>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let size = *this.size;
+        let take = |items: &mut Vec<_>| mem::replace(items, Vec::with_capacity(size));
+
+        loop {
+            match CompletionStream::poll_next(this.stream.as_mut(), cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.items.is_empty() {
+                        *this.timer = Some(sleep(*this.duration));
+                    }
+                    this.items.push(item);
+                    if this.items.len() >= size {
+                        *this.timer = None;
+                        return Poll::Ready(Some(take(this.items)));
+                    }
+                }
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    *this.timer = None;
+                    if this.items.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(mem::take(this.items)));
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // The inner stream can be dropped at any time, so the chunk can be yielded even though it
+        // is in the middle of yielding an item.
+        if let Some(timer) = this.timer {
+            if Future::poll(Pin::new(timer), cx).is_ready() {
+                *this.timer = None;
+                return Poll::Ready(Some(take(this.items)));
+            }
+        }
+        Poll::Pending
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        *this.timer = None;
+        this.items.clear();
+        if !*this.done {
+            ready!(CompletionStream::poll_cancel(this.stream, cx));
+            *this.done = true;
+        }
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // Timeouts can split the items into chunks of any size.
+        let (lower, upper) = CompletionStream::size_hint(&self.stream);
+        let lower = lower.saturating_add(self.items.len());
+        (
+            lower / self.size + usize::from(lower % self.size != 0),
+            upper.and_then(|upper| upper.checked_add(self.items.len())),
+        )
+    }
+}
+
+impl<S> Stream for ChunksTimeout<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+{
+    type Item = Vec<<S as CompletionStream>::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+    use std::vec::IntoIter;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    /// A stream that yields some items, then never yields again, recording whether it was
+    /// cancelled.
+    struct ThenPending {
+        items: IntoIter<u32>,
+        cancelled: bool,
+    }
+
+    impl CompletionStream for ThenPending {
+        type Item = u32;
+
+        unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            Stream::poll_next(self, cx)
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.get_mut().cancelled = true;
+            Poll::Ready(())
+        }
+    }
+
+    impl Stream for ThenPending {
+        type Item = u32;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            match self.get_mut().items.next() {
+                Some(item) => Poll::Ready(Some(item)),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    #[test]
+    fn flush_on_count() {
+        let stream = stream::iter(0..5)
+            .into_completion()
+            .chunks_timeout(2, Duration::from_secs(30));
+        assert_eq!(CompletionStream::size_hint(&stream), (3, Some(5)));
+
+        let chunks: Vec<_> = block_on(stream.collect());
+        assert_eq!(chunks, [vec![0, 1], vec![2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn flush_on_timeout() {
+        let duration = Duration::from_millis(20);
+        let late = stream::once_future(Box::pin(async {
+            sleep(Duration::from_millis(200)).await;
+            3
+        }));
+        let mut stream = stream::StreamExt::chain(stream::iter(vec![1, 2]), late)
+            .into_completion()
+            .chunks_timeout(10, duration);
+
+        // The chunk is flushed on time, without waiting for the item the inner stream is in the
+        // middle of.
+        let start = Instant::now();
+        assert_eq!(block_on(stream.next()), Some(vec![1, 2]));
+        let elapsed = start.elapsed();
+        assert!(elapsed >= duration && elapsed < Duration::from_millis(200));
+
+        assert_eq!(block_on(stream.next()), Some(vec![3]));
+        assert_eq!(block_on(stream.next()), None);
+    }
+
+    #[test]
+    fn flush_during_pending_gap() {
+        let mut stream = ThenPending {
+            items: vec![1, 2].into_iter(),
+            cancelled: false,
+        }
+        .chunks_timeout(10, Duration::from_millis(1));
+
+        // The inner stream never yields again, but the partial chunk is still yielded.
+        assert_eq!(block_on(stream.next()), Some(vec![1, 2]));
+        assert!(stream.timer.is_none());
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+        assert!(stream.stream.cancelled);
+    }
+
+    #[test]
+    fn no_items_no_timer() {
+        let mut stream = ThenPending {
+            items: Vec::new().into_iter(),
+            cancelled: false,
+        }
+        .chunks_timeout(10, Duration::from_millis(1));
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert!(stream.timer.is_none());
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+    }
+
+    #[test]
+    fn cancel() {
+        let mut stream = ThenPending {
+            items: vec![1].into_iter(),
+            cancelled: false,
+        }
+        .chunks_timeout(10, Duration::from_secs(30));
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert!(stream.timer.is_some());
+
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+        assert!(stream.timer.is_none());
+        assert!(stream.items.is_empty());
+        assert!(stream.stream.cancelled);
+    }
+}
@@ -0,0 +1,240 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::combine_latest`](crate::CompletionStreamExt::combine_latest).
+    #[derive(Debug, Clone)]
+    pub struct CombineLatest<A: CompletionStream, B: CompletionStream> {
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+        a_side: Side<A::Item>,
+        b_side: Side<B::Item>,
+        // Whether to poll `b` before `a`. This flips every time one of them yields an item, so
+        // that neither stream can starve the other.
+        b_first: bool,
+    }
+}
+
+/// The state of one of the streams in a `CombineLatest`.
+#[derive(Debug, Clone)]
+struct Side<T> {
+    state: State,
+    /// The latest item yielded by the stream.
+    latest: Option<T>,
+    /// Whether `latest` hasn't been yielded in a pair yet.
+    new: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// The stream is not in the middle of yielding an item.
+    Idle,
+    /// The stream is in the middle of yielding an item.
+    Polling,
+    /// The stream is exhausted or cancelled.
+    Done,
+}
+
+impl<A: CompletionStream, B: CompletionStream> CombineLatest<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            a_side: Side::new(),
+            b_side: Side::new(),
+            b_first: false,
+        }
+    }
+}
+
+impl<T> Side<T> {
+    fn new() -> Self {
+        Self {
+            state: State::Idle,
+            latest: None,
+            new: false,
+        }
+    }
+
+    /// Poll the stream, returning whether it yielded an item.
+    unsafe fn poll<S>(&mut self, stream: Pin<&mut S>, cx: &mut Context<'_>) -> bool
+    where
+        S: CompletionStream<Item = T>,
+    {
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                self.state = State::Idle;
+                self.latest = Some(item);
+                self.new = true;
+                true
+            }
+            Poll::Ready(None) => {
+                self.state = State::Done;
+                false
+            }
+            Poll::Pending => {
+                self.state = State::Polling;
+                false
+            }
+        }
+    }
+
+    /// Cancel the stream, returning whether it has finished cancelling.
+    unsafe fn poll_cancel<S: CompletionStream>(
+        &mut self,
+        stream: Pin<&mut S>,
+        cx: &mut Context<'_>,
+    ) -> bool {
+        if self.state != State::Done {
+            if stream.poll_cancel(cx).is_pending() {
+                return false;
+            }
+            self.state = State::Done;
+        }
+        self.latest = None;
+        true
+    }
+
+    /// Whether the stream will never contribute to another pair.
+    fn is_dead(&self) -> bool {
+        self.state == State::Done && self.latest.is_none()
+    }
+}
+
+impl<A, B> CompletionStream for CombineLatest<A, B>
+where
+    A: CompletionStream,
+    B: CompletionStream,
+    A::Item: Clone,
+    B::Item: Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // If one of the streams ended without yielding anything, there will never be a pair to
+            // yield so the other stream is cancelled.
+            if this.a_side.is_dead() || this.b_side.is_dead() {
+                let a_done = this.a_side.poll_cancel(this.a.as_mut(), cx);
+                let b_done = this.b_side.poll_cancel(this.b.as_mut(), cx);
+                return if a_done && b_done {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                };
+            }
+            if this.a_side.state == State::Done && this.b_side.state == State::Done {
+                return Poll::Ready(None);
+            }
+
+            for &poll_b in &[*this.b_first, !*this.b_first] {
+                // Don't start yielding a new item if we already have one to yield, but always
+                // continue yielding an item that has been started.
+                let has_new = this.a_side.new || this.b_side.new;
+                let yielded = if poll_b {
+                    let side = &mut *this.b_side;
+                    (side.state == State::Polling || side.state == State::Idle && !has_new)
+                        && side.poll(this.b.as_mut(), cx)
+                } else {
+                    let side = &mut *this.a_side;
+                    (side.state == State::Polling || side.state == State::Idle && !has_new)
+                        && side.poll(this.a.as_mut(), cx)
+                };
+                if yielded {
+                    *this.b_first = !poll_b;
+                }
+            }
+
+            // The streams must not be left in the middle of yielding an item.
+            if this.a_side.state == State::Polling || this.b_side.state == State::Polling {
+                return Poll::Pending;
+            }
+
+            if this.a_side.new || this.b_side.new {
+                this.a_side.new = false;
+                this.b_side.new = false;
+                if let (Some(a), Some(b)) = (&this.a_side.latest, &this.b_side.latest) {
+                    return Poll::Ready(Some((a.clone(), b.clone())));
+                }
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let a_done = this.a_side.poll_cancel(this.a, cx);
+        let b_done = this.b_side.poll_cancel(this.b, cx);
+        if a_done && b_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = |state, (_, upper): (usize, Option<usize>)| match state {
+            State::Idle | State::Polling => upper,
+            State::Done => Some(0),
+        };
+        let a_upper = remaining(self.a_side.state, self.a.size_hint());
+        let b_upper = remaining(self.b_side.state, self.b.size_hint());
+        let buffered = usize::from(self.a_side.new || self.b_side.new);
+        let upper = a_upper
+            .and_then(|a_upper| a_upper.checked_add(b_upper?))
+            .and_then(|upper| upper.checked_add(buffered));
+        (0, upper)
+    }
+}
+
+impl<A, B> Stream for CombineLatest<A, B>
+where
+    A: CompletionStream + Stream<Item = <A as CompletionStream>::Item>,
+    B: CompletionStream + Stream<Item = <B as CompletionStream>::Item>,
+    <A as CompletionStream>::Item: Clone,
+    <B as CompletionStream>::Item: Clone,
+{
+    type Item = <Self as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, ready};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn different_rates() {
+        let fast = stream::iter(0..6)
+            .into_completion()
+            .then(|item| Yield::new(1, ready(item)));
+        let slow = stream::iter(vec!["a", "b"])
+            .into_completion()
+            .then(|item| Yield::new(4, ready(item)));
+
+        // The fast stream isn't polled again while the slow stream is in the middle of yielding an
+        // item, so it can't run ahead until the slow stream is exhausted.
+        let pairs: Vec<_> = block_on(fast.combine_latest(slow).collect());
+        assert_eq!(
+            pairs,
+            [(0, "a"), (1, "b"), (2, "b"), (3, "b"), (4, "b"), (5, "b")]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! `Dedup` and `DedupBy`.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::dedup`](crate::CompletionStreamExt::dedup).
+    #[derive(Debug, Clone)]
+    pub struct Dedup<S: CompletionStream> {
+        #[pin]
+        inner: DedupBy<S, fn(&S::Item, &S::Item) -> bool>,
+    }
+}
+
+impl<S: CompletionStream> Dedup<S>
+where
+    S::Item: PartialEq,
+{
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            inner: DedupBy::new(stream, PartialEq::eq),
+        }
+    }
+}
+
+impl<S> CompletionStream for Dedup<S>
+where
+    S: CompletionStream,
+    S::Item: Clone,
+{
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().inner.poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S> Stream for Dedup<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    <S as CompletionStream>::Item: Clone,
+{
+    type Item = <Self as CompletionStream>::Item;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::dedup_by`](crate::CompletionStreamExt::dedup_by).
+    #[derive(Debug, Clone)]
+    pub struct DedupBy<S: CompletionStream, F> {
+        #[pin]
+        stream: S,
+        f: F,
+        // The last item yielded, kept to compare against the next one.
+        last: Option<S::Item>,
+    }
+}
+
+impl<S: CompletionStream, F> DedupBy<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            last: None,
+        }
+    }
+}
+
+impl<S, F> CompletionStream for DedupBy<S, F>
+where
+    S: CompletionStream,
+    S::Item: Clone,
+    F: FnMut(&S::Item, &S::Item) -> bool,
+{
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(item) = ready!(this.stream.as_mut().poll_next(cx)) {
+                if !matches!(this.last, Some(last) if (this.f)(last, &item)) {
+                    *this.last = Some(item.clone());
+                    break Poll::Ready(Some(item));
+                }
+            } else {
+                *this.last = None;
+                break Poll::Ready(None);
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        ready!(this.stream.poll_cancel(cx));
+        *this.last = None;
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        // All the remaining items could be duplicates of the last one.
+        (usize::from(self.last.is_none() && lower > 0), upper)
+    }
+}
+
+impl<S, F> Stream for DedupBy<S, F>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    <S as CompletionStream>::Item: Clone,
+    F: FnMut(&<S as CompletionStream>::Item, &<S as CompletionStream>::Item) -> bool,
+{
+    type Item = <Self as CompletionStream>::Item;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
@@ -6,7 +6,8 @@ use futures_core::{ready, Stream};
 use pin_project_lite::pin_project;
 
 pin_project! {
-    /// Stream for [`CompletionStreamExt::enumerate`](crate::CompletionStreamExt::enumerate).
+    /// Stream for [`CompletionStreamExt::enumerate`](crate::CompletionStreamExt::enumerate) and
+    /// [`CompletionStreamExt::enumerate_from`](crate::CompletionStreamExt::enumerate_from).
     #[derive(Debug, Clone)]
     pub struct Enumerate<S> {
         #[pin]
@@ -16,8 +17,11 @@ pin_project! {
 }
 
 impl<S> Enumerate<S> {
-    pub(crate) fn new(stream: S) -> Self {
-        Self { stream, count: 0 }
+    pub(crate) fn new(stream: S, start: usize) -> Self {
+        Self {
+            stream,
+            count: start,
+        }
     }
 }
 
@@ -58,3 +62,22 @@ where
         CompletionStream::size_hint(self)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn from_100() {
+        let stream = stream::iter("abc".chars())
+            .into_completion()
+            .enumerate_from(100);
+        let v: Vec<_> = block_on(stream.collect());
+        assert_eq!(v, [(100, 'a'), (101, 'b'), (102, 'c')]);
+    }
+}
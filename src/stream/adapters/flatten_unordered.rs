@@ -0,0 +1,272 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for
+    /// [`CompletionStreamExt::flatten_unordered`](crate::CompletionStreamExt::flatten_unordered).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub struct FlattenUnordered<S: CompletionStream>
+    where
+        S::Item: CompletionStream,
+    {
+        #[pin]
+        stream: S,
+        // The inner streams being drained, in the order their items will be yielded.
+        active: Vec<Child<S::Item>>,
+        limit: usize,
+        // Whether the outer stream is in the middle of yielding an item.
+        stream_busy: bool,
+        // Whether the outer stream has been exhausted or cancelled.
+        stream_done: bool,
+    }
+}
+
+/// An inner stream being drained by a `FlattenUnordered`.
+#[derive(Debug)]
+struct Child<S: CompletionStream> {
+    stream: Pin<Box<S>>,
+    // Whether the stream is in the middle of yielding an item.
+    busy: bool,
+    // An item the stream has yielded that hasn't been returned yet.
+    buffered: Option<S::Item>,
+}
+
+impl<S: CompletionStream> FlattenUnordered<S>
+where
+    S::Item: CompletionStream,
+{
+    pub(crate) fn new(stream: S, limit: usize) -> Self {
+        assert_ne!(limit, 0, "`flatten_unordered` limit must be non-zero");
+        Self {
+            stream,
+            active: Vec::new(),
+            limit,
+            stream_busy: false,
+            stream_done: false,
+        }
+    }
+}
+
+impl<S> Debug for FlattenUnordered<S>
+where
+    S: CompletionStream + Debug,
+    S::Item: CompletionStream + Debug,
+    <S::Item as CompletionStream>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlattenUnordered")
+            .field("stream", &self.stream)
+            .field("active", &self.active)
+            .field("limit", &self.limit)
+            .field("stream_busy", &self.stream_busy)
+            .field("stream_done", &self.stream_done)
+            .finish()
+    }
+}
+
+impl<S, U> CompletionStream for FlattenUnordered<S>
+where
+    S: CompletionStream<Item = U>,
+    U: CompletionStream,
+{
+    type Item = U::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            while !*this.stream_done && this.active.len() < *this.limit {
+                *this.stream_busy = false;
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(stream)) => this.active.push(Child {
+                        stream: Box::pin(stream),
+                        busy: false,
+                        buffered: None,
+                    }),
+                    Poll::Ready(None) => *this.stream_done = true,
+                    Poll::Pending => {
+                        *this.stream_busy = true;
+                        break;
+                    }
+                }
+            }
+
+            let mut i = 0;
+            while i < this.active.len() {
+                let child = &mut this.active[i];
+                if child.buffered.is_none() {
+                    child.busy = false;
+                    match child.stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(item)) => child.buffered = Some(item),
+                        Poll::Ready(None) => {
+                            this.active.remove(i);
+                            continue;
+                        }
+                        Poll::Pending => child.busy = true,
+                    }
+                }
+                i += 1;
+            }
+
+            // No stream may be left in the middle of yielding an item.
+            if *this.stream_busy || this.active.iter().any(|child| child.busy) {
+                return Poll::Pending;
+            }
+
+            if let Some(i) = this
+                .active
+                .iter()
+                .position(|child| child.buffered.is_some())
+            {
+                // Move the stream to the back so the others get a chance to yield first.
+                let mut child = this.active.remove(i);
+                let item = child.buffered.take();
+                this.active.push(child);
+                return Poll::Ready(item);
+            }
+
+            if *this.stream_done && this.active.is_empty() {
+                return Poll::Ready(None);
+            }
+            // Otherwise some inner streams were exhausted, so start new ones.
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut this = self.project();
+
+        if !*this.stream_done && this.stream.as_mut().poll_cancel(cx).is_ready() {
+            *this.stream_done = true;
+            *this.stream_busy = false;
+        }
+        let mut i = 0;
+        while i < this.active.len() {
+            if this.active[i].stream.as_mut().poll_cancel(cx).is_ready() {
+                this.active.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        if *this.stream_done && this.active.is_empty() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut lower: usize = 0;
+        let mut upper = Some(0_usize);
+        for child in &self.active {
+            let buffered = usize::from(child.buffered.is_some());
+            let (stream_lower, stream_upper) = child.stream.size_hint();
+            let stream_lower = stream_lower.saturating_add(buffered);
+            let stream_upper = stream_upper.and_then(|upper| upper.checked_add(buffered));
+            lower = lower.saturating_add(stream_lower);
+            upper = upper.and_then(|upper| upper.checked_add(stream_upper?));
+        }
+        if !self.stream_done && self.stream.size_hint().1 != Some(0) {
+            upper = None;
+        }
+        (lower, upper)
+    }
+}
+
+impl<S, U> Stream for FlattenUnordered<S>
+where
+    S: CompletionStream<Item = U> + Stream<Item = U>,
+    U: CompletionStream + Stream<Item = <U as CompletionStream>::Item>,
+{
+    type Item = <Self as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::vec;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, ready};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn interleaves() {
+        let streams = vec![
+            stream::iter(vec![0, 1, 2]).into_completion(),
+            stream::iter(vec![10]).into_completion(),
+            stream::iter(vec![20, 21]).into_completion(),
+        ];
+        let stream = stream::iter(streams).into_completion().flatten_unordered(2);
+        let items: Vec<_> = block_on(stream.collect());
+
+        // The third stream is only started once the second one has finished.
+        assert_eq!(items, [0, 10, 1, 2, 20, 21]);
+    }
+
+    #[test]
+    fn waits_for_busy_streams() {
+        // A stream that yields one item after being pending `times` times.
+        let delayed = |item: i32, times: usize| {
+            stream::iter(vec![item])
+                .into_completion()
+                .then(move |item| Yield::new(times, ready(item)))
+        };
+        let (fast, slow) = (delayed(0, 0), delayed(10, 2));
+        let mut stream = stream::iter(vec![fast, slow])
+            .into_completion()
+            .flatten_unordered(2);
+
+        let cx = &mut crate::noop_cx();
+        let mut poll = || unsafe { CompletionStreamExt::poll_next(&mut stream, cx) };
+
+        // The first stream's item is held until the second stream has finished its item.
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(poll(), Poll::Ready(Some(0)));
+        assert_eq!(poll(), Poll::Ready(Some(10)));
+        assert_eq!(poll(), Poll::Ready(None));
+    }
+
+    #[test]
+    fn cancel() {
+        let streams = vec![
+            stream::iter(vec![0, 1]).into_completion(),
+            stream::iter(vec![10, 11]).into_completion(),
+        ];
+        let mut stream = stream::iter(streams).into_completion().flatten_unordered(2);
+
+        let cx = &mut crate::noop_cx();
+        assert_eq!(
+            unsafe { CompletionStreamExt::poll_next(&mut stream, cx) },
+            Poll::Ready(Some(0))
+        );
+        assert_eq!(stream.active.len(), 2);
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+        assert!(stream.active.is_empty());
+        assert!(stream.stream_done);
+    }
+
+    #[test]
+    #[should_panic = "`flatten_unordered` limit must be non-zero"]
+    fn zero_limit() {
+        let streams = vec![stream::empty::<()>().into_completion()];
+        let _ = stream::iter(streams).into_completion().flatten_unordered(0);
+    }
+}
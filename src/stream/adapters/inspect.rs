@@ -59,3 +59,139 @@ where
         CompletionStream::size_hint(self)
     }
 }
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::inspect_ok`](crate::CompletionStreamExt::inspect_ok).
+    #[derive(Debug, Clone)]
+    pub struct InspectOk<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+    }
+}
+
+impl<S, F> InspectOk<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, F, T, E> CompletionStream for InspectOk<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+    F: FnMut(&T),
+{
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let val = ready!(this.stream.poll_next(cx));
+        if let Some(Ok(val)) = &val {
+            (this.f)(val);
+        }
+        Poll::Ready(val)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().stream.poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, F, T, E> Stream for InspectOk<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+    F: FnMut(&T),
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::inspect_err`](crate::CompletionStreamExt::inspect_err).
+    #[derive(Debug, Clone)]
+    pub struct InspectErr<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+    }
+}
+
+impl<S, F> InspectErr<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, F, T, E> CompletionStream for InspectErr<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+    F: FnMut(&E),
+{
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let val = ready!(this.stream.poll_next(cx));
+        if let Some(Err(val)) = &val {
+            (this.f)(val);
+        }
+        Poll::Ready(val)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().stream.poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, F, T, E> Stream for InspectErr<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+    F: FnMut(&E),
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn inspect_err_only_errors() {
+        let mut errors = Vec::new();
+        let mut oks = Vec::new();
+        let items: Vec<_> = block_on(
+            stream::iter(vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)])
+                .into_completion()
+                .inspect_err(|e| errors.push(*e))
+                .inspect_ok(|x| oks.push(*x))
+                .collect(),
+        );
+        assert_eq!(items, [Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)]);
+        assert_eq!(errors, ["a", "b"]);
+        assert_eq!(oks, [1, 2, 3]);
+    }
+}
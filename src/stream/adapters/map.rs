@@ -1,4 +1,4 @@
-//! `Map` and `Then`.
+//! `Map`, `MapOk`, `MapErr` and `Then`.
 
 use core::future::Future;
 use core::pin::Pin;
@@ -61,6 +61,112 @@ where
     }
 }
 
+pin_project! {
+    /// Stream for [`CompletionStreamExt::map_ok`](crate::CompletionStreamExt::map_ok).
+    #[derive(Debug, Clone)]
+    pub struct MapOk<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+    }
+}
+
+impl<S, F> MapOk<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, F, T, E, U> CompletionStream for MapOk<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+    F: FnMut(T) -> U,
+{
+    type Item = Result<U, E>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        this.stream
+            .as_mut()
+            .poll_next(cx)
+            .map(|value| value.map(|res| res.map(&mut *this.f)))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().stream.poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, F, T, E, U> Stream for MapOk<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> U,
+{
+    type Item = Result<U, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(&self.stream)
+    }
+}
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::map_err`](crate::CompletionStreamExt::map_err).
+    #[derive(Debug, Clone)]
+    pub struct MapErr<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+    }
+}
+
+impl<S, F> MapErr<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, F, T, E, E2> CompletionStream for MapErr<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+    F: FnMut(E) -> E2,
+{
+    type Item = Result<T, E2>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        this.stream
+            .as_mut()
+            .poll_next(cx)
+            .map(|value| value.map(|res| res.map_err(&mut *this.f)))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().stream.poll_cancel(cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, F, T, E, E2> Stream for MapErr<S, F>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+    F: FnMut(E) -> E2,
+{
+    type Item = Result<T, E2>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(&self.stream)
+    }
+}
+
 pin_project! {
     /// Stream for [`CompletionStreamExt::then`](crate::CompletionStreamExt::then).
     #[derive(Debug, Clone)]
@@ -113,7 +219,9 @@ where
     unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
         let mut this = self.project();
         if let Some(fut) = this.fut.as_mut().as_pin_mut() {
-            fut.poll_cancel(cx)
+            ready!(fut.poll_cancel(cx));
+            this.fut.set(None);
+            Poll::Ready(())
         } else {
             this.stream.poll_cancel(cx)
         }
@@ -144,3 +252,97 @@ where
         CompletionStream::size_hint(self)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, CompletionFutureExt, FutureExt};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn map_ok_and_map_err() {
+        let items = vec![Ok(1), Err("a"), Ok(2), Err("bc"), Ok(3)];
+
+        let mut ok_calls = 0;
+        let mapped: Vec<_> = block_on(
+            stream::iter(items.clone())
+                .into_completion()
+                .map_ok(|x| {
+                    ok_calls += 1;
+                    x * 10
+                })
+                .collect(),
+        );
+        assert_eq!(mapped, [Ok(10), Err("a"), Ok(20), Err("bc"), Ok(30)]);
+        assert_eq!(ok_calls, 3);
+
+        let mut err_calls = 0;
+        let mapped: Vec<_> = block_on(
+            stream::iter(items)
+                .into_completion()
+                .map_err(|e| {
+                    err_calls += 1;
+                    e.len()
+                })
+                .collect(),
+        );
+        assert_eq!(mapped, [Ok(1), Err(1), Ok(2), Err(2), Ok(3)]);
+        assert_eq!(err_calls, 2);
+    }
+
+    #[test]
+    fn then_sequential() {
+        let log = RefCell::new(Vec::new());
+        let stream = stream::iter(0..3).into_completion().then(|i| {
+            log.borrow_mut().push(("start", i));
+            let log = &log;
+            Yield::new(
+                2,
+                async move {
+                    log.borrow_mut().push(("end", i));
+                    i * 10
+                }
+                .into_completion(),
+            )
+        });
+
+        let items: Vec<_> = block_on(stream.collect());
+        assert_eq!(items, [0, 10, 20]);
+        assert_eq!(
+            *log.borrow(),
+            [
+                ("start", 0),
+                ("end", 0),
+                ("start", 1),
+                ("end", 1),
+                ("start", 2),
+                ("end", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn then_cancel() {
+        let (fut, counts) = Yield::once(async { 1 }.into_completion()).count_polls();
+        let mut fut = Some(fut);
+        let stream = stream::iter(0..2)
+            .into_completion()
+            .then(|_| fut.take().unwrap());
+        futures_lite::pin!(stream);
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            assert!(CompletionStream::poll_next(stream.as_mut(), cx).is_pending());
+            assert!(CompletionStream::poll_cancel(stream.as_mut(), cx).is_ready());
+        }
+        assert_eq!(counts.cancels(), 1);
+        assert!(stream.fut.is_none());
+    }
+}
@@ -10,6 +10,24 @@ pub use map::*;
 mod filter;
 pub use filter::*;
 
+mod dedup;
+pub use dedup::*;
+
+#[cfg(feature = "alloc")]
+mod chunk_by;
+#[cfg(feature = "alloc")]
+pub use chunk_by::*;
+
+#[cfg(feature = "alloc")]
+mod windows;
+#[cfg(feature = "alloc")]
+pub use windows::*;
+
+#[cfg(feature = "alloc")]
+mod rechunk;
+#[cfg(feature = "alloc")]
+pub use rechunk::*;
+
 mod enumerate;
 pub use enumerate::*;
 
@@ -19,18 +37,58 @@ pub use peekable::*;
 mod flatten;
 pub use flatten::*;
 
+#[cfg(feature = "alloc")]
+mod flatten_unordered;
+#[cfg(feature = "alloc")]
+pub use flatten_unordered::*;
+
+#[cfg(feature = "alloc")]
+mod buffered_by_key;
+#[cfg(feature = "alloc")]
+pub use buffered_by_key::*;
+
 mod skip_take_while;
 pub use skip_take_while::*;
 
 mod skip_take;
 pub use skip_take::*;
 
+mod take_until;
+pub use take_until::*;
+
+mod scan_async;
+pub use scan_async::*;
+
+#[cfg(feature = "std")]
+mod timeout_total;
+#[cfg(feature = "std")]
+pub use timeout_total::*;
+
+#[cfg(feature = "std")]
+mod chunks_timeout;
+#[cfg(feature = "std")]
+pub use chunks_timeout::*;
+
+#[cfg(feature = "std")]
+mod rate_limited;
+#[cfg(feature = "std")]
+pub use rate_limited::*;
+
+mod combine_latest;
+pub use combine_latest::*;
+
 mod fuse;
 pub use fuse::*;
 
 mod inspect;
 pub use inspect::*;
 
+mod split_at_err;
+pub use split_at_err::*;
+
+mod tolerate_errors;
+pub use tolerate_errors::*;
+
 mod cloned;
 pub use cloned::*;
 
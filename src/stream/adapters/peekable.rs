@@ -104,6 +104,105 @@ impl<S: CompletionStream> Peekable<S> {
         }
     }
 
+    /// Consume and return the next value in the stream if it satisfies the predicate.
+    ///
+    /// If the next value doesn't satisfy the predicate, or the stream has ended, the returned
+    /// future outputs `None` and the value remains peeked.
+    ///
+    /// # Cancellation
+    ///
+    /// This has the same cancellation behaviour as [`peek`](Self::peek).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::{stream, pin};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter("123+4".chars()).into_completion().peekable();
+    /// pin!(stream);
+    ///
+    /// let mut digits = String::new();
+    /// while let Some(digit) = stream.as_mut().next_if(char::is_ascii_digit).await {
+    ///     digits.push(digit);
+    /// }
+    /// assert_eq!(digits, "123");
+    /// assert_eq!(stream.next().await, Some('+'));
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn next_if<F>(self: Pin<&mut Self>, f: F) -> NextIf<'_, S, F>
+    where
+        F: FnOnce(&S::Item) -> bool,
+    {
+        NextIf {
+            stream: Some(self),
+            f: Some(f),
+        }
+    }
+
+    /// Consume and return the next value in the stream if it is equal to `expected`.
+    ///
+    /// If the next value isn't equal to `expected`, or the stream has ended, the returned future
+    /// outputs `None` and the value remains peeked.
+    ///
+    /// # Cancellation
+    ///
+    /// This has the same cancellation behaviour as [`peek`](Self::peek).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::{stream, pin};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec![0, 1, 2]).into_completion().peekable();
+    /// pin!(stream);
+    ///
+    /// assert_eq!(stream.as_mut().next_if_eq(&0).await, Some(0));
+    /// assert_eq!(stream.as_mut().next_if_eq(&0).await, None);
+    /// assert_eq!(stream.next().await, Some(1));
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn next_if_eq<'a, T>(self: Pin<&'a mut Self>, expected: &'a T) -> NextIfEq<'a, S, T>
+    where
+        T: ?Sized,
+        S::Item: PartialEq<T>,
+    {
+        NextIfEq {
+            stream: Some(self),
+            expected,
+        }
+    }
+
+    /// Attempt to consume the next value in the stream if it satisfies the predicate.
+    ///
+    /// # Safety
+    ///
+    /// See [`CompletionStream::poll_next`].
+    unsafe fn poll_next_if(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        f: impl FnOnce(&S::Item) -> bool,
+    ) -> Poll<Option<S::Item>> {
+        ready!(self.as_mut().poll_peek(cx));
+
+        let this = self.project();
+        match mem::replace(this.peeked, Poll::Pending) {
+            Poll::Ready(Some(item)) => {
+                if f(&item) {
+                    return Poll::Ready(Some(item));
+                }
+                *this.peeked = Poll::Ready(Some(item));
+            }
+            peeked => *this.peeked = peeked,
+        }
+        Poll::Ready(None)
+    }
+
     /// Attempt to cancel peeking the next value in the stream.
     ///
     /// This will cancel the underlying stream if the next value in the stream has not already been
@@ -216,3 +315,138 @@ where
         unsafe { CompletionFuture::poll(self, cx) }
     }
 }
+
+/// Future for [`Peekable::next_if`].
+pub struct NextIf<'a, S: CompletionStream, F> {
+    stream: Option<Pin<&'a mut Peekable<S>>>,
+    f: Option<F>,
+}
+
+impl<S: CompletionStream, F> Unpin for NextIf<'_, S, F> {}
+
+impl<S: CompletionStream + Debug, F> Debug for NextIf<'_, S, F>
+where
+    S::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextIf")
+            .field("stream", &self.stream)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F> CompletionFuture for NextIf<'_, S, F>
+where
+    S: CompletionStream,
+    F: FnOnce(&S::Item) -> bool,
+{
+    type Output = Option<S::Item>;
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let stream = this.stream.as_mut().expect("polled after completion");
+        ready!(stream.as_mut().poll_peek(cx));
+        let f = this.f.take().unwrap();
+        this.stream.take().unwrap().poll_next_if(cx, f)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let stream = self
+            .get_mut()
+            .stream
+            .as_mut()
+            .expect("polled after completion");
+        stream.as_mut().poll_peek_cancel(cx)
+    }
+}
+
+impl<S, F> Future for NextIf<'_, S, F>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    F: FnOnce(&<S as CompletionStream>::Item) -> bool,
+{
+    type Output = <Self as CompletionFuture>::Output;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+/// Future for [`Peekable::next_if_eq`].
+pub struct NextIfEq<'a, S: CompletionStream, T: ?Sized> {
+    stream: Option<Pin<&'a mut Peekable<S>>>,
+    expected: &'a T,
+}
+
+impl<S: CompletionStream + Debug, T: Debug + ?Sized> Debug for NextIfEq<'_, S, T>
+where
+    S::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextIfEq")
+            .field("stream", &self.stream)
+            .field("expected", &self.expected)
+            .finish()
+    }
+}
+
+impl<S, T> CompletionFuture for NextIfEq<'_, S, T>
+where
+    S: CompletionStream,
+    S::Item: PartialEq<T>,
+    T: ?Sized,
+{
+    type Output = Option<S::Item>;
+    unsafe fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.as_mut().expect("polled after completion");
+        ready!(stream.as_mut().poll_peek(cx));
+        let expected = self.expected;
+        self.stream
+            .take()
+            .unwrap()
+            .poll_next_if(cx, |item| item == expected)
+    }
+    unsafe fn poll_cancel(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let stream = self.stream.as_mut().expect("polled after completion");
+        stream.as_mut().poll_peek_cancel(cx)
+    }
+}
+
+impl<S, T> Future for NextIfEq<'_, S, T>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    <S as CompletionStream>::Item: PartialEq<T>,
+    T: ?Sized,
+{
+    type Output = <Self as CompletionFuture>::Output;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn next_if() {
+        let stream = stream::iter(vec![1, 2, 5, 6]).into_completion().peekable();
+        futures_lite::pin!(stream);
+
+        // Consumed if it matches.
+        assert_eq!(block_on(stream.as_mut().next_if(|&x| x < 3)), Some(1));
+        assert_eq!(block_on(stream.as_mut().next_if(|&x| x < 3)), Some(2));
+
+        // Left peekable if not.
+        assert_eq!(block_on(stream.as_mut().next_if(|&x| x < 3)), None);
+        assert_eq!(block_on(stream.as_mut().peek()), Some(&5));
+
+        assert_eq!(block_on(stream.as_mut().next_if_eq(&6)), None);
+        assert_eq!(block_on(stream.as_mut().next_if_eq(&5)), Some(5));
+        assert_eq!(block_on(stream.as_mut().next_if_eq(&6)), Some(6));
+
+        // The end of the stream is never consumed.
+        assert_eq!(block_on(stream.as_mut().next_if(|_| true)), None);
+        assert_eq!(block_on(stream.next()), None);
+    }
+}
@@ -0,0 +1,185 @@
+use core::cmp;
+use core::convert::TryFrom;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{sleep, Sleep};
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::rate_limited`](crate::CompletionStreamExt::rate_limited).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[derive(Debug)]
+    pub struct RateLimited<S> {
+        #[pin]
+        stream: S,
+        // The time it takes for a single token to be added to the bucket.
+        interval: Duration,
+        burst: u32,
+        tokens: u32,
+        // The point in time from which the next token is being accumulated.
+        last_refill: Instant,
+        // The timer waiting for the next token, if the bucket is empty.
+        wait: Option<Sleep>,
+        // Whether the stream is in the middle of yielding an item.
+        stream_busy: bool,
+    }
+}
+
+impl<S> RateLimited<S> {
+    pub(crate) fn new(stream: S, rate: u32, burst: u32) -> Self {
+        assert_ne!(rate, 0, "rate must be non-zero");
+        assert_ne!(burst, 0, "burst must be non-zero");
+
+        Self {
+            stream,
+            // Rates above one per nanosecond would make the interval zero.
+            interval: cmp::max(Duration::from_secs(1) / rate, Duration::from_nanos(1)),
+            burst,
+            tokens: burst,
+            last_refill: Instant::now(),
+            wait: None,
+            stream_busy: false,
+        }
+    }
+}
+
+impl<S: CompletionStream> CompletionStream for RateLimited<S> {
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        loop {
+            if let Some(wait) = this.wait {
+                ready!(Future::poll(Pin::new(wait), cx));
+                *this.wait = None;
+            }
+
+            // Add a token for every whole interval that has passed, keeping the remainder
+            // towards the next one.
+            let now = Instant::now();
+            let new_tokens = u32::try_from(
+                now.duration_since(*this.last_refill).as_nanos() / this.interval.as_nanos(),
+            )
+            .unwrap_or(u32::MAX);
+            if new_tokens != 0 {
+                *this.tokens = cmp::min(this.tokens.saturating_add(new_tokens), *this.burst);
+                *this.last_refill += *this.interval * new_tokens;
+            }
+
+            if *this.tokens != 0 {
+                break;
+            }
+            let deadline = *this.last_refill + *this.interval;
+            *this.wait = Some(sleep(deadline.saturating_duration_since(now)));
+        }
+
+        *this.stream_busy = true;
+        let item = ready!(this.stream.poll_next(cx));
+        *this.stream_busy = false;
+
+        if item.is_some() {
+            *this.tokens -= 1;
+        }
+        Poll::Ready(item)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if let Some(wait) = this.wait {
+            ready!(CompletionFuture::poll_cancel(Pin::new(wait), cx));
+            *this.wait = None;
+        }
+        if *this.stream_busy {
+            ready!(this.stream.poll_cancel(cx));
+            *this.stream_busy = false;
+        }
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S> Stream for RateLimited<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+{
+    type Item = <S as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn burst() {
+        let start = Instant::now();
+        let stream = stream::iter(0..5)
+            .into_completion()
+            .rate_limited(4, 3)
+            .map(|i| (i, start.elapsed()));
+        let items: Vec<_> = block_on(stream.collect());
+
+        let interval = Duration::from_millis(250);
+        assert_eq!(items.len(), 5);
+        for &(_, elapsed) in &items[..3] {
+            assert!(elapsed < interval);
+        }
+        // The last two items are each delayed by about an interval.
+        assert!(items[3].1 >= interval && items[3].1 < interval * 2);
+        assert!(items[4].1 >= interval * 2 && items[4].1 < interval * 3);
+    }
+
+    #[test]
+    fn pause_refills_up_to_burst() {
+        let mut stream = stream::iter(0..4).into_completion().rate_limited(20, 2);
+        block_on(stream.next()).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+
+        // The bucket is full again, but holds no more than `burst` tokens.
+        let start = Instant::now();
+        block_on(stream.next()).unwrap();
+        block_on(stream.next()).unwrap();
+        block_on(stream.next()).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn full_bucket_does_not_refill() {
+        let mut stream = stream::iter(0..2).into_completion().rate_limited(20, 1);
+        std::thread::sleep(Duration::from_millis(200));
+
+        block_on(stream.next()).unwrap();
+        let start = Instant::now();
+        block_on(stream.next()).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn huge_rate() {
+        let stream = stream::iter(0..3)
+            .into_completion()
+            .rate_limited(u32::MAX, 1);
+        assert_eq!(block_on(stream.collect::<Vec<_>>()), [0, 1, 2]);
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::rechunk`](crate::CompletionStreamExt::rechunk).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[derive(Debug, Clone)]
+    pub struct Rechunk<S> {
+        #[pin]
+        stream: S,
+        size: usize,
+        // Bytes that have been received but not yet yielded.
+        buf: Vec<u8>,
+        // Whether the stream has ended.
+        done: bool,
+    }
+}
+
+impl<S> Rechunk<S> {
+    pub(crate) fn new(stream: S, size: usize) -> Self {
+        assert_ne!(size, 0, "`rechunk` size must be non-zero");
+        Self {
+            stream,
+            size,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<S: CompletionStream<Item = Vec<u8>>> CompletionStream for Rechunk<S> {
+    type Item = Vec<u8>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if this.buf.len() >= *this.size {
+                let rest = this.buf.split_off(*this.size);
+                break Poll::Ready(Some(mem::replace(this.buf, rest)));
+            }
+            if *this.done {
+                break Poll::Ready(if this.buf.is_empty() {
+                    None
+                } else {
+                    Some(mem::take(this.buf))
+                });
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(chunk) if this.buf.is_empty() => *this.buf = chunk,
+                Some(chunk) => this.buf.extend_from_slice(&chunk),
+                None => *this.done = true,
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if !*this.done {
+            ready!(this.stream.poll_cancel(cx));
+        }
+        *this.buf = Vec::new();
+        Poll::Ready(())
+    }
+}
+
+impl<S> Stream for Rechunk<S>
+where
+    S: CompletionStream<Item = Vec<u8>> + Stream<Item = Vec<u8>>,
+{
+    type Item = Vec<u8>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::task::Poll;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn uniform() {
+        let chunks = vec![b"abc".to_vec(), b"defgh".to_vec()];
+        let stream = stream::iter(chunks).into_completion().rechunk(2);
+        let v: Vec<_> = block_on(stream.collect());
+        assert_eq!(v, [b"ab", b"cd", b"ef", b"gh"]);
+    }
+
+    #[test]
+    fn smaller_final_piece() {
+        let chunks = vec![b"a".to_vec(), Vec::new(), b"bcdefgh".to_vec()];
+        let stream = stream::iter(chunks).into_completion().rechunk(3);
+        let v: Vec<_> = block_on(stream.collect());
+        assert_eq!(v, [&b"abc"[..], b"def", b"gh"]);
+    }
+
+    #[test]
+    fn cancel() {
+        let chunks =
+            stream::StreamExt::chain(stream::iter(vec![b"abc".to_vec()]), stream::pending());
+        let mut stream = chunks.into_completion().rechunk(2);
+
+        let cx = &mut crate::noop_cx();
+        let poll = unsafe { CompletionStreamExt::poll_next(&mut stream, cx) };
+        assert_eq!(poll, Poll::Ready(Some(b"ab".to_vec())));
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert_eq!(stream.buf, b"c");
+
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+        assert!(stream.buf.is_empty());
+    }
+}
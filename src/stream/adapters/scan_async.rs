@@ -0,0 +1,138 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::scan_async`](crate::CompletionStreamExt::scan_async).
+    #[derive(Debug, Clone)]
+    pub struct ScanAsync<S, St, F, Fut> {
+        #[pin]
+        stream: S,
+        // The state, or `None` once the scan has ended or been cancelled.
+        state: Option<St>,
+        f: F,
+        #[pin]
+        fut: Option<Fut>,
+    }
+}
+
+impl<S, St, F, Fut> ScanAsync<S, St, F, Fut> {
+    pub(crate) fn new(stream: S, init: St, f: F) -> Self {
+        Self {
+            stream,
+            state: Some(init),
+            f,
+            fut: None,
+        }
+    }
+}
+
+impl<S, St, F, Fut, T> CompletionStream for ScanAsync<S, St, F, Fut>
+where
+    S: CompletionStream,
+    F: FnMut(&mut St, S::Item) -> Fut,
+    Fut: CompletionFuture<Output = Option<T>>,
+{
+    type Item = T;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if this.fut.is_none() {
+            let item = match this.state {
+                Some(_) => ready!(this.stream.poll_next(cx)),
+                None => None,
+            };
+            if let (Some(state), Some(item)) = (this.state.as_mut(), item) {
+                this.fut.set(Some((this.f)(state, item)));
+            } else {
+                *this.state = None;
+                return Poll::Ready(None);
+            }
+        }
+
+        let output = ready!(this.fut.as_mut().as_pin_mut().unwrap().poll(cx));
+        this.fut.set(None);
+        if output.is_none() {
+            *this.state = None;
+        }
+        Poll::Ready(output)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut this = self.project();
+        if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+            ready!(fut.poll_cancel(cx));
+            this.fut.set(None);
+        }
+        ready!(this.stream.poll_cancel(cx));
+        *this.state = None;
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.state.is_none() {
+            return (0, Some(0));
+        }
+        let future_len = usize::from(self.fut.is_some());
+        let (_, stream_max) = self.stream.size_hint();
+        (0, stream_max.and_then(|l| l.checked_add(future_len)))
+    }
+}
+
+impl<S, St, F, Fut, T> Stream for ScanAsync<S, St, F, Fut>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    F: FnMut(&mut St, <S as CompletionStream>::Item) -> Fut,
+    Fut: CompletionFuture<Output = Option<T>> + Future<Output = Option<T>>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn stops_at_threshold() {
+        let stream = stream::iter(1..)
+            .into_completion()
+            .scan_async(0, |total, x| {
+                *total += x;
+                let total = *total;
+                Yield::once(ready(if total > 10 { None } else { Some(total) }))
+            });
+        let totals: Vec<_> = block_on(stream.collect());
+        assert_eq!(totals, [1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn cancel() {
+        let (fut, counts) = Yield::once(ready(Some(()))).count_polls();
+        let mut fut = Some(fut);
+        let mut stream = stream::iter(0..2)
+            .into_completion()
+            .scan_async((), |(), _| fut.take().unwrap());
+
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut stream, cx) }.is_pending());
+        assert!(unsafe { CompletionStreamExt::poll_cancel(&mut stream, cx) }.is_ready());
+        assert_eq!(counts.cancels(), 1);
+        assert!(stream.state.is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::split_at_err`](crate::CompletionStreamExt::split_at_err).
+    #[derive(Debug, Clone)]
+    pub struct SplitAtErr<S, E> {
+        #[pin]
+        stream: S,
+        // Whether the inner stream has ended or yielded an error.
+        done: bool,
+        error: Option<E>,
+    }
+}
+
+impl<S, E> SplitAtErr<S, E> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            done: false,
+            error: None,
+        }
+    }
+
+    /// Get a reference to the error that ended the stream, if there was one.
+    #[must_use]
+    pub fn error(&self) -> Option<&E> {
+        self.error.as_ref()
+    }
+
+    /// Consume the stream, returning the error that ended it if there was one.
+    ///
+    /// This returns `None` if the stream hasn't ended yet, or if it ended without an error.
+    #[must_use]
+    pub fn into_error(self) -> Option<E> {
+        self.error
+    }
+}
+
+impl<S, T, E> CompletionStream for SplitAtErr<S, E>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        match ready!(this.stream.poll_next(cx)) {
+            Some(Ok(item)) => Poll::Ready(Some(item)),
+            Some(Err(e)) => {
+                *this.done = true;
+                *this.error = Some(e);
+                Poll::Ready(None)
+            }
+            None => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if *this.done {
+            Poll::Ready(())
+        } else {
+            this.stream.poll_cancel(cx)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.stream.size_hint().1)
+        }
+    }
+}
+
+impl<S, T, E> Stream for SplitAtErr<S, E>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn errors_after_two() {
+        let mut stream = stream::iter(vec![Ok(1), Ok(2), Err("oh no"), Ok(3)])
+            .into_completion()
+            .split_at_err();
+
+        let items: Vec<_> = block_on((&mut stream).collect());
+        assert_eq!(items, [1, 2]);
+        assert_eq!(block_on(stream.next()), None);
+        assert_eq!(stream.error(), Some(&"oh no"));
+        assert_eq!(stream.into_error(), Some("oh no"));
+    }
+
+    #[test]
+    fn no_error() {
+        let mut stream = stream::iter(vec![Ok::<_, ()>(1), Ok(2)])
+            .into_completion()
+            .split_at_err();
+
+        let items: Vec<_> = block_on((&mut stream).collect());
+        assert_eq!(items, [1, 2]);
+        assert_eq!(stream.into_error(), None);
+    }
+}
@@ -69,3 +69,29 @@ where
         CompletionStream::size_hint(self)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn every_third() {
+        let stream = stream::iter(0..10).into_completion().step_by(3);
+        assert_eq!(CompletionStream::size_hint(&stream), (4, Some(4)));
+        let items: Vec<_> = block_on(stream.collect());
+        assert_eq!(items, [0, 3, 6, 9]);
+    }
+
+    #[test]
+    #[should_panic = "cannot step by zero"]
+    fn zero_step() {
+        let _ = stream::iter(0..10).into_completion().step_by(0);
+    }
+}
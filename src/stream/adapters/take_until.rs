@@ -0,0 +1,147 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::PollCancelFreeOnDrop;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::take_until`](crate::CompletionStreamExt::take_until).
+    #[derive(Debug, Clone)]
+    pub struct TakeUntil<S, F> {
+        #[pin]
+        stream: S,
+        // `None` once the future has completed or been cancelled.
+        #[pin]
+        fut: Option<F>,
+        state: State,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Both the stream and the future are running.
+    Running,
+    /// The future has completed and we are cancelling the stream.
+    Cancelling,
+    /// The stream has been exhausted and we are cancelling the future.
+    CancellingFuture,
+    /// Both the stream and the future are done.
+    Done,
+}
+
+impl<S, F> TakeUntil<S, F> {
+    pub(crate) fn new(stream: S, fut: F) -> Self {
+        Self {
+            stream,
+            fut: Some(fut),
+            state: State::Running,
+        }
+    }
+}
+
+impl<S: CompletionStream, F: PollCancelFreeOnDrop> CompletionStream for TakeUntil<S, F> {
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.state == State::Running {
+            if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+                if fut.poll(cx).is_ready() {
+                    this.fut.set(None);
+                    *this.state = State::Cancelling;
+                }
+            }
+        }
+
+        match this.state {
+            State::Running => {
+                let item = ready!(this.stream.poll_next(cx));
+                if item.is_some() {
+                    return Poll::Ready(item);
+                }
+                *this.state = State::CancellingFuture;
+            }
+            State::Cancelling => {
+                ready!(this.stream.poll_cancel(cx));
+                *this.state = State::Done;
+            }
+            State::CancellingFuture | State::Done => {}
+        }
+
+        if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+            ready!(fut.poll_cancel(cx));
+            this.fut.set(None);
+        }
+        *this.state = State::Done;
+        Poll::Ready(None)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut this = self.project();
+
+        if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+            ready!(fut.poll_cancel(cx));
+            this.fut.set(None);
+        }
+        if let State::Running | State::Cancelling = this.state {
+            *this.state = State::Cancelling;
+            ready!(this.stream.poll_cancel(cx));
+        }
+        *this.state = State::Done;
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.state == State::Running {
+            let (_, upper) = self.stream.size_hint();
+            (0, upper)
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<S, F> Stream for TakeUntil<S, F>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    F: PollCancelFreeOnDrop,
+{
+    type Item = <S as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, pending, ready, CompletionFutureExt};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn signal_fires_mid_stream() {
+        let (signal, counts) = Yield::new(3, ready(())).count_polls();
+        let stream = stream::iter(0..10).into_completion().take_until(signal);
+        assert_eq!(block_on(stream.collect::<Vec<_>>()), [0, 1, 2]);
+        assert_eq!(counts.polls(), 4);
+        assert_eq!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn stream_ends_first() {
+        let (signal, counts) = pending::<()>().count_polls();
+        let stream = stream::iter(0..3).into_completion().take_until(signal);
+        assert_eq!(block_on(stream.collect::<Vec<_>>()), [0, 1, 2]);
+        assert_eq!(counts.cancels(), 1);
+    }
+}
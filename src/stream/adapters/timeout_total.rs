@@ -0,0 +1,152 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::Duration;
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{sleep, Sleep};
+
+pin_project! {
+    /// Stream for
+    /// [`CompletionStreamExt::timeout_total`](crate::CompletionStreamExt::timeout_total).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[derive(Debug)]
+    pub struct TimeoutTotal<S> {
+        #[pin]
+        stream: S,
+        duration: Duration,
+        // The timer, started on the first poll.
+        timer: Option<Sleep>,
+        state: State,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Running,
+    /// The time is up and the inner stream is being cancelled.
+    Cancelling,
+    /// The inner stream is exhausted or has been cancelled.
+    Done,
+}
+
+impl<S> TimeoutTotal<S> {
+    pub(crate) fn new(stream: S, duration: Duration) -> Self {
+        Self {
+            stream,
+            duration,
+            timer: None,
+            state: State::Running,
+        }
+    }
+}
+
+impl<S: CompletionStream> CompletionStream for TimeoutTotal<S> {
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.state == State::Running {
+            let duration = *this.duration;
+            let timer = this.timer.get_or_insert_with(|| sleep(duration));
+            if Future::poll(Pin::new(timer), cx).is_ready() {
+                *this.state = State::Cancelling;
+            } else {
+                let item = ready!(this.stream.as_mut().poll_next(cx));
+                if item.is_none() {
+                    *this.state = State::Done;
+                }
+                return Poll::Ready(item);
+            }
+        }
+        if *this.state == State::Cancelling {
+            ready!(this.stream.poll_cancel(cx));
+            *this.state = State::Done;
+        }
+        Poll::Ready(None)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        *this.timer = None;
+        if *this.state != State::Done {
+            ready!(this.stream.poll_cancel(cx));
+            *this.state = State::Done;
+        }
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            State::Running => (0, self.stream.size_hint().1),
+            State::Cancelling | State::Done => (0, Some(0)),
+        }
+    }
+}
+
+impl<S> Stream for TimeoutTotal<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+{
+    type Item = <S as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    /// A stream that never yields, and records whether it was cancelled.
+    #[derive(Default)]
+    struct Forever {
+        cancelled: bool,
+    }
+
+    impl CompletionStream for Forever {
+        type Item = ();
+
+        unsafe fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<()>> {
+            assert!(!self.cancelled);
+            Poll::Pending
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.get_mut().cancelled = true;
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn cuts_off() {
+        let duration = Duration::from_millis(50);
+        let mut stream = Forever::default().timeout_total(duration);
+
+        let start = Instant::now();
+        assert_eq!(block_on(stream.next()), None);
+        assert!(start.elapsed() >= duration);
+        assert!(stream.stream.cancelled);
+        assert_eq!(block_on(stream.next()), None);
+    }
+
+    #[test]
+    fn ends_early() {
+        let stream = stream::iter(0..3)
+            .into_completion()
+            .timeout_total(Duration::from_secs(60));
+        assert_eq!(block_on(stream.count()), 3);
+    }
+}
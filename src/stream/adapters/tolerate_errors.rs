@@ -0,0 +1,132 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for
+    /// [`CompletionStreamExt::tolerate_errors`](crate::CompletionStreamExt::tolerate_errors).
+    #[derive(Debug, Clone)]
+    pub struct TolerateErrors<S, E> {
+        #[pin]
+        stream: S,
+        limit: usize,
+        // The number of errors in a row so far.
+        errors: usize,
+        // The error that ended the stream, stored while the inner stream is cancelled.
+        error: Option<E>,
+        // Whether the inner stream has ended or been cancelled.
+        done: bool,
+    }
+}
+
+impl<S, E> TolerateErrors<S, E> {
+    pub(crate) fn new(stream: S, limit: usize) -> Self {
+        assert_ne!(limit, 0, "`tolerate_errors` limit must be non-zero");
+        Self {
+            stream,
+            limit,
+            errors: 0,
+            error: None,
+            done: false,
+        }
+    }
+}
+
+impl<S, T, E> CompletionStream for TolerateErrors<S, E>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        while this.error.is_none() {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(item)) => {
+                    *this.errors = 0;
+                    return Poll::Ready(Some(Ok(item)));
+                }
+                Some(Err(e)) => {
+                    *this.errors += 1;
+                    if *this.errors == *this.limit {
+                        *this.error = Some(e);
+                    }
+                }
+                None => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+
+        ready!(this.stream.poll_cancel(cx));
+        *this.done = true;
+        Poll::Ready(this.error.take().map(Err))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if !*this.done {
+            ready!(this.stream.poll_cancel(cx));
+            *this.done = true;
+        }
+        *this.error = None;
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else if self.error.is_some() {
+            (1, Some(1))
+        } else {
+            (0, self.stream.size_hint().1)
+        }
+    }
+}
+
+impl<S, T, E> Stream for TolerateErrors<S, E>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn scattered_errors_survive() {
+        let items = vec![Ok(1), Err("a"), Ok(2), Err("b"), Err("c"), Ok(3)];
+        let stream = stream::iter(items).into_completion().tolerate_errors(3);
+        let v: Vec<_> = block_on(stream.collect());
+        assert_eq!(v, [Ok(1), Ok(2), Ok(3)]);
+    }
+
+    #[test]
+    fn consecutive_errors_stop() {
+        let items = vec![Ok(1), Err("a"), Err("b"), Err("c"), Ok(2)];
+        let stream = stream::iter(items).into_completion().tolerate_errors(3);
+        let v: Vec<_> = block_on(stream.collect());
+        assert_eq!(v, [Ok(1), Err("c")]);
+    }
+}
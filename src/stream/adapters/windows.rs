@@ -0,0 +1,89 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for [`CompletionStreamExt::windows`](crate::CompletionStreamExt::windows).
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[derive(Debug, Clone)]
+    pub struct Windows<S: CompletionStream> {
+        #[pin]
+        stream: S,
+        size: usize,
+        // The last `size` items of the stream.
+        buf: VecDeque<S::Item>,
+    }
+}
+
+impl<S: CompletionStream> Windows<S> {
+    pub(crate) fn new(stream: S, size: usize) -> Self {
+        assert_ne!(size, 0, "window size must be non-zero");
+        Self {
+            stream,
+            size,
+            buf: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<S> CompletionStream for Windows<S>
+where
+    S: CompletionStream,
+    S::Item: Clone,
+{
+    type Item = Vec<S::Item>;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(item) = ready!(this.stream.as_mut().poll_next(cx)) {
+                if this.buf.len() == *this.size {
+                    this.buf.pop_front();
+                }
+                this.buf.push_back(item);
+                if this.buf.len() == *this.size {
+                    break Poll::Ready(Some(this.buf.iter().cloned().collect()));
+                }
+            } else {
+                this.buf.clear();
+                break Poll::Ready(None);
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        ready!(this.stream.poll_cancel(cx));
+        *this.buf = VecDeque::new();
+        Poll::Ready(())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every item yields a window once the buffer has been filled.
+        let until_full = (self.size - 1).saturating_sub(self.buf.len());
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_sub(until_full),
+            upper.map(|upper| upper.saturating_sub(until_full)),
+        )
+    }
+}
+
+impl<S> Stream for Windows<S>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    <S as CompletionStream>::Item: Clone,
+{
+    type Item = Vec<<S as CompletionStream>::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
@@ -340,3 +340,47 @@ where
         intermediate
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::string::String;
+    use std::vec::Vec;
+
+    use completion_core::CompletionStream;
+    use futures_lite::stream;
+
+    use crate::future::block_on;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn hash_map() {
+        let stream = stream::iter(vec![("a", 1), ("b", 2), ("a", 3)]).into_completion();
+        let map: HashMap<_, _> = block_on(stream.collect());
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 3);
+        assert_eq!(map["b"], 2);
+    }
+
+    #[test]
+    fn result_of_string() {
+        let stream = stream::iter(vec![Ok('h'), Ok('i')]).into_completion();
+        let res: Result<String, ()> = block_on(stream.collect());
+        assert_eq!(res, Ok(String::from("hi")));
+
+        let stream = stream::iter(vec![Ok('h'), Err(5), Ok('i')]).into_completion();
+        assert_eq!(block_on(stream.collect::<Result<String, _>>()), Err(5));
+    }
+
+    #[test]
+    fn vec_reserves_from_size_hint() {
+        let stream = stream::iter(0..100).into_completion();
+        assert_eq!(CompletionStream::size_hint(&stream), (100, Some(100)));
+        let v: Vec<_> = block_on(stream.collect());
+        assert!(v.capacity() >= 100);
+
+        // `filter` only knows an upper bound, so nothing is reserved up front.
+        let stream = stream::iter(0..100).into_completion().filter(|&x| x < 10);
+        assert_eq!(CompletionStream::size_hint(&stream), (0, Some(100)));
+    }
+}
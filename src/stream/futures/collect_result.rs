@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for
+    /// [`CompletionStreamExt::collect_result`](crate::CompletionStreamExt::collect_result).
+    #[derive(Debug)]
+    pub struct CollectResult<S, T, E> {
+        #[pin]
+        stream: S,
+        items: Vec<T>,
+        // The first error, present while the stream is being cancelled.
+        error: Option<E>,
+    }
+}
+
+impl<S: CompletionStream, T, E> CollectResult<S, T, E> {
+    pub(crate) fn new(stream: S) -> Self {
+        let (lower, _) = stream.size_hint();
+        Self {
+            stream,
+            items: Vec::with_capacity(lower),
+            error: None,
+        }
+    }
+}
+
+impl<S, T, E> CompletionFuture for CollectResult<S, T, E>
+where
+    S: CompletionStream<Item = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, E>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if this.error.is_none() {
+            loop {
+                match ready!(this.stream.as_mut().poll_next(cx)) {
+                    Some(Ok(item)) => this.items.push(item),
+                    Some(Err(e)) => {
+                        *this.error = Some(e);
+                        break;
+                    }
+                    None => return Poll::Ready(Ok(mem::take(this.items))),
+                }
+            }
+        }
+
+        ready!(this.stream.poll_cancel(cx));
+        this.items.clear();
+        Poll::Ready(Err(this.error.take().unwrap()))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        ready!(this.stream.poll_cancel(cx));
+        this.items.clear();
+        *this.error = None;
+        Poll::Ready(())
+    }
+}
+
+impl<S, T, E> Future for CollectResult<S, T, E>
+where
+    S: CompletionStream<Item = Result<T, E>> + Stream<Item = Result<T, E>>,
+{
+    type Output = <Self as CompletionFuture>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::vec::IntoIter;
+
+    use crate::future::block_on;
+
+    /// A stream of items, which may be pending, that records whether it was cancelled.
+    struct Items {
+        items: IntoIter<Poll<Result<i32, &'static str>>>,
+        cancelled: bool,
+    }
+
+    impl Items {
+        fn new(items: Vec<Poll<Result<i32, &'static str>>>) -> Self {
+            Self {
+                items: items.into_iter(),
+                cancelled: false,
+            }
+        }
+    }
+
+    impl CompletionStream for Items {
+        type Item = Result<i32, &'static str>;
+
+        unsafe fn poll_next(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item>> {
+            match self.get_mut().items.next() {
+                Some(Poll::Ready(item)) => Poll::Ready(Some(item)),
+                Some(Poll::Pending) => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                None => Poll::Ready(None),
+            }
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.get_mut().cancelled = true;
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn all_ok() {
+        let stream = Items::new(vec![Poll::Ready(Ok(3)), Poll::Pending, Poll::Ready(Ok(1))]);
+        let mut fut = CollectResult::new(stream);
+        assert_eq!(block_on(Pin::new(&mut fut)), Ok(vec![3, 1]));
+        assert!(!fut.stream.cancelled);
+    }
+
+    #[test]
+    fn error_in_middle() {
+        let stream = Items::new(vec![
+            Poll::Ready(Ok(3)),
+            Poll::Ready(Ok(1)),
+            Poll::Pending,
+            Poll::Ready(Err("oh no")),
+            Poll::Ready(Ok(2)),
+        ]);
+        let mut fut = CollectResult::new(stream);
+        let cx = &mut crate::noop_cx();
+
+        assert!(unsafe { Pin::new(&mut fut).poll(cx) }.is_pending());
+        assert_eq!(fut.items, [3, 1]);
+        assert!(!fut.stream.cancelled);
+
+        assert_eq!(
+            unsafe { Pin::new(&mut fut).poll(cx) },
+            Poll::Ready(Err("oh no"))
+        );
+        assert!(fut.stream.cancelled);
+        assert_eq!(fut.stream.items.len(), 1);
+    }
+}
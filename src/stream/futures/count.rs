@@ -52,3 +52,135 @@ where
         unsafe { CompletionFuture::poll(self, cx) }
     }
 }
+
+pin_project! {
+    /// Future for
+    /// [`CompletionStreamExt::drain_or_cancel`](crate::CompletionStreamExt::drain_or_cancel).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct DrainOrCancel<S, C> {
+        count: usize,
+        #[pin]
+        stream: S,
+        #[pin]
+        signal: C,
+        // Whether the stream has been exhausted or cancelled.
+        stream_done: bool,
+        // Whether the signal has completed or been cancelled.
+        signal_done: bool,
+    }
+}
+
+impl<S, C> DrainOrCancel<S, C> {
+    pub(crate) fn new(stream: S, signal: C) -> Self {
+        Self {
+            count: 0,
+            stream,
+            signal,
+            stream_done: false,
+            signal_done: false,
+        }
+    }
+}
+
+impl<S, C> CompletionFuture for DrainOrCancel<S, C>
+where
+    S: CompletionStream,
+    C: CompletionFuture<Output = ()>,
+{
+    type Output = usize;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        // Drain the stream until it ends or the signal fires, checking the signal before every
+        // item so that a stream that is always ready can't keep it from being noticed.
+        while !*this.stream_done && !*this.signal_done {
+            if this.signal.as_mut().poll(cx).is_ready() {
+                *this.signal_done = true;
+                break;
+            }
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(_) => *this.count += 1,
+                None => *this.stream_done = true,
+            }
+        }
+
+        // Cancel whichever of the two is still running.
+        if !*this.stream_done {
+            ready!(this.stream.poll_cancel(cx));
+            *this.stream_done = true;
+        }
+        if !*this.signal_done {
+            ready!(this.signal.poll_cancel(cx));
+            *this.signal_done = true;
+        }
+        Poll::Ready(*this.count)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+
+        if !*this.stream_done && this.stream.poll_cancel(cx).is_ready() {
+            *this.stream_done = true;
+        }
+        if !*this.signal_done && this.signal.poll_cancel(cx).is_ready() {
+            *this.signal_done = true;
+        }
+
+        if *this.stream_done && *this.signal_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<S, C> Future for DrainOrCancel<S, C>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    C: CompletionFuture<Output = ()> + Future<Output = ()>,
+{
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use futures_lite::stream;
+
+    use crate::future::{block_on, ready, CompletionFutureExt};
+    use crate::test_utils::{CompletionFutureExt as _, Yield};
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn signal_fires_after_two_items() {
+        // The signal is polled before each item, and again when the stream is pending.
+        let (signal, counts) = Yield::new(5, ready(())).count_polls();
+        let stream = stream::iter(0..10)
+            .into_completion()
+            .then(|i| Yield::once(ready(i)));
+        assert_eq!(block_on(stream.drain_or_cancel(signal).check()), 2);
+        assert_eq!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn signal_fires_while_stream_ready() {
+        let (signal, counts) = Yield::new(3, ready(())).count_polls();
+        let stream = stream::iter(0..10).into_completion();
+        assert_eq!(block_on(stream.drain_or_cancel(signal).check()), 3);
+        assert_eq!(counts.cancels(), 0);
+    }
+
+    #[test]
+    fn stream_ends_first() {
+        let (signal, counts) = Yield::new(100, ready(())).count_polls();
+        let stream = stream::iter(0..10)
+            .into_completion()
+            .then(|i| Yield::once(ready(i)));
+        assert_eq!(block_on(stream.drain_or_cancel(signal).check()), 10);
+        assert_ne!(counts.cancels(), 0);
+    }
+}
@@ -64,3 +64,62 @@ where
         unsafe { CompletionFuture::poll(self, cx) }
     }
 }
+
+pin_project! {
+    /// Future for [`CompletionStreamExt::fold_mut`](crate::CompletionStreamExt::fold_mut).
+    #[derive(Debug)]
+    pub struct FoldMut<S, F, T> {
+        #[pin]
+        stream: S,
+        f: F,
+        accumulator: Option<T>,
+    }
+}
+
+impl<S, F, T> FoldMut<S, F, T> {
+    pub(crate) fn new(stream: S, init: T, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            accumulator: Some(init),
+        }
+    }
+}
+
+impl<S, F, T> CompletionFuture for FoldMut<S, F, T>
+where
+    S: CompletionStream,
+    F: FnMut(&mut T, S::Item),
+{
+    type Output = T;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let accumulator = this.accumulator.as_mut().expect("polled after completion");
+                    (this.f)(accumulator, item);
+                }
+                None => {
+                    break Poll::Ready(this.accumulator.take().expect("polled after completion"))
+                }
+            }
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().stream.poll_cancel(cx)
+    }
+}
+
+impl<S, F, T> Future for FoldMut<S, F, T>
+where
+    S: CompletionStream + Stream<Item = <S as CompletionStream>::Item>,
+    F: FnMut(&mut T, <S as CompletionStream>::Item),
+{
+    type Output = <Self as CompletionFuture>::Output;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
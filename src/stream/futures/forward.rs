@@ -0,0 +1,188 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionSink, CompletionStream};
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionStreamExt::forward`](crate::CompletionStreamExt::forward).
+    ///
+    /// Unlike most futures in this crate this never implements [`Future`](core::future::Future),
+    /// since there is no way to tell whether the sink can be dropped in the middle of an
+    /// operation.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you use them"]
+    pub struct Forward<S: CompletionStream, Si> {
+        #[pin]
+        stream: S,
+        #[pin]
+        sink: Si,
+        // An item taken from the stream that hasn't been passed to the sink yet.
+        buffered: Option<S::Item>,
+        // Whether the sink is in the middle of an operation.
+        sink_busy: bool,
+        // Whether the stream is in the middle of yielding an item.
+        stream_busy: bool,
+        stream_done: bool,
+    }
+}
+
+impl<S: CompletionStream, Si> Forward<S, Si> {
+    pub(crate) fn new(stream: S, sink: Si) -> Self {
+        Self {
+            stream,
+            sink,
+            buffered: None,
+            sink_busy: false,
+            stream_busy: false,
+            stream_done: false,
+        }
+    }
+}
+
+impl<S, Si> CompletionFuture for Forward<S, Si>
+where
+    S: CompletionStream,
+    Si: CompletionSink<S::Item>,
+{
+    type Output = Result<(), Si::Error>;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            if this.buffered.is_some() {
+                *this.sink_busy = true;
+                let res = ready!(this.sink.as_mut().poll_ready(cx)).and_then(|()| {
+                    let item = this.buffered.take().unwrap();
+                    this.sink.as_mut().start_send(item)
+                });
+                *this.sink_busy = false;
+                if let Err(e) = res {
+                    return Poll::Ready(Err(e));
+                }
+            }
+
+            if *this.stream_done {
+                break;
+            }
+            *this.stream_busy = true;
+            let item = ready!(this.stream.as_mut().poll_next(cx));
+            *this.stream_busy = false;
+            match item {
+                Some(item) => *this.buffered = Some(item),
+                None => *this.stream_done = true,
+            }
+        }
+
+        *this.sink_busy = true;
+        let res = ready!(this.sink.poll_close(cx));
+        *this.sink_busy = false;
+        Poll::Ready(res)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        if *this.stream_busy {
+            ready!(this.stream.poll_cancel(cx));
+            *this.stream_busy = false;
+            *this.stream_done = true;
+        }
+        if *this.sink_busy {
+            ready!(CompletionSink::poll_cancel(this.sink, cx));
+            *this.sink_busy = false;
+        }
+        *this.buffered = None;
+        Poll::Ready(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+    use std::vec::Vec;
+
+    use futures_lite::stream;
+
+    use crate::future::{block_on, CompletionFutureExt};
+    use crate::test_utils::Yield;
+    use crate::{CompletionStreamExt, StreamExt};
+
+    #[test]
+    fn into_vec() {
+        let mut sink = Vec::new();
+        let res = block_on(stream::iter(0..5).into_completion().forward(&mut sink));
+        assert_eq!(res, Ok::<_, Infallible>(()));
+        assert_eq!(sink, [0, 1, 2, 3, 4]);
+    }
+
+    /// A sink that is never ready, counting how many times it is cancelled.
+    #[derive(Default)]
+    struct Stuck {
+        cancels: usize,
+    }
+
+    impl CompletionSink<i32> for Stuck {
+        type Error = Infallible;
+
+        unsafe fn poll_ready(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Pending
+        }
+        fn start_send(self: Pin<&mut Self>, _item: i32) -> Result<(), Self::Error> {
+            unreachable!()
+        }
+        unsafe fn poll_flush(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_close(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_cancel(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.cancels += 1;
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn cancel_sink() {
+        let mut sink = Stuck::default();
+        let mut fut = stream::iter(0..5).into_completion().forward(&mut sink);
+
+        let cx = &mut crate::noop_cx();
+        unsafe {
+            assert!(CompletionFuture::poll(Pin::new(&mut fut), cx).is_pending());
+            assert!(CompletionFuture::poll_cancel(Pin::new(&mut fut), cx).is_ready());
+        }
+        drop(fut);
+        assert_eq!(sink.cancels, 1);
+    }
+
+    #[test]
+    fn cancel_stream() {
+        let (item, counts) = Yield::new(5, crate::future::ready(0)).count_polls();
+        let mut item = Some(item);
+        let stream = stream::iter(0..1)
+            .into_completion()
+            .then(move |_| item.take().unwrap());
+        let mut fut = stream.forward(Vec::new());
+
+        let cx = &mut crate::noop_cx();
+        let mut fut = unsafe { Pin::new_unchecked(&mut fut) };
+        unsafe {
+            assert!(CompletionFuture::poll(fut.as_mut(), cx).is_pending());
+            while CompletionFuture::poll_cancel(fut.as_mut(), cx).is_pending() {}
+        }
+        assert_ne!(counts.cancels(), 0);
+    }
+}
@@ -13,9 +13,17 @@ pub use nth::*;
 mod for_each;
 pub use for_each::*;
 
+mod forward;
+pub use forward::*;
+
 mod collect;
 pub use collect::*;
 
+#[cfg(feature = "alloc")]
+mod collect_result;
+#[cfg(feature = "alloc")]
+pub use collect_result::*;
+
 mod fold;
 pub use fold::*;
 
@@ -27,3 +35,6 @@ pub use find::*;
 
 mod max_min;
 pub use max_min::*;
+
+mod unzip;
+pub use unzip::*;
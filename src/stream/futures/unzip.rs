@@ -0,0 +1,67 @@
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for [`CompletionStreamExt::unzip`](crate::CompletionStreamExt::unzip).
+    #[derive(Debug)]
+    pub struct Unzip<S, CA, CB> {
+        #[pin]
+        stream: S,
+        left: CA,
+        right: CB,
+    }
+}
+
+impl<S, CA: Default, CB: Default> Unzip<S, CA, CB> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            left: CA::default(),
+            right: CB::default(),
+        }
+    }
+}
+
+impl<S, A, B, CA, CB> CompletionFuture for Unzip<S, CA, CB>
+where
+    S: CompletionStream<Item = (A, B)>,
+    CA: Default + Extend<A>,
+    CB: Default + Extend<B>,
+{
+    type Output = (CA, CB);
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        while let Some((a, b)) = ready!(this.stream.as_mut().poll_next(cx)) {
+            this.left.extend(Some(a));
+            this.right.extend(Some(b));
+        }
+        Poll::Ready((mem::take(this.left), mem::take(this.right)))
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        ready!(this.stream.poll_cancel(cx));
+        *this.left = CA::default();
+        *this.right = CB::default();
+        Poll::Ready(())
+    }
+}
+
+impl<S, A, B, CA, CB> Future for Unzip<S, CA, CB>
+where
+    S: CompletionStream<Item = (A, B)> + Stream<Item = (A, B)>,
+    CA: Default + Extend<A>,
+    CB: Default + Extend<B>,
+{
+    type Output = <Self as CompletionFuture>::Output;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe { CompletionFuture::poll(self, cx) }
+    }
+}
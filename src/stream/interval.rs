@@ -0,0 +1,152 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use completion_core::{CompletionFuture, CompletionStream};
+use futures_core::{ready, Stream};
+
+use crate::future::{saturating_add, sleep_until, Sleep};
+
+/// Create a stream that yields every `period`.
+///
+/// The first tick is yielded one `period` after this function is called. Each item is the
+/// [`Instant`] at which that tick was scheduled.
+///
+/// If the stream isn't polled for longer than a `period`, the missed ticks are skipped rather
+/// than burnt down: a single tick is yielded as soon as the stream is polled again, and the
+/// following ticks are scheduled one `period` after it.
+///
+/// Cancelling the stream removes its pending timer.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use completion::{stream, CompletionStreamExt};
+///
+/// # completion::future::block_on(completion::completion_async! {
+/// let start = Instant::now();
+/// let ticks = stream::interval(Duration::from_millis(10)).take(3).count().await;
+/// assert_eq!(ticks, 3);
+/// assert!(start.elapsed() >= Duration::from_millis(30));
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn interval(period: Duration) -> Interval {
+    assert!(
+        period > Duration::from_secs(0),
+        "`interval` period must be non-zero"
+    );
+    Interval {
+        sleep: sleep_until(saturating_add(Instant::now(), period)),
+        period,
+    }
+}
+
+/// Stream for [`interval`].
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you use them"]
+pub struct Interval {
+    sleep: Sleep,
+    period: Duration,
+}
+
+impl Interval {
+    /// Get the period of the interval.
+    #[must_use]
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+impl CompletionStream for Interval {
+    type Item = Instant;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Stream::poll_next(self, cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut self.get_mut().sleep).poll_cancel(cx)
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        ready!(Future::poll(Pin::new(&mut this.sleep), cx));
+
+        let tick = this.sleep.deadline();
+        let now = Instant::now();
+        let mut next = saturating_add(tick, this.period);
+        if next <= now {
+            // Skip the ticks that were missed.
+            next = saturating_add(now, this.period);
+        }
+        this.sleep = sleep_until(next);
+
+        Poll::Ready(Some(tick))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use crate::future::block_on;
+    use crate::CompletionStreamExt;
+
+    #[test]
+    fn spacing() {
+        let period = Duration::from_millis(20);
+        let mut interval = interval(period);
+
+        let first = block_on(interval.next()).unwrap();
+        let second = block_on(interval.next()).unwrap();
+        let third = block_on(interval.next()).unwrap();
+        assert_eq!(second - first, period);
+        assert_eq!(third - second, period);
+        assert!(Instant::now() >= third);
+    }
+
+    #[test]
+    fn skips_missed_ticks() {
+        let period = Duration::from_millis(20);
+        let mut interval = interval(period);
+        thread::sleep(period * 5);
+
+        // Only one tick is yielded for the missed period.
+        let late = block_on(interval.next()).unwrap();
+        let next = block_on(interval.next()).unwrap();
+        assert!(next - late >= period * 5);
+    }
+
+    #[test]
+    fn huge_period() {
+        let mut interval = interval(Duration::MAX);
+        let mut cx = crate::noop_cx();
+        assert!(
+            unsafe { CompletionStream::poll_next(Pin::new(&mut interval), &mut cx) }.is_pending()
+        );
+        assert!(unsafe { Pin::new(&mut interval).poll_cancel(&mut cx) }.is_ready());
+    }
+
+    #[test]
+    #[should_panic = "`interval` period must be non-zero"]
+    fn zero_period() {
+        let _ = interval(Duration::from_secs(0));
+    }
+}
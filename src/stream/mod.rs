@@ -2,17 +2,22 @@
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::cmp;
 #[cfg(feature = "std")]
 use core::iter::FusedIterator;
 use core::pin::Pin;
 use core::task::{Context, Poll};
+#[cfg(feature = "std")]
+use std::time::Duration;
 
-use completion_core::CompletionFuture;
 #[doc(no_inline)]
 pub use completion_core::CompletionStream;
+use completion_core::{CompletionFuture, CompletionSink};
 use futures_core::Stream;
 
+use super::future::PollCancelFreeOnDrop;
 use super::{Adapter, MustComplete};
 
 mod adapters;
@@ -24,11 +29,33 @@ pub use futures::*;
 mod unfold;
 pub use unfold::*;
 
+mod select;
+pub use select::*;
+
+#[cfg(feature = "alloc")]
+mod unordered;
+#[cfg(feature = "alloc")]
+pub use unordered::Unordered;
+
+#[cfg(feature = "std")]
+mod interval;
+#[cfg(feature = "std")]
+pub use interval::{interval, Interval};
+
+#[cfg(feature = "std")]
+mod split;
+#[cfg(feature = "std")]
+pub use split::{SplitSink, SplitStream};
+
 mod from_completion_stream;
 pub use from_completion_stream::FromCompletionStream;
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 pub(crate) use from_completion_stream::FromCompletionStreamInner;
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test-util")))]
+pub mod test;
+
 /// Extension trait for [`CompletionStream`].
 pub trait CompletionStreamExt: CompletionStream {
     /// A convenience for calling [`CompletionStream::poll_next`] on [`Unpin`] streams.
@@ -92,6 +119,9 @@ pub trait CompletionStreamExt: CompletionStream {
 
     /// Count the number of items in the stream.
     ///
+    /// The items themselves are dropped as soon as they are yielded. Cancelling the returned
+    /// future cancels the stream.
+    ///
     /// # Examples
     ///
     /// ```
@@ -104,6 +134,7 @@ pub trait CompletionStreamExt: CompletionStream {
     ///
     /// assert_eq!(stream_1.count().await, 4);
     /// assert_eq!(stream_2.count().await, 3);
+    /// assert_eq!(stream::iter(1..=5).into_completion().count().await, 5);
     /// # });
     /// ```
     fn count(self) -> Count<Self>
@@ -113,8 +144,42 @@ pub trait CompletionStreamExt: CompletionStream {
         Count::new(self)
     }
 
+    /// Drain the stream until a cancellation signal fires, then cancel it, outputting the number of
+    /// items drained.
+    ///
+    /// Items are dropped as soon as they are yielded. If the signal completes first, the stream is
+    /// cancelled; if the stream ends first, the signal is cancelled. Either way, the returned
+    /// future only outputs once both have finished. Cancelling the returned future cancels both
+    /// the stream and the signal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # future::block_on(completion::completion_async! {
+    /// // A signal that never fires lets the whole stream drain.
+    /// let stream = stream::iter(0..5).into_completion();
+    /// assert_eq!(stream.drain_or_cancel(future::never()).await, 5);
+    ///
+    /// // A signal that has already fired drains nothing.
+    /// let stream = stream::pending::<()>().into_completion();
+    /// assert_eq!(stream.drain_or_cancel(future::ready(())).await, 0);
+    /// # });
+    /// ```
+    fn drain_or_cancel<C>(self, signal: C) -> DrainOrCancel<Self, C>
+    where
+        C: CompletionFuture<Output = ()>,
+        Self: Sized,
+    {
+        DrainOrCancel::new(self, signal)
+    }
+
     /// Get the last element in the stream.
     ///
+    /// Only the most recent item is kept. Cancelling the returned future cancels the stream.
+    ///
     /// # Examples
     ///
     /// ```
@@ -123,6 +188,7 @@ pub trait CompletionStreamExt: CompletionStream {
     ///
     /// # completion::future::block_on(completion::completion_async! {
     /// assert_eq!(stream::iter(3..7).into_completion().last().await, Some(6));
+    /// assert_eq!(stream::iter(1..=5).into_completion().last().await, Some(5));
     /// assert_eq!(stream::empty::<String>().into_completion().last().await, None);
     /// # });
     /// ```
@@ -211,6 +277,75 @@ pub trait CompletionStreamExt: CompletionStream {
         Chain::new(self, other)
     }
 
+    /// Combine this stream with another, yielding the latest item of both whenever either of them
+    /// yields a new item.
+    ///
+    /// Nothing is yielded until both streams have yielded at least once. Unlike zipping, items are
+    /// not paired up positionally: the latest item of each stream is cloned into every pair until
+    /// it is replaced by a newer one. If one stream is exhausted, its last item continues to be
+    /// paired with new items from the other stream; if it is exhausted without ever yielding an
+    /// item, the other stream is cancelled and the returned stream ends.
+    ///
+    /// The two streams are polled in alternating order to ensure a fast stream can't starve a slow
+    /// one. Cancelling the returned stream cancels both streams.
+    ///
+    /// Since completion streams cannot be dropped until they have finished yielding an item, like
+    /// with [`select_with_strategy`](crate::stream::select_with_strategy) a pair is only yielded
+    /// once neither stream is in the middle of yielding an item, and a stream that has yielded a
+    /// new item isn't polled again until that item has been yielded in a pair. This means that
+    /// while both streams are running, pairs are yielded at the pace of the slower stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{completion_stream, CompletionStreamExt, StreamExt};
+    /// use futures_lite::future::yield_now;
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let fast = stream::iter(vec![1, 2, 3, 4]).into_completion();
+    /// let slow = completion_stream! {
+    ///     for item in vec!["a", "b"] {
+    ///         yield_now().await;
+    ///         yield_now().await;
+    ///         yield item;
+    ///     }
+    /// };
+    ///
+    /// let pairs: Vec<_> = fast.combine_latest(slow).collect().await;
+    /// assert_eq!(pairs, [(2, "a"), (3, "a"), (4, "b")]);
+    /// # });
+    /// ```
+    fn combine_latest<U>(self, other: U) -> CombineLatest<Self, U>
+    where
+        U: CompletionStream,
+        Self::Item: Clone,
+        U::Item: Clone,
+        Self: Sized,
+    {
+        CombineLatest::new(self, other)
+    }
+
+    /// Split a value that is both a stream and a sink into two separate halves.
+    ///
+    /// This is useful for duplex resources like connections, where reading and writing need to be
+    /// driven independently, for example in two halves of a [`zip`](crate::future::zip). Both
+    /// halves share the resource behind a lock, which is only held for the duration of each
+    /// individual poll.
+    ///
+    /// Cancelling one half only cancels the operation in progress on that half, by calling
+    /// [`CompletionStream::poll_cancel`] or [`CompletionSink::poll_cancel`] on the resource
+    /// respectively; the other half is unaffected and can continue to be used. The resource is
+    /// dropped once both halves are dropped.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn split<Item>(self) -> (SplitSink<Self>, SplitStream<Self>)
+    where
+        Self: CompletionSink<Item> + Sized,
+    {
+        split::split(self)
+    }
+
     // TODO: zip
 
     /// Map this stream's items with a closure.
@@ -239,8 +374,69 @@ pub trait CompletionStreamExt: CompletionStream {
         Map::new(self, f)
     }
 
+    /// Map the successful items of a stream of [`Result`]s with a closure, passing errors on.
+    ///
+    /// The closure is not called for errors, at the end of the stream, or while it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let items: Vec<_> = stream::iter(vec![Ok(1), Err("oh no"), Ok(2)])
+    ///     .into_completion()
+    ///     .map_ok(|x| x * 10)
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(items, [Ok(10), Err("oh no"), Ok(20)]);
+    /// # });
+    /// ```
+    fn map_ok<T, E, U, F>(self, f: F) -> MapOk<Self, F>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+        F: FnMut(T) -> U,
+    {
+        MapOk::new(self, f)
+    }
+
+    /// Map the errors of a stream of [`Result`]s with a closure, passing successful items on.
+    ///
+    /// The closure is not called for successful items, at the end of the stream, or while it is
+    /// cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let items: Vec<_> = stream::iter(vec![Ok(1), Err("oh no"), Ok(2)])
+    ///     .into_completion()
+    ///     .map_err(str::len)
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(items, [Ok(1), Err(5), Ok(2)]);
+    /// # });
+    /// ```
+    fn map_err<T, E, E2, F>(self, f: F) -> MapErr<Self, F>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+        F: FnMut(E) -> E2,
+    {
+        MapErr::new(self, f)
+    }
+
     /// Map this stream's items with an asynchronous closure.
     ///
+    /// Each item's future is run to completion before the next item is taken from the stream, so
+    /// the outputs are yielded in order. Cancelling the stream while a future is in flight cancels
+    /// that future, and the item it was produced from is lost.
+    ///
     /// # Examples
     ///
     /// ```
@@ -288,6 +484,35 @@ pub trait CompletionStreamExt: CompletionStream {
         ForEach::new(self, f)
     }
 
+    /// Send every item in the stream into a sink, closing the sink once the stream has ended.
+    ///
+    /// Items are only taken from the stream once the sink is ready to receive them. If the sink
+    /// fails, the error is returned immediately and the rest of the stream is left unconsumed.
+    ///
+    /// Cancelling the returned future cancels the stream if it is in the middle of yielding an
+    /// item and the sink if it is in the middle of an operation. An item that has been taken from
+    /// the stream but not yet passed to the sink is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut sink = Vec::new();
+    /// stream::iter(0..5).into_completion().forward(&mut sink).await.unwrap();
+    /// assert_eq!(sink, [0, 1, 2, 3, 4]);
+    /// # });
+    /// ```
+    fn forward<Si>(self, sink: Si) -> Forward<Self, Si>
+    where
+        Si: CompletionSink<Self::Item>,
+        Self: Sized,
+    {
+        Forward::new(self, sink)
+    }
+
     /// Keep the values in the stream for which the predicate resolves to `true`.
     ///
     /// # Examples
@@ -343,6 +568,156 @@ pub trait CompletionStreamExt: CompletionStream {
         FilterMap::new(self, f)
     }
 
+    /// Remove consecutive repeated items from the stream.
+    ///
+    /// Each yielded item is cloned once so that it can be remembered and compared against the
+    /// following items, which is why the items must be [`Clone`]. If the stream is cancelled the
+    /// remembered item is dropped along with the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec![1, 1, 2, 2, 2, 3, 1]).into_completion();
+    /// assert_eq!(stream.dedup().collect::<Vec<_>>().await, [1, 2, 3, 1]);
+    /// # });
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self::Item: PartialEq + Clone,
+        Self: Sized,
+    {
+        Dedup::new(self)
+    }
+
+    /// Remove consecutive items from the stream that the closure considers equal.
+    ///
+    /// The closure is passed the last yielded item and the new item, and should return `true` if
+    /// the new item is a duplicate and should be skipped. As with [`dedup`](Self::dedup), each
+    /// yielded item is cloned once to remember it, which is why the items must be [`Clone`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec!["a", "A", "b", "c", "C", "c"]).into_completion();
+    /// let stream = stream.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(stream.collect::<Vec<_>>().await, ["a", "b", "c"]);
+    /// # });
+    /// ```
+    fn dedup_by<F>(self, f: F) -> DedupBy<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+        Self::Item: Clone,
+        Self: Sized,
+    {
+        DedupBy::new(self, f)
+    }
+
+    /// Group runs of consecutive items that have the same key into [`Vec`]s.
+    ///
+    /// Items are buffered until an item with a different key is found, at which point the
+    /// completed group is yielded. The final group is yielded when the stream ends. Cancelling the
+    /// stream drops the group that was being built up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec![("a", 1), ("a", 2), ("b", 3)]).into_completion();
+    /// assert_eq!(
+    ///     stream.chunk_by(|&(key, _)| key).collect::<Vec<_>>().await,
+    ///     [vec![("a", 1), ("a", 2)], vec![("b", 3)]],
+    /// );
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn chunk_by<K, F>(self, f: F) -> ChunkBy<Self, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+        Self: Sized,
+    {
+        ChunkBy::new(self, f)
+    }
+
+    /// Yield overlapping windows of the last `size` items of the stream.
+    ///
+    /// The first window is yielded once `size` items have arrived, and each item after that
+    /// slides the window along by one. Since every item can appear in up to `size` windows, the
+    /// items must implement [`Clone`]. Cancelling the stream drops the buffered items, so the
+    /// next window will only be yielded once another `size` items have arrived.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(1..=5).into_completion().windows(3);
+    /// assert_eq!(
+    ///     stream.collect::<Vec<_>>().await,
+    ///     [[1, 2, 3], [2, 3, 4], [3, 4, 5]],
+    /// );
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self::Item: Clone,
+        Self: Sized,
+    {
+        Windows::new(self, size)
+    }
+
+    /// Re-slice a stream of byte chunks into chunks of exactly `size` bytes.
+    ///
+    /// Incoming chunks are buffered and split up or joined together as needed, so every yielded
+    /// chunk is `size` bytes long except possibly the last one, which holds whatever is left when
+    /// the stream ends. This is useful for framing protocols with fixed-size records. Cancelling
+    /// the stream cancels the inner stream and drops any buffered bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let chunks = vec![b"abc".to_vec(), b"defgh".to_vec()];
+    /// let stream = stream::iter(chunks).into_completion().rechunk(3);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [&b"abc"[..], b"def", b"gh"]);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn rechunk(self, size: usize) -> Rechunk<Self>
+    where
+        Self: CompletionStream<Item = Vec<u8>> + Sized,
+    {
+        Rechunk::new(self, size)
+    }
+
     /// Yield the current iteration count as well as the next value.
     ///
     /// The returned stream yields pairs `(i, val)` where `i` is the current index of iteration and
@@ -380,7 +755,38 @@ pub trait CompletionStreamExt: CompletionStream {
     where
         Self: Sized,
     {
-        Enumerate::new(self)
+        Enumerate::new(self, 0)
+    }
+
+    /// Like [`enumerate`](Self::enumerate), but starting the count at `start` instead of zero.
+    ///
+    /// This is useful for continuing a sequence that was numbered previously.
+    ///
+    /// # Overflow Behaviour
+    ///
+    /// Overflow is handled the same way as in [`enumerate`](Self::enumerate): if the index would go
+    /// past [`usize::MAX`], the stream panics with debug assertions enabled and otherwise wraps
+    /// around to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut stream = stream::iter(vec!['x', 'y']).into_completion().enumerate_from(100);
+    ///
+    /// assert_eq!(stream.next().await, Some((100, 'x')));
+    /// assert_eq!(stream.next().await, Some((101, 'y')));
+    /// assert_eq!(stream.next().await, None);
+    /// # });
+    /// ```
+    fn enumerate_from(self, start: usize) -> Enumerate<Self>
+    where
+        Self: Sized,
+    {
+        Enumerate::new(self, start)
     }
 
     /// Create a stream which can use [`peek`](Peekable::peek) to look at the next element of the
@@ -425,83 +831,273 @@ pub trait CompletionStreamExt: CompletionStream {
     /// use futures_lite::stream;
     ///
     /// # completion::future::block_on(completion::completion_async! {
-    /// let stream = stream::iter("   Hello world!".chars()).into_completion();
-    /// let text: String = stream.skip_while(char::is_ascii_whitespace).collect().await;
-    /// assert_eq!(text, "Hello world!");
+    /// let stream = stream::iter("   Hello world!".chars()).into_completion();
+    /// let text: String = stream.skip_while(char::is_ascii_whitespace).collect().await;
+    /// assert_eq!(text, "Hello world!");
+    /// # });
+    /// ```
+    fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        SkipWhile::new(self, predicate)
+    }
+
+    /// Take items while the predicate returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter("Hello world!\nFoo bar".chars()).into_completion();
+    /// let text: String = stream.take_while(|&c| c != '\n').collect().await;
+    /// assert_eq!(text, "Hello world!");
+    /// # });
+    /// ```
+    fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        TakeWhile::new(self, predicate)
+    }
+
+    /// Skip the first `n` items in the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(0..10).into_completion();
+    /// assert_eq!(stream.skip(5).collect::<Vec<_>>().await, [5, 6, 7, 8, 9]);
+    /// # });
+    /// ```
+    fn skip(self, n: usize) -> Skip<Self>
+    where
+        Self: Sized,
+    {
+        Skip::new(self, n)
+    }
+
+    /// Takes the first `n` items of the stream. All other items will be ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::repeat(19).into_completion();
+    /// assert_eq!(stream.take(5).collect::<Vec<_>>().await, [19, 19, 19, 19, 19]);
+    /// # });
+    /// ```
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, n)
+    }
+
+    /// Takes items of the stream until the given future completes.
+    ///
+    /// The future is polled before the stream each time an item is requested, and as soon as it
+    /// completes the stream is cancelled and no more items are yielded. If the stream ends first,
+    /// the future is cancelled.
+    ///
+    /// Since streams can be dropped in between items while the future is still running, the
+    /// future must be safe to drop at any time, as expressed by the [`PollCancelFreeOnDrop`]
+    /// trait. Regular [`Future`]s can be converted with
+    /// [`FutureExt::into_completion`](crate::FutureExt::into_completion).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::task::Poll;
+    ///
+    /// use completion::{CompletionStreamExt, FutureExt, StreamExt};
+    /// use futures_lite::{future, stream};
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut polls = 0;
+    /// let stop = future::poll_fn(|cx| {
+    ///     polls += 1;
+    ///     if polls > 3 {
+    ///         Poll::Ready(())
+    ///     } else {
+    ///         cx.waker().wake_by_ref();
+    ///         Poll::Pending
+    ///     }
+    /// })
+    /// .into_completion();
+    ///
+    /// let stream = stream::repeat(1).into_completion();
+    /// assert_eq!(stream.take_until(stop).collect::<Vec<_>>().await, [1, 1, 1]);
+    /// # });
+    /// ```
+    fn take_until<F>(self, fut: F) -> TakeUntil<Self, F>
+    where
+        Self: Sized,
+        F: PollCancelFreeOnDrop,
+    {
+        TakeUntil::new(self, fut)
+    }
+
+    /// Limit the rate at which items are yielded with a token bucket.
+    ///
+    /// Up to `burst` items can be yielded immediately, after which items are yielded at most
+    /// `rate` times per second. Time spent waiting for items from the underlying stream counts
+    /// towards refilling the bucket, up to the `burst` limit. Rates above one billion per second
+    /// are treated as one billion per second.
+    ///
+    /// Cancelling the stream cancels the wait for the next token if there is one, and then the
+    /// underlying stream if it is in the middle of yielding an item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` or `burst` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let start = Instant::now();
+    /// let stream = stream::iter(0..4).into_completion().rate_limited(100, 2);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [0, 1, 2, 3]);
+    ///
+    /// // Two items were delayed by 10ms each.
+    /// assert!(start.elapsed() >= Duration::from_millis(20));
     /// # });
     /// ```
-    fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P>
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn rate_limited(self, rate: u32, burst: u32) -> RateLimited<Self>
     where
-        P: FnMut(&Self::Item) -> bool,
         Self: Sized,
     {
-        SkipWhile::new(self, predicate)
+        RateLimited::new(self, rate, burst)
     }
 
-    /// Take items while the predicate returns `true`.
+    /// End the stream once a duration has elapsed since it was first polled.
+    ///
+    /// This bounds the stream as a whole, regardless of how often it yields items. When the time
+    /// is up, the inner stream is cancelled and the stream ends, without yielding any error item.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::time::Duration;
+    ///
     /// use completion::{CompletionStreamExt, StreamExt};
     /// use futures_lite::stream;
     ///
     /// # completion::future::block_on(completion::completion_async! {
-    /// let stream = stream::iter("Hello world!\nFoo bar".chars()).into_completion();
-    /// let text: String = stream.take_while(|&c| c != '\n').collect().await;
-    /// assert_eq!(text, "Hello world!");
+    /// let stream = stream::pending::<()>()
+    ///     .into_completion()
+    ///     .timeout_total(Duration::from_millis(10));
+    /// assert_eq!(stream.count().await, 0);
     /// # });
     /// ```
-    fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P>
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn timeout_total(self, duration: Duration) -> TimeoutTotal<Self>
     where
-        P: FnMut(&Self::Item) -> bool,
         Self: Sized,
     {
-        TakeWhile::new(self, predicate)
+        TimeoutTotal::new(self, duration)
     }
 
-    /// Skip the first `n` items in the stream.
+    /// Collect items into chunks, yielding a chunk once it has `size` items or once `duration`
+    /// has elapsed since its first item arrived, whichever comes first.
+    ///
+    /// The timer is only running while a chunk has items in it, so no empty chunks are yielded.
+    /// When the stream ends, any remaining items are yielded as a final, shorter chunk. Cancelling
+    /// the stream discards the current chunk and cancels both the inner stream and the timer.
+    ///
+    /// The timer keeps running while the inner stream is pending, so a chunk is yielded on time
+    /// even if the inner stream is in the middle of yielding an item; that item then starts the
+    /// next chunk. Since this can leave the inner stream in the middle of an item when the
+    /// returned stream is dropped, the inner stream must also implement [`Stream`], which allows
+    /// it to be dropped at any time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::time::Duration;
+    ///
     /// use completion::{CompletionStreamExt, StreamExt};
     /// use futures_lite::stream;
     ///
     /// # completion::future::block_on(completion::completion_async! {
-    /// let stream = stream::iter(0..10).into_completion();
-    /// assert_eq!(stream.skip(5).collect::<Vec<_>>().await, [5, 6, 7, 8, 9]);
+    /// let chunks: Vec<_> = stream::iter(0..5)
+    ///     .into_completion()
+    ///     .chunks_timeout(2, Duration::from_millis(10))
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(chunks, [vec![0, 1], vec![2, 3], vec![4]]);
     /// # });
     /// ```
-    fn skip(self, n: usize) -> Skip<Self>
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn chunks_timeout(self, size: usize, duration: Duration) -> ChunksTimeout<Self>
     where
-        Self: Sized,
+        Self: Sized + Stream<Item = <Self as CompletionStream>::Item>,
     {
-        Skip::new(self, n)
+        ChunksTimeout::new(self, size, duration)
     }
 
-    /// Takes the first `n` items of the stream. All other items will be ignored.
+    // TODO: scan
+
+    /// Transform the stream with an asynchronous closure that has access to some state.
+    ///
+    /// The closure is given a mutable reference to the state, initially `init`, along with each
+    /// item, and returns a future. Each future is run to completion before the next item is taken
+    /// from the stream. If a future outputs `Some`, its value is yielded; if it outputs `None`,
+    /// the stream ends.
+    ///
+    /// Cancelling the stream cancels the in-flight future if there is one, then cancels the
+    /// underlying stream, and then drops the state.
     ///
     /// # Examples
     ///
     /// ```
-    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use completion::{CompletionStreamExt, StreamExt, completion_async_move};
     /// use futures_lite::stream;
     ///
-    /// # completion::future::block_on(completion::completion_async! {
-    /// let stream = stream::repeat(19).into_completion();
-    /// assert_eq!(stream.take(5).collect::<Vec<_>>().await, [19, 19, 19, 19, 19]);
+    /// # completion::future::block_on(completion_async_move! {
+    /// let stream = stream::iter(1..).into_completion().scan_async(0, |total, x| {
+    ///     *total += x;
+    ///     let total = *total;
+    ///     completion_async_move!(if total > 10 { None } else { Some(total) })
+    /// });
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [1, 3, 6, 10]);
     /// # });
     /// ```
-    fn take(self, n: usize) -> Take<Self>
+    fn scan_async<St, F, Fut, T>(self, init: St, f: F) -> ScanAsync<Self, St, F, Fut>
     where
         Self: Sized,
+        F: FnMut(&mut St, Self::Item) -> Fut,
+        Fut: CompletionFuture<Output = Option<T>>,
     {
-        Take::new(self, n)
+        ScanAsync::new(self, init, f)
     }
 
-    // TODO: scan
-
     /// Map the stream, flattening nested structure.
     ///
     /// `.flat_map(f)` is equivalent to `.[`map`](Self::map)`(f).`[`flatten`](Self::flatten)`()`.
@@ -558,10 +1154,107 @@ pub trait CompletionStreamExt: CompletionStream {
         Flatten::new(self)
     }
 
+    /// Flatten a stream of streams, draining several inner streams concurrently.
+    ///
+    /// Up to `limit` inner streams are drained at once, and their items are yielded in whatever
+    /// order they become available. Whenever an inner stream is exhausted, a new one is taken from
+    /// the outer stream to replace it. The stream ends once the outer stream and all the inner
+    /// streams are exhausted.
+    ///
+    /// Since completion streams cannot be dropped until they have finished yielding an item, an
+    /// item is only yielded once none of the active inner streams nor the outer stream are in the
+    /// middle of yielding an item; items that arrive in the meantime are buffered. This means that
+    /// items are yielded at the pace of the slowest active stream.
+    ///
+    /// Cancelling the stream cancels the outer stream and all the active inner streams, dropping
+    /// any buffered items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let streams = vec![
+    ///     stream::iter(0..3).into_completion(),
+    ///     stream::iter(3..5).into_completion(),
+    ///     stream::iter(5..6).into_completion(),
+    /// ];
+    /// let stream = stream::iter(streams).into_completion().flatten_unordered(2);
+    /// let mut v: Vec<u8> = stream.collect().await;
+    /// v.sort_unstable();
+    /// assert_eq!(v, [0, 1, 2, 3, 4, 5]);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn flatten_unordered(self, limit: usize) -> FlattenUnordered<Self>
+    where
+        Self: Sized,
+        Self::Item: CompletionStream,
+    {
+        FlattenUnordered::new(self, limit)
+    }
+
+    /// Run up to `limit` futures from the stream at once, yielding their outputs in ascending
+    /// order of a key computed from each output.
+    ///
+    /// This acts as a reorder buffer: futures are taken from the stream until `limit` of them are
+    /// either running or completed but not yet yielded, and once all the running ones have
+    /// completed, the output with the lowest key is yielded and another future is taken in its
+    /// place. Keys are only compared within this window, so outputs come out fully sorted as long
+    /// as each one is fewer than `limit` positions away from its sorted position; this is useful
+    /// for reassembling out-of-order responses such as sequence-numbered packets.
+    ///
+    /// Since completion streams cannot be dropped until they have finished yielding an item,
+    /// nothing is yielded while the source stream is in the middle of yielding the next future.
+    ///
+    /// Cancelling the stream cancels the source stream and all the running futures, and drops
+    /// any completed outputs that haven't been yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{future, CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let responses = stream::iter(vec![(1, "b"), (0, "a"), (3, "d"), (2, "c")])
+    ///     .into_completion()
+    ///     .map(future::ready)
+    ///     .buffered_by_key(2, |&(seq, _)| seq);
+    /// let v: Vec<_> = responses.map(|(_, data)| data).collect().await;
+    /// assert_eq!(v, ["a", "b", "c", "d"]);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn buffered_by_key<K, F>(self, limit: usize, key_fn: F) -> BufferedByKey<Self, K, F>
+    where
+        Self: Sized,
+        Self::Item: CompletionFuture,
+        K: Ord,
+        F: FnMut(&<Self::Item as CompletionFuture>::Output) -> K,
+    {
+        BufferedByKey::new(self, limit, key_fn)
+    }
+
     /// Fuse the stream so that it is guaranteed to continue to yield [`None`] when exhausted.
     ///
     /// If the stream is cancelled, it is also guaranteed to continue to yield [`None`].
     ///
+    /// Once the stream has been exhausted or cancelled it is dropped, so it will never be polled
+    /// again, and cancelling the fused stream does nothing.
+    ///
     /// # Examples
     ///
     /// ```
@@ -576,6 +1269,30 @@ pub trait CompletionStreamExt: CompletionStream {
     /// assert_eq!(stream.next().await, None);
     /// # });
     /// ```
+    ///
+    /// The inner stream isn't polled after it has ended:
+    ///
+    /// ```
+    /// use std::task::Poll;
+    ///
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut polls = 0;
+    /// let mut stream = stream::poll_fn(|_| {
+    ///     polls += 1;
+    ///     assert_eq!(polls, 1, "polled after completion");
+    ///     Poll::Ready(None::<()>)
+    /// })
+    /// .into_completion()
+    /// .fuse();
+    ///
+    /// assert_eq!(stream.next().await, None);
+    /// assert_eq!(stream.next().await, None);
+    /// assert!(stream.is_done());
+    /// # });
+    /// ```
     fn fuse(self) -> Fuse<Self>
     where
         Self: Sized,
@@ -611,6 +1328,128 @@ pub trait CompletionStreamExt: CompletionStream {
         Inspect::new(self, f)
     }
 
+    /// Do something with each successful item in a stream of [`Result`]s, passing the value on.
+    ///
+    /// The closure is not called for errors, at the end of the stream, or while it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut total = 0;
+    /// let items: Vec<_> = stream::iter(vec![Ok(1), Err("oh no"), Ok(2)])
+    ///     .into_completion()
+    ///     .inspect_ok(|x| total += x)
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(items, [Ok(1), Err("oh no"), Ok(2)]);
+    /// assert_eq!(total, 3);
+    /// # });
+    /// ```
+    fn inspect_ok<T, E, F>(self, f: F) -> InspectOk<Self, F>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+        F: FnMut(&T),
+    {
+        InspectOk::new(self, f)
+    }
+
+    /// Do something with each error in a stream of [`Result`]s, passing the value on.
+    ///
+    /// This is useful for logging errors in a pipeline while still propagating them. The closure
+    /// is not called for successful items, at the end of the stream, or while it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let items: Vec<_> = stream::iter(vec![Ok(1), Err("oh no"), Ok(2)])
+    ///     .into_completion()
+    ///     .inspect_err(|e| eprintln!("error: {}", e))
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(items, [Ok(1), Err("oh no"), Ok(2)]);
+    /// # });
+    /// ```
+    fn inspect_err<T, E, F>(self, f: F) -> InspectErr<Self, F>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+        F: FnMut(&E),
+    {
+        InspectErr::new(self, f)
+    }
+
+    /// Yield the successful items of a stream of [`Result`]s, ending at the first error.
+    ///
+    /// The error that ended the stream can then be retrieved with
+    /// [`SplitAtErr::into_error`] or [`SplitAtErr::error`]. This allows the successful items
+    /// to be processed separately from the error handling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let mut stream = stream::iter(vec![Ok(1), Ok(2), Err("oh no"), Ok(3)])
+    ///     .into_completion()
+    ///     .split_at_err();
+    ///
+    /// let mut total = 0;
+    /// while let Some(x) = stream.next().await {
+    ///     total += x;
+    /// }
+    /// assert_eq!(total, 3);
+    /// assert_eq!(stream.into_error(), Some("oh no"));
+    /// # });
+    /// ```
+    fn split_at_err<T, E>(self) -> SplitAtErr<Self, E>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+    {
+        SplitAtErr::new(self)
+    }
+
+    /// Skip errors in a stream of [`Result`]s, ending the stream once `limit` errors occur in a
+    /// row.
+    ///
+    /// Successful items are passed through, and reset the count of consecutive errors. Errors are
+    /// dropped until `limit` of them have occurred in a row, at which point the inner stream is
+    /// cancelled and the last error is yielded before the stream ends. This is useful for noisy
+    /// sources where occasional errors are expected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let items = vec![Ok(1), Err("a"), Ok(2), Err("b"), Err("c"), Ok(3)];
+    /// let stream = stream::iter(items).into_completion().tolerate_errors(2);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [Ok(1), Ok(2), Err("c")]);
+    /// # });
+    /// ```
+    fn tolerate_errors<T, E>(self, limit: usize) -> TolerateErrors<Self, E>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+    {
+        TolerateErrors::new(self, limit)
+    }
+
     // TODO: by_ref
 
     /// Collect all the items in the stream into a collection.
@@ -661,6 +1500,36 @@ pub trait CompletionStreamExt: CompletionStream {
         Collect::new(self)
     }
 
+    /// Collect all the successful items in a stream of [`Result`]s into a [`Vec`], stopping at
+    /// the first error.
+    ///
+    /// This is a specialized form of [`collect`](Self::collect) into a `Result<Vec<T>, E>`. The
+    /// items are kept in the order the stream produced them. When an error is encountered, the
+    /// collected items are discarded and the stream is cancelled before the error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec![Ok(3), Ok(1), Ok(2)]).into_completion();
+    /// assert_eq!(stream.collect_result::<_, ()>().await, Ok(vec![3, 1, 2]));
+    ///
+    /// let stream = stream::iter(vec![Ok(3), Err("oh no"), Ok(2)]).into_completion();
+    /// assert_eq!(stream.collect_result::<i32, _>().await, Err("oh no"));
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn collect_result<T, E>(self) -> CollectResult<Self, T, E>
+    where
+        Self: Sized + CompletionStream<Item = Result<T, E>>,
+    {
+        CollectResult::new(self)
+    }
+
     // TODO: partition
     // TODO: try_fold
     // TODO: try_for_each
@@ -693,6 +1562,43 @@ pub trait CompletionStreamExt: CompletionStream {
         Fold::new(self, init, f)
     }
 
+    /// Accumulate a value over a stream, mutating the accumulator in place.
+    ///
+    /// This is like [`fold`](Self::fold), but `f` takes a mutable reference to the accumulator
+    /// instead of taking and returning it by value. This avoids moving large accumulators on every
+    /// item. Once the stream is finished, it returns the accumulator. If the future is cancelled,
+    /// the accumulator is dropped along with it.
+    ///
+    /// # Examples
+    ///
+    /// Count how many times each word appears:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let words = stream::iter(vec!["a", "b", "a", "c", "a", "b"]).into_completion();
+    /// let frequencies = words
+    ///     .fold_mut(HashMap::new(), |map, word| *map.entry(word).or_insert(0) += 1)
+    ///     .await;
+    ///
+    /// assert_eq!(frequencies.len(), 3);
+    /// assert_eq!(frequencies["a"], 3);
+    /// assert_eq!(frequencies["b"], 2);
+    /// assert_eq!(frequencies["c"], 1);
+    /// # });
+    /// ```
+    fn fold_mut<T, F>(self, init: T, f: F) -> FoldMut<Self, F, T>
+    where
+        F: FnMut(&mut T, Self::Item),
+        Self: Sized,
+    {
+        FoldMut::new(self, init, f)
+    }
+
     /// Check if all the elements in the stream match a predicate.
     ///
     /// This is short-circuiting; it will stop once it finds a `false`.
@@ -1003,7 +1909,33 @@ pub trait CompletionStreamExt: CompletionStream {
         MinByKey::new(self, f)
     }
 
-    // TODO: unzip
+    /// Split a stream of pairs into two collections.
+    ///
+    /// Each collection starts out as its [`Default`] value, and the stream is drained once, with
+    /// the components of each pair [extended](Extend) into the respective collection. If the future
+    /// is cancelled, both partial collections are dropped along with the cancelled stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(vec![(1, 'a'), (2, 'b')]).into_completion();
+    /// let (numbers, letters): (Vec<i32>, String) = stream.unzip().await;
+    /// assert_eq!(numbers, [1, 2]);
+    /// assert_eq!(letters, "ab");
+    /// # });
+    /// ```
+    fn unzip<CA, CB>(self) -> Unzip<Self, CA, CB>
+    where
+        Self: Sized,
+        CA: Default,
+        CB: Default,
+    {
+        Unzip::new(self)
+    }
 
     /// Copy all of the elements in the stream.
     ///
@@ -1056,6 +1988,10 @@ pub trait CompletionStreamExt: CompletionStream {
     /// Instead of stopping at [`None`], this stream will start again, from the beginning. The
     /// returned stream will only return [`None`] when the underlying stream is empty.
     ///
+    /// Since a stream can only be consumed once, this requires the stream to be [`Clone`]: a copy
+    /// of the original stream is kept and cloned whenever the current one runs out. Cancelling the
+    /// returned stream cancels the copy that is currently being iterated over.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1073,6 +2009,9 @@ pub trait CompletionStreamExt: CompletionStream {
     /// assert_eq!(stream.next().await, Some(2));
     /// assert_eq!(stream.next().await, Some(0));
     /// // And so on...
+    ///
+    /// let stream = stream::iter(1..=3).into_completion().cycle().take(7);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [1, 2, 3, 1, 2, 3, 1]);
     /// # });
     /// ```
     fn cycle(self) -> Cycle<Self>
@@ -1099,13 +2038,18 @@ pub trait CompletionStreamExt: CompletionStream {
     /// use completion::{CompletionStreamExt, StreamExt};
     /// use futures_lite::stream;
     ///
+    /// # completion::future::block_on(completion::completion_async! {
     /// # let some_condition = true;
     /// // These streams are different types, but boxing them makes them the same type.
     /// let stream = if some_condition {
-    ///     stream::iter(2..18).into_completion().boxed()
+    ///     stream::iter(2..6).into_completion().boxed()
     /// } else {
     ///     stream::iter(vec![5, 3, 7, 8, 2]).into_completion().boxed()
     /// };
+    ///
+    /// // The boxed stream is itself a stream.
+    /// assert_eq!(stream.map(|x| x * 2).collect::<Vec<_>>().await, [4, 6, 8, 10]);
+    /// # });
     /// ```
     #[cfg(feature = "alloc")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
@@ -1143,12 +2087,12 @@ pub trait CompletionStreamExt: CompletionStream {
 }
 impl<T: CompletionStream + ?Sized> CompletionStreamExt for T {}
 
-/// A type-erased completion future.
+/// A type-erased completion stream.
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type BoxCompletionStream<'a, T> = Pin<Box<dyn CompletionStream<Item = T> + Send + 'a>>;
 
-/// A type-erased completion future that cannot be send across threads.
+/// A type-erased completion stream that cannot be send across threads.
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type LocalBoxCompletionStream<'a, T> = Pin<Box<dyn CompletionStream<Item = T> + 'a>>;
@@ -1157,6 +2101,11 @@ pub type LocalBoxCompletionStream<'a, T> = Pin<Box<dyn CompletionStream<Item = T
 pub trait StreamExt: Stream + Sized {
     /// Convert this stream into a [`CompletionStream`].
     ///
+    /// Regular streams are cancelled by dropping them, so the returned stream's
+    /// [`poll_cancel`](CompletionStream::poll_cancel) does nothing and returns
+    /// [`Poll::Ready`] immediately. This allows streams from the rest of the ecosystem to be used
+    /// with all the completion stream combinators.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1165,6 +2114,18 @@ pub trait StreamExt: Stream + Sized {
     ///
     /// let completion_stream = stream::iter(&[1, 1, 2, 3, 5]).into_completion();
     /// ```
+    ///
+    /// Use a regular stream with completion stream combinators:
+    ///
+    /// ```
+    /// use completion::{CompletionStreamExt, StreamExt};
+    /// use futures_lite::stream;
+    ///
+    /// # completion::future::block_on(completion::completion_async! {
+    /// let stream = stream::iter(1..=5).into_completion().filter(|n| n % 2 == 1);
+    /// assert_eq!(stream.collect::<Vec<_>>().await, [1, 3, 5]);
+    /// # });
+    /// ```
     fn into_completion(self) -> Adapter<Self> {
         Adapter(self)
     }
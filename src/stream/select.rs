@@ -0,0 +1,268 @@
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+/// Merge two streams into one, using a custom strategy to decide which stream to poll first.
+///
+/// Each time an item is requested from the returned stream, the strategy is called with a mutable
+/// reference to some state to determine which stream gets priority. When one of the streams is
+/// exhausted, the other stream continues to be polled by itself.
+///
+/// Since completion streams cannot be dropped until they have finished yielding an item, if the
+/// preferred stream is pending and the other stream yields an item, the returned stream waits for
+/// the preferred stream to finish yielding its item too and buffers it, before yielding either.
+///
+/// Cancelling the returned stream cancels both streams, dropping any buffered items.
+///
+/// # Examples
+///
+/// Always prefer the first stream:
+///
+/// ```
+/// use completion::{stream, CompletionStreamExt, StreamExt};
+/// use futures_lite::stream::iter;
+///
+/// # completion::future::block_on(completion::completion_async! {
+/// let a = iter(vec![1, 2, 3]).into_completion();
+/// let b = iter(vec![10, 20, 30]).into_completion();
+///
+/// let stream = stream::select_with_strategy(a, b, |_: &mut ()| stream::PollNext::Left);
+/// assert_eq!(stream.collect::<Vec<_>>().await, [1, 2, 3, 10, 20, 30]);
+/// # });
+/// ```
+///
+/// Alternate between the streams:
+///
+/// ```
+/// use completion::{stream, CompletionStreamExt, StreamExt};
+/// use futures_lite::stream::iter;
+///
+/// # completion::future::block_on(completion::completion_async! {
+/// let a = iter(vec![1, 2, 3]).into_completion();
+/// let b = iter(vec![10, 20, 30, 40]).into_completion();
+///
+/// let stream = stream::select_with_strategy(a, b, stream::PollNext::toggle);
+/// assert_eq!(stream.collect::<Vec<_>>().await, [1, 10, 2, 20, 3, 30, 40]);
+/// # });
+/// ```
+pub fn select_with_strategy<A, B, F, St>(a: A, b: B, strategy: F) -> SelectWithStrategy<A, B, F, St>
+where
+    A: CompletionStream,
+    B: CompletionStream<Item = A::Item>,
+    F: FnMut(&mut St) -> PollNext,
+    St: Default,
+{
+    SelectWithStrategy {
+        a,
+        b,
+        a_state: Side::Idle,
+        b_state: Side::Idle,
+        strategy,
+        state: St::default(),
+    }
+}
+
+/// Which stream [`select_with_strategy`] should poll first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PollNext {
+    /// Poll the first stream first.
+    #[default]
+    Left,
+    /// Poll the second stream first.
+    Right,
+}
+
+impl PollNext {
+    /// Toggle the value, returning the old value.
+    ///
+    /// This can be used as a strategy for [`select_with_strategy`] that alternates between the
+    /// streams.
+    #[allow(clippy::return_self_not_must_use)]
+    pub fn toggle(&mut self) -> Self {
+        let old = *self;
+        *self = match old {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        };
+        old
+    }
+}
+
+pin_project! {
+    /// Stream for [`select_with_strategy`].
+    #[derive(Debug)]
+    pub struct SelectWithStrategy<A: CompletionStream, B, F, St> {
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+        a_state: Side<A::Item>,
+        b_state: Side<A::Item>,
+        strategy: F,
+        state: St,
+    }
+}
+
+/// The state of one of the streams in a `SelectWithStrategy`.
+#[derive(Debug)]
+enum Side<T> {
+    /// The stream is not in the middle of yielding an item.
+    Idle,
+    /// The stream is in the middle of yielding an item.
+    Polling,
+    /// The stream has yielded an item that hasn't been returned yet.
+    Buffered(T),
+    /// The stream is exhausted or cancelled.
+    Done,
+}
+
+impl<T> Side<T> {
+    /// Poll the stream if it should be polled.
+    unsafe fn poll<S>(&mut self, stream: Pin<&mut S>, cx: &mut Context<'_>)
+    where
+        S: CompletionStream<Item = T>,
+    {
+        if let Self::Idle | Self::Polling = self {
+            *self = match stream.poll_next(cx) {
+                Poll::Ready(Some(item)) => Self::Buffered(item),
+                Poll::Ready(None) => Self::Done,
+                Poll::Pending => Self::Polling,
+            };
+        }
+    }
+
+    /// Take the buffered item, if there is one.
+    fn take(&mut self) -> Option<T> {
+        if let Self::Buffered(_) = self {
+            match mem::replace(self, Self::Idle) {
+                Self::Buffered(item) => Some(item),
+                _ => unreachable!(),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Cancel the stream, returning whether it has finished cancelling.
+    unsafe fn poll_cancel<S: CompletionStream>(
+        &mut self,
+        stream: Pin<&mut S>,
+        cx: &mut Context<'_>,
+    ) -> bool {
+        if let Self::Done = self {
+            return true;
+        }
+        if stream.poll_cancel(cx).is_ready() {
+            *self = Self::Done;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn size_hint<S: CompletionStream>(&self, stream: &S) -> (usize, Option<usize>) {
+        match self {
+            Self::Idle | Self::Polling => stream.size_hint(),
+            Self::Buffered(_) => {
+                let (lower, upper) = stream.size_hint();
+                (
+                    lower.saturating_add(1),
+                    upper.and_then(|upper| upper.checked_add(1)),
+                )
+            }
+            Self::Done => (0, Some(0)),
+        }
+    }
+}
+
+impl<A, B, F, St> CompletionStream for SelectWithStrategy<A, B, F, St>
+where
+    A: CompletionStream,
+    B: CompletionStream<Item = A::Item>,
+    F: FnMut(&mut St) -> PollNext,
+{
+    type Item = A::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let first = (this.strategy)(this.state);
+
+        let (a_state, b_state) = (this.a_state, this.b_state);
+        let (first_state, second_state) = match first {
+            PollNext::Left => {
+                a_state.poll(this.a, cx);
+                // Don't start yielding a new item from the other stream if we already have one.
+                if !(matches!(a_state, Side::Buffered(_)) && matches!(b_state, Side::Idle)) {
+                    b_state.poll(this.b, cx);
+                }
+                (a_state, b_state)
+            }
+            PollNext::Right => {
+                b_state.poll(this.b, cx);
+                if !(matches!(b_state, Side::Buffered(_)) && matches!(a_state, Side::Idle)) {
+                    a_state.poll(this.a, cx);
+                }
+                (b_state, a_state)
+            }
+        };
+
+        // The streams must not be left in the middle of yielding an item.
+        if let Side::Polling = first_state {
+            return Poll::Pending;
+        }
+        if let Side::Polling = second_state {
+            return Poll::Pending;
+        }
+
+        if let Some(item) = first_state.take().or_else(|| second_state.take()) {
+            return Poll::Ready(Some(item));
+        }
+
+        // Both streams were polled, and neither is pending or yielded an item.
+        debug_assert!(matches!(
+            (first_state, second_state),
+            (Side::Done, Side::Done)
+        ));
+        Poll::Ready(None)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let a_done = this.a_state.poll_cancel(this.a, cx);
+        let b_done = this.b_state.poll_cancel(this.b, cx);
+        if a_done && b_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a_state.size_hint(&self.a);
+        let (b_lower, b_upper) = self.b_state.size_hint(&self.b);
+        (
+            a_lower.saturating_add(b_lower),
+            a_upper.and_then(|a_upper| a_upper.checked_add(b_upper?)),
+        )
+    }
+}
+
+impl<A, B, F, St> Stream for SelectWithStrategy<A, B, F, St>
+where
+    A: CompletionStream + Stream<Item = <A as CompletionStream>::Item>,
+    B: CompletionStream<Item = <A as CompletionStream>::Item>
+        + Stream<Item = <A as CompletionStream>::Item>,
+    F: FnMut(&mut St) -> PollNext,
+{
+    type Item = <A as CompletionStream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe { CompletionStream::poll_next(self, cx) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        CompletionStream::size_hint(self)
+    }
+}
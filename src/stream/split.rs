@@ -0,0 +1,199 @@
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+
+use completion_core::{CompletionSink, CompletionStream};
+
+/// Lock the shared resource. The lock is only ever held for the duration of a single poll, so
+/// this never blocks for long.
+fn lock<T>(shared: &Mutex<T>) -> Pin<MutexGuard<'_, T>> {
+    let guard = shared
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    // SAFETY: The value is never moved out of the `Arc<Mutex<T>>`.
+    unsafe { Pin::new_unchecked(guard) }
+}
+
+/// The sink half of a value split by
+/// [`CompletionStreamExt::split`](crate::CompletionStreamExt::split).
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless you use them"]
+pub struct SplitSink<S> {
+    shared: Arc<Mutex<S>>,
+}
+
+/// The stream half of a value split by
+/// [`CompletionStreamExt::split`](crate::CompletionStreamExt::split).
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you use them"]
+pub struct SplitStream<S> {
+    shared: Arc<Mutex<S>>,
+}
+
+pub(crate) fn split<S>(resource: S) -> (SplitSink<S>, SplitStream<S>) {
+    let shared = Arc::new(Mutex::new(resource));
+    (
+        SplitSink {
+            shared: Arc::clone(&shared),
+        },
+        SplitStream { shared },
+    )
+}
+
+impl<S> SplitSink<S> {
+    /// Check whether this sink and the stream come from the same value.
+    #[must_use]
+    pub fn is_pair_of(&self, stream: &SplitStream<S>) -> bool {
+        Arc::ptr_eq(&self.shared, &stream.shared)
+    }
+}
+
+impl<S> SplitStream<S> {
+    /// Check whether this stream and the sink come from the same value.
+    #[must_use]
+    pub fn is_pair_of(&self, sink: &SplitSink<S>) -> bool {
+        sink.is_pair_of(self)
+    }
+}
+
+impl<S: CompletionSink<Item>, Item> CompletionSink<Item> for SplitSink<S> {
+    type Error = S::Error;
+
+    unsafe fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        lock(&self.shared).as_mut().poll_ready(cx)
+    }
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        lock(&self.shared).as_mut().start_send(item)
+    }
+    unsafe fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        lock(&self.shared).as_mut().poll_flush(cx)
+    }
+    unsafe fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        lock(&self.shared).as_mut().poll_close(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        CompletionSink::poll_cancel(lock(&self.shared).as_mut(), cx)
+    }
+}
+
+impl<S: CompletionStream> CompletionStream for SplitStream<S> {
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        lock(&self.shared).as_mut().poll_next(cx)
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        CompletionStream::poll_cancel(lock(&self.shared).as_mut(), cx)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        lock(&self.shared).size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+    use std::convert::Infallible;
+    use std::task::Waker;
+
+    /// An in-memory resource that yields every value sent to it.
+    #[derive(Default)]
+    struct Echo {
+        buffer: VecDeque<i32>,
+        closed: bool,
+        reader: Option<Waker>,
+    }
+
+    impl CompletionStream for Echo {
+        type Item = i32;
+
+        unsafe fn poll_next(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item>> {
+            if let Some(item) = self.buffer.pop_front() {
+                Poll::Ready(Some(item))
+            } else if self.closed {
+                Poll::Ready(None)
+            } else {
+                self.reader = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            Poll::Ready(())
+        }
+    }
+
+    impl CompletionSink<i32> for Echo {
+        type Error = Infallible;
+
+        unsafe fn poll_ready(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn start_send(mut self: Pin<&mut Self>, item: i32) -> Result<(), Self::Error> {
+            self.buffer.push_back(item);
+            if let Some(reader) = self.reader.take() {
+                reader.wake();
+            }
+            Ok(())
+        }
+        unsafe fn poll_flush(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_close(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            self.closed = true;
+            if let Some(reader) = self.reader.take() {
+                reader.wake();
+            }
+            Poll::Ready(Ok(()))
+        }
+        unsafe fn poll_cancel(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn echo() {
+        let (mut sink, mut stream) = split(Echo::default());
+        assert!(sink.is_pair_of(&stream));
+        let cx = &mut crate::noop_cx();
+
+        unsafe {
+            assert_eq!(Pin::new(&mut stream).poll_next(cx), Poll::Pending);
+
+            for i in 0..3 {
+                assert_eq!(Pin::new(&mut sink).poll_ready(cx), Poll::Ready(Ok(())));
+                Pin::new(&mut sink).start_send(i).unwrap();
+            }
+            assert_eq!(Pin::new(&mut stream).poll_next(cx), Poll::Ready(Some(0)));
+            assert_eq!(Pin::new(&mut stream).poll_next(cx), Poll::Ready(Some(1)));
+
+            assert_eq!(Pin::new(&mut sink).poll_close(cx), Poll::Ready(Ok(())));
+            assert_eq!(Pin::new(&mut stream).poll_next(cx), Poll::Ready(Some(2)));
+            assert_eq!(Pin::new(&mut stream).poll_next(cx), Poll::Ready(None));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Utilities for testing completion streams.
+//!
+//! # Examples
+//!
+//! Check that [`take`](crate::CompletionStreamExt::take) cancels its inner stream:
+//!
+//! ```
+//! use completion::{future, stream::test::CancelAudited, CompletionStreamExt, StreamExt};
+//! use completion::completion_async;
+//! use futures_lite::stream;
+//!
+//! # future::block_on(completion_async! {
+//! let mut audited = CancelAudited::new(stream::pending::<i32>().into_completion());
+//! let mut taken = (&mut audited).take(2);
+//! assert_eq!(future::race((taken.next(), completion_async!(None))).await, None);
+//! assert!(audited.was_cancelled());
+//! # });
+//! ```
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use completion_core::CompletionStream;
+use pin_project_lite::pin_project;
+
+use crate::future::test::Guard;
+
+pin_project! {
+    /// A stream that panics if it is dropped while its inner stream is in the middle of yielding
+    /// an item.
+    ///
+    /// A stream is considered to be in the middle of yielding an item once it has returned
+    /// [`Poll::Pending`] from [`poll_next`](CompletionStream::poll_next), until it either yields
+    /// an item or [`poll_cancel`](CompletionStream::poll_cancel) returns [`Poll::Ready`].
+    /// Dropping it at that point is a violation of the completion stream contract that this
+    /// wrapper catches; dropping it between items is allowed.
+    ///
+    /// Like [`AssertCancelled`](crate::future::test::AssertCancelled), this deliberately doesn't
+    /// implement [`Stream`](futures_core::Stream).
+    #[derive(Debug, Clone)]
+    #[must_use = "streams do nothing unless you use them"]
+    pub struct CancelAudited<S> {
+        #[pin]
+        stream: S,
+        guard: Guard,
+    }
+}
+
+impl<S> CancelAudited<S> {
+    /// Wrap a stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            guard: Guard::new("stream"),
+        }
+    }
+
+    /// Whether the inner stream has been cancelled.
+    #[must_use]
+    pub fn was_cancelled(&self) -> bool {
+        self.guard.was_cancelled()
+    }
+}
+
+impl<S: CompletionStream> CompletionStream for CancelAudited<S> {
+    type Item = S::Item;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let poll = this.stream.poll_next(cx);
+        this.guard.polled(poll.is_pending());
+        poll
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+        let poll = this.stream.poll_cancel(cx);
+        if poll.is_ready() {
+            this.guard.cancel();
+        }
+        poll
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures_lite::stream;
+
+    use crate::{CompletionStreamExt, StreamExt};
+
+    // Cancellation and dropping while running are covered by the tests of the shared guard in
+    // `future::test`.
+
+    #[test]
+    fn dropped_between_items() {
+        let mut stream = CancelAudited::new(stream::iter(0..3).into_completion());
+        let poll = unsafe { stream.poll_next(&mut crate::noop_cx()) };
+        assert_eq!(poll, Poll::Ready(Some(0)));
+        assert!(!stream.was_cancelled());
+    }
+}
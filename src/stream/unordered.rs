@@ -0,0 +1,399 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use atomic_waker::AtomicWaker;
+use completion_core::{CompletionFuture, CompletionStream};
+
+/// A set of futures that yields their outputs in the order they complete.
+///
+/// Futures are added with [`push`](Self::push), and only the futures that have issued wakeups are
+/// polled. While the set is open, an empty set doesn't end; it waits for more futures to be
+/// pushed.
+///
+/// # Shutting down
+///
+/// There are two ways to shut the set down:
+///
+/// - [`close`](Self::close) stops the set from accepting new futures, but lets the ones it
+///   already contains run to completion. The stream keeps yielding their outputs, and ends once
+///   they have all finished.
+/// - [`cancel_all`](Self::cancel_all) also stops the set from accepting new futures, but instead
+///   cancels every future it contains. The stream yields no more items, and ends once every
+///   future has finished cancelling.
+///
+/// Cancelling the stream itself with [`poll_cancel`](CompletionStream::poll_cancel) behaves like
+/// `cancel_all`.
+///
+/// Since completion streams can be dropped between items, the set only yields outputs while none
+/// of the futures it has started polling are still running. The outputs of futures that complete
+/// while others are running are buffered until then, so items tend to arrive in batches.
+///
+/// # Examples
+///
+/// ```
+/// use completion::{CompletionStreamExt, completion_async, completion_async_move};
+/// use completion::stream::Unordered;
+///
+/// # completion::future::block_on(completion_async! {
+/// let mut set = Unordered::new();
+/// for i in 1..=2 {
+///     set.push(completion_async_move!(i));
+/// }
+/// set.close();
+///
+/// let mut outputs: Vec<_> = set.collect().await;
+/// outputs.sort_unstable();
+/// assert_eq!(outputs, [1, 2]);
+/// # });
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct Unordered<F: CompletionFuture> {
+    slots: Vec<Slot<F>>,
+    // Outputs of completed futures waiting for the running futures to finish.
+    outputs: VecDeque<F::Output>,
+    waker: Arc<AtomicWaker>,
+    state: State,
+}
+
+impl<F: CompletionFuture> Unpin for Unordered<F> {}
+
+struct Slot<F> {
+    fut: Pin<Box<F>>,
+    // Whether the future has been polled, and so must not be dropped until it completes.
+    started: bool,
+    wake: Arc<SlotWake>,
+    waker: Waker,
+}
+
+/// The waker of a single future in the set.
+struct SlotWake {
+    woken: AtomicBool,
+    parent: Arc<AtomicWaker>,
+}
+
+impl Wake for SlotWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.parent.wake();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// New futures can be pushed.
+    Open,
+    /// The remaining futures are being driven to completion.
+    Closed,
+    /// The remaining futures are being cancelled.
+    Cancelling,
+}
+
+impl<F: CompletionFuture> Unordered<F> {
+    /// Create a new empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            outputs: VecDeque::new(),
+            waker: Arc::new(AtomicWaker::new()),
+            state: State::Open,
+        }
+    }
+
+    /// Add a future to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set has been closed with [`close`](Self::close) or
+    /// [`cancel_all`](Self::cancel_all).
+    pub fn push(&mut self, fut: F) {
+        assert_eq!(self.state, State::Open, "pushed to a closed `Unordered`");
+
+        let wake = Arc::new(SlotWake {
+            woken: AtomicBool::new(true),
+            parent: Arc::clone(&self.waker),
+        });
+        self.slots.push(Slot {
+            fut: Box::pin(fut),
+            started: false,
+            waker: Waker::from(Arc::clone(&wake)),
+            wake,
+        });
+        self.waker.wake();
+    }
+
+    /// Stop accepting new futures, letting the ones already in the set run to completion.
+    ///
+    /// Once they have all completed, the stream ends.
+    pub fn close(&mut self) {
+        if self.state == State::Open {
+            self.state = State::Closed;
+            self.waker.wake();
+        }
+    }
+
+    /// Stop accepting new futures, and cancel all the ones already in the set.
+    ///
+    /// The stream won't yield any more items, and ends once all the futures have been cancelled.
+    pub fn cancel_all(&mut self) {
+        if self.state != State::Cancelling {
+            self.state = State::Cancelling;
+            self.outputs.clear();
+            // Every future has to be cancelled, not only the ones that were woken.
+            for slot in &self.slots {
+                slot.wake.woken.store(true, Ordering::Relaxed);
+            }
+            self.waker.wake();
+        }
+    }
+
+    /// Whether the set has been closed with [`close`](Self::close) or
+    /// [`cancel_all`](Self::cancel_all).
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.state != State::Open
+    }
+
+    /// Get the number of futures in the set, including completed ones whose outputs haven't been
+    /// yielded yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len() + self.outputs.len()
+    }
+
+    /// Whether the set contains no futures.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty() && self.outputs.is_empty()
+    }
+
+    /// Like [`poll_next`](CompletionStream::poll_next), but yields outputs as soon as they are
+    /// available, even while other futures are still running.
+    ///
+    /// # Safety
+    ///
+    /// As well as the usual requirements of `poll_next`, the set must not be dropped until it is
+    /// empty or has been cancelled, as it might contain running futures after yielding an item.
+    pub(crate) unsafe fn poll_next_eager(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<F::Output>> {
+        self.waker.register(cx.waker());
+
+        if self.state == State::Cancelling {
+            return self.poll_cancel_all().map(|()| None);
+        }
+        if let Some(output) = self.outputs.pop_front() {
+            return Poll::Ready(Some(output));
+        }
+        self.poll_slots()
+    }
+
+    /// Poll the woken futures until one completes.
+    unsafe fn poll_slots(&mut self) -> Poll<Option<F::Output>> {
+        let mut i = 0;
+        while i < self.slots.len() {
+            let slot = &mut self.slots[i];
+            if slot.wake.woken.swap(false, Ordering::Acquire) {
+                slot.started = true;
+                let cx = &mut Context::from_waker(&slot.waker);
+                if let Poll::Ready(output) = slot.fut.as_mut().poll(cx) {
+                    self.slots.swap_remove(i);
+                    return Poll::Ready(Some(output));
+                }
+            }
+            i += 1;
+        }
+
+        if self.slots.is_empty() && self.state == State::Closed {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Drive the cancellation of all the futures in the set.
+    unsafe fn poll_cancel_all(&mut self) -> Poll<()> {
+        let mut i = 0;
+        while i < self.slots.len() {
+            let slot = &mut self.slots[i];
+            if !slot.started {
+                self.slots.swap_remove(i);
+                continue;
+            }
+            if slot.wake.woken.swap(false, Ordering::Acquire) {
+                let cx = &mut Context::from_waker(&slot.waker);
+                if slot.fut.as_mut().poll_cancel(cx).is_ready() {
+                    self.slots.swap_remove(i);
+                    continue;
+                }
+            }
+            i += 1;
+        }
+
+        if self.slots.is_empty() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<F: CompletionFuture> CompletionStream for Unordered<F> {
+    type Item = F::Output;
+
+    unsafe fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.waker.register(cx.waker());
+
+        if this.state == State::Cancelling {
+            return this.poll_cancel_all().map(|()| None);
+        }
+
+        if this.outputs.is_empty() || this.slots.iter().any(|slot| slot.started) {
+            while let Poll::Ready(Some(output)) = this.poll_slots() {
+                this.outputs.push_back(output);
+            }
+            if this.slots.iter().any(|slot| slot.started) {
+                return Poll::Pending;
+            }
+        }
+
+        match this.outputs.pop_front() {
+            Some(output) => Poll::Ready(Some(output)),
+            None if this.slots.is_empty() && this.state == State::Closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+    unsafe fn poll_cancel(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        this.waker.register(cx.waker());
+        this.cancel_all();
+        this.poll_cancel_all()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            State::Open => (self.len(), None),
+            State::Closed => (self.len(), Some(self.len())),
+            State::Cancelling => (0, Some(0)),
+        }
+    }
+}
+
+impl<F: CompletionFuture> Default for Unordered<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: CompletionFuture> Debug for Unordered<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Unordered")
+            .field("len", &self.len())
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::vec::Vec;
+
+    use crate::future::{block_on, ready, CompletionFutureExt, CountPolls, PollCounts, Ready};
+    use crate::test_utils::Yield;
+    use crate::CompletionStreamExt;
+
+    fn mixed_latency() -> (
+        Unordered<CountPolls<Yield<Ready<usize>>>>,
+        Vec<Arc<PollCounts>>,
+    ) {
+        let mut set = Unordered::new();
+        let mut counts = Vec::new();
+        for &yields in &[3, 1, 2] {
+            let (fut, count) = Yield::new(yields, ready(yields)).count_polls();
+            set.push(fut);
+            counts.push(count);
+        }
+        (set, counts)
+    }
+
+    #[test]
+    fn close_drains() {
+        let (mut set, counts) = mixed_latency();
+        set.close();
+        assert!(set.is_closed());
+
+        let mut outputs: Vec<_> = block_on((&mut set).collect());
+        outputs.sort_unstable();
+        assert_eq!(outputs, [1, 2, 3]);
+        assert!(set.is_empty());
+        assert!(counts.iter().all(|counts| counts.cancels() == 0));
+    }
+
+    #[test]
+    fn waits_for_running_futures() {
+        let (mut set, counts) = mixed_latency();
+        set.close();
+
+        let cx = &mut crate::noop_cx();
+        let first = loop {
+            if let Poll::Ready(output) = unsafe { CompletionStreamExt::poll_next(&mut set, cx) } {
+                break output;
+            }
+        };
+        // Nothing is yielded until the slowest future has completed.
+        assert_eq!(counts[0].polls(), 4);
+        assert_eq!(set.len(), 2);
+
+        let mut outputs: Vec<_> = first
+            .into_iter()
+            .chain(block_on(set.collect::<Vec<_>>()))
+            .collect();
+        outputs.sort_unstable();
+        assert_eq!(outputs, [1, 2, 3]);
+    }
+
+    #[test]
+    fn cancel_all() {
+        let (mut set, counts) = mixed_latency();
+
+        // Start all the futures.
+        let cx = &mut crate::noop_cx();
+        assert!(unsafe { CompletionStreamExt::poll_next(&mut set, cx) }.is_pending());
+
+        set.cancel_all();
+        let outputs: Vec<_> = block_on((&mut set).collect());
+        assert!(outputs.is_empty());
+        assert!(set.is_empty());
+        assert!(counts.iter().all(|counts| counts.cancels() > 0));
+    }
+
+    #[test]
+    fn cancel_all_unstarted() {
+        let (mut set, counts) = mixed_latency();
+        set.cancel_all();
+        let outputs: Vec<_> = block_on((&mut set).collect());
+        assert!(outputs.is_empty());
+        assert!(counts.iter().all(|counts| counts.polls() == 0));
+    }
+
+    #[test]
+    #[should_panic = "pushed to a closed `Unordered`"]
+    fn push_after_close() {
+        let mut set = Unordered::new();
+        set.close();
+        set.push(ready(()));
+    }
+}
@@ -0,0 +1,75 @@
+//! Check that futures can be boxed in custom allocators.
+#![cfg(all(feature = "allocator_api", feature = "macro"))]
+#![feature(allocator_api)]
+
+use std::alloc::{AllocError, Allocator, Layout};
+use std::cell::UnsafeCell;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use completion::{
+    completion_async, completion_async_move, future, CompletionFuture, CompletionFutureExt,
+};
+
+/// A bump allocator that never frees memory.
+struct Bump {
+    memory: UnsafeCell<[u8; 4096]>,
+    used: AtomicUsize,
+    allocations: AtomicUsize,
+}
+unsafe impl Sync for Bump {}
+
+impl Bump {
+    /// Create a new allocator for a single test. It is leaked since pinned boxes need `'static`
+    /// allocators.
+    fn new() -> &'static Self {
+        Box::leak(Box::new(Self {
+            memory: UnsafeCell::new([0; 4096]),
+            used: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+        }))
+    }
+}
+
+unsafe impl Allocator for &'static Bump {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.memory.get().cast::<u8>();
+        let mut start = 0;
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                start = (base as usize + used).div_ceil(layout.align()) * layout.align()
+                    - base as usize;
+                Some(start + layout.size()).filter(|&end| end <= 4096)
+            })
+            .map_err(|_| AllocError)?;
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        let ptr = unsafe { base.add(start) };
+        Ok(NonNull::slice_from_raw_parts(
+            NonNull::new(ptr).unwrap(),
+            layout.size(),
+        ))
+    }
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+#[test]
+fn boxed_in_bump() {
+    let bump = Bump::new();
+    let futures: Vec<_> = (0..3)
+        .map(|i| completion_async_move!(i * 2).boxed_in(bump))
+        .collect();
+    assert_eq!(bump.allocations.load(Ordering::SeqCst), 3);
+
+    let outputs: Vec<_> = futures.into_iter().map(future::block_on).collect();
+    assert_eq!(outputs, [0, 2, 4]);
+}
+
+#[test]
+fn boxed_in_is_completion_future() {
+    fn assert_completion_future<F: CompletionFuture<Output = i32>>(_: &F) {}
+
+    let bump = Bump::new();
+    let fut = completion_async!(5).boxed_in(bump);
+    assert_completion_future(&fut);
+    assert_eq!(future::block_on(fut), 5);
+}
@@ -0,0 +1,33 @@
+//! Check that the joining futures can be used from `no_std` crates with only the `alloc` feature.
+#![cfg(feature = "alloc")]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use completion::future::{self, CompletionFuture};
+
+fn _zip() -> impl CompletionFuture<Output = (i32, &'static str)> {
+    future::zip((future::ready(5), future::ready("hello")))
+}
+
+fn _try_zip() -> impl CompletionFuture<Output = Result<(i32, i32), ()>> {
+    future::try_zip((future::ready(Ok(5)), future::ready(Ok(6))))
+}
+
+fn _race() -> impl CompletionFuture<Output = i32> {
+    future::race((future::ready(5), future::pending()))
+}
+
+fn _race_ok() -> impl CompletionFuture<Output = Result<i32, (i32, i32)>> {
+    future::race_ok((future::ready(Err(5)), future::ready(Err(6))))
+}
+
+fn _zip_array() -> impl CompletionFuture<Output = [i32; 3]> {
+    future::zip_array([future::ready(1), future::ready(2), future::ready(3)])
+}
+
+fn _zip_all(futures: Vec<future::Ready<i32>>) -> future::ZipAll<future::Ready<i32>> {
+    future::zip_all(futures)
+}